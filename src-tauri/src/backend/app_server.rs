@@ -1,23 +1,28 @@
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::{Child, ChildStdin, Command};
-use tokio::sync::{mpsc, oneshot, Mutex};
+use tokio::sync::{mpsc, oneshot, watch, Mutex};
+use tokio::task::JoinHandle;
 use tokio::time::timeout;
 
 use crate::backend::events::{AppServerEvent, EventSink};
+use crate::backend::workspace_lock::{acquire_workspace_lock, WorkspaceLockGuard};
 use crate::shared::process_core::tokio_command;
 use crate::codex::args::apply_codex_args;
-use crate::types::WorkspaceEntry;
+use crate::types::{
+    ModelInfo, PathDiagnostics, PendingRequestInfo, SessionInfo, SessionState, ThreadPreferences,
+    WorkspaceEntry,
+};
 
-fn extract_thread_id(value: &Value) -> Option<String> {
+pub(crate) fn extract_thread_id(value: &Value) -> Option<String> {
     let params = value.get("params")?;
 
     params
@@ -34,17 +39,153 @@ fn extract_thread_id(value: &Value) -> Option<String> {
         })
 }
 
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn pending_request_info(
+    id: u64,
+    method: Option<&String>,
+    enqueued_at_ms: u64,
+    now_ms: u64,
+) -> PendingRequestInfo {
+    PendingRequestInfo {
+        id,
+        method: method.cloned().unwrap_or_else(|| "unknown".to_string()),
+        enqueued_at_ms,
+        age_ms: now_ms.saturating_sub(enqueued_at_ms),
+    }
+}
+
+const MAX_LOG_EVENTS_PER_WINDOW: usize = 50;
+const LOG_EVENT_WINDOW: Duration = Duration::from_secs(1);
+const DEFAULT_PING_INTERVAL: Duration = Duration::from_secs(30);
+const DEFAULT_STALL_TIMEOUT: Duration = Duration::from_secs(60);
+/// Upper bound for `pingIntervalSecs` and `stallTimeoutSecs`.
+pub(crate) const MAX_KEEPALIVE_SECS: u64 = 3600;
+/// Threads tracked in `thread_activity`; the least recently active is evicted past this.
+const MAX_TRACKED_THREADS: usize = 512;
+
+/// Caps how many log-style events are emitted per window so a noisy child process
+/// cannot flood the frontend. Dropped lines are reported once the window rolls over.
+struct EventRateLimiter {
+    max_per_window: usize,
+    window: Duration,
+    window_start: Instant,
+    emitted: usize,
+    suppressed: usize,
+}
+
+impl EventRateLimiter {
+    fn new(max_per_window: usize, window: Duration) -> Self {
+        Self {
+            max_per_window,
+            window,
+            window_start: Instant::now(),
+            emitted: 0,
+            suppressed: 0,
+        }
+    }
+
+    /// Returns whether the event may be emitted, plus the number of events dropped in
+    /// the previous window when a new window starts.
+    fn check(&mut self, now: Instant) -> (bool, usize) {
+        let mut flushed = 0;
+        if now.duration_since(self.window_start) >= self.window {
+            flushed = std::mem::take(&mut self.suppressed);
+            self.window_start = now;
+            self.emitted = 0;
+        }
+        if self.emitted < self.max_per_window {
+            self.emitted += 1;
+            (true, flushed)
+        } else {
+            self.suppressed += 1;
+            (false, flushed)
+        }
+    }
+
+    fn take_suppressed(&mut self) -> usize {
+        std::mem::take(&mut self.suppressed)
+    }
+}
+
+fn suppressed_event(method: &str, count: usize) -> Value {
+    json!({
+        "method": method,
+        "params": {
+            "message": format!("suppressed {count} lines"),
+            "suppressed": count,
+        },
+    })
+}
+
+pub(crate) struct PendingRequest {
+    pub(crate) sender: oneshot::Sender<Result<Value, String>>,
+    pub(crate) enqueued_at_ms: u64,
+}
+
 pub(crate) struct WorkspaceSession {
     pub(crate) entry: WorkspaceEntry,
     pub(crate) child: Mutex<Child>,
     pub(crate) stdin: Mutex<ChildStdin>,
-    pub(crate) pending: Mutex<HashMap<u64, oneshot::Sender<Value>>>,
+    pub(crate) pending: Mutex<HashMap<u64, PendingRequest>>,
+    pub(crate) request_id_to_method: Mutex<HashMap<u64, String>>,
     pub(crate) next_id: AtomicU64,
     /// Callbacks for background threads - events for these threadIds are sent through the channel
     pub(crate) background_thread_callbacks: Mutex<HashMap<String, mpsc::UnboundedSender<Value>>>,
+    /// Streamed assistant text for each thread's in-flight turn, kept so a reloaded UI can repaint it
+    pub(crate) turn_output: Mutex<HashMap<String, String>>,
+    /// Result of the `initialize` handshake, used to answer capability queries
+    pub(crate) initialize_result: Mutex<Option<Value>>,
+    /// When our `initialized` notification went out after a successful `initialize`; the
+    /// app-server doesn't acknowledge it, so this marks the handshake as complete
+    pub(crate) initialized_at: Mutex<Option<Instant>>,
+    /// Unix ms of the last message read from the app-server; 0 until the first one arrives
+    pub(crate) last_event_at_ms: AtomicU64,
+    pub(crate) started_at: Instant,
+    /// Approval policy set at runtime; applies to turns and threads started afterwards
+    pub(crate) approval_policy: Mutex<Option<String>>,
+    /// Normalized `model/list` result, fetched once per session
+    pub(crate) models: Mutex<Option<Vec<ModelInfo>>>,
+    /// Model/effort per thread id, so follow-up turns stay on the same model
+    pub(crate) thread_preferences: Mutex<HashMap<String, ThreadPreferences>>,
+    /// Methods the server answered with method-not-found, so fallbacks skip them next time
+    unsupported_methods: Mutex<HashSet<String>>,
+    /// Mirrors `entry.monitor_only` so toggling it applies without a respawn
+    pub(crate) monitor_only: AtomicBool,
+    /// Unix ms of the last notification forwarded for each thread id
+    pub(crate) thread_activity: Mutex<HashMap<String, u64>>,
+    /// Keepalive ping task; aborted when the session is dropped
+    keepalive: std::sync::Mutex<Option<JoinHandle<()>>>,
+    /// Set once the session is removed from the sessions map; subscribers also see the
+    /// channel close when the session is dropped
+    removed: watch::Sender<bool>,
+    /// Held for the session's lifetime so other monitor processes can't open the workspace
+    _workspace_lock: WorkspaceLockGuard,
+}
+
+impl Drop for WorkspaceSession {
+    fn drop(&mut self) {
+        if let Some(task) = self.keepalive.get_mut().ok().and_then(Option::take) {
+            task.abort();
+        }
+    }
 }
 
 impl WorkspaceSession {
+    /// Tells tasks tied to this session, such as file watchers, that it has been removed.
+    pub(crate) fn mark_removed(&self) {
+        self.removed.send_replace(true);
+    }
+
+    pub(crate) fn subscribe_removed(&self) -> watch::Receiver<bool> {
+        self.removed.subscribe()
+    }
+
     async fn write_message(&self, value: Value) -> Result<(), String> {
         let mut stdin = self.stdin.lock().await;
         let mut line = serde_json::to_string(&value).map_err(|e| e.to_string())?;
@@ -55,13 +196,72 @@ impl WorkspaceSession {
             .map_err(|e| e.to_string())
     }
 
-    pub(crate) async fn send_request(&self, method: &str, params: Value) -> Result<Value, String> {
+    async fn register_request(
+        &self,
+        method: &str,
+    ) -> (u64, oneshot::Receiver<Result<Value, String>>) {
         let id = self.next_id.fetch_add(1, Ordering::SeqCst);
         let (tx, rx) = oneshot::channel();
-        self.pending.lock().await.insert(id, tx);
+        self.request_id_to_method
+            .lock()
+            .await
+            .insert(id, method.to_string());
+        self.pending.lock().await.insert(
+            id,
+            PendingRequest {
+                sender: tx,
+                enqueued_at_ms: now_ms(),
+            },
+        );
+        (id, rx)
+    }
+
+    pub(crate) async fn send_request(&self, method: &str, params: Value) -> Result<Value, String> {
+        let (id, rx) = self.register_request(method).await;
         self.write_message(json!({ "id": id, "method": method, "params": params }))
             .await?;
-        rx.await.map_err(|_| "request canceled".to_string())
+        rx.await.map_err(|_| "request canceled".to_string())?
+    }
+
+    /// Like `send_request`, but returns `None` instead of sending when the method is known to
+    /// be unsupported, and remembers a method-not-found reply so later calls skip it too.
+    pub(crate) async fn send_request_if_supported(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<Option<Value>, String> {
+        if self.supports_method(method).await == Some(false) {
+            return Ok(None);
+        }
+        let response = self.send_request(method, params).await?;
+        if is_method_not_found(&response) {
+            self.unsupported_methods
+                .lock()
+                .await
+                .insert(method.to_string());
+            return Ok(None);
+        }
+        Ok(Some(response))
+    }
+
+    /// Sends `method` and reports whether any reply, error replies included, arrives within
+    /// `wait`. An unanswered request is forgotten so a late reply is dropped.
+    pub(crate) async fn probe(&self, method: &str, wait: Duration) -> Result<bool, String> {
+        let (id, rx) = self.register_request(method).await;
+        let message = json!({ "id": id, "method": method, "params": {} });
+        // A hung process may stop draining stdin, so the write is bounded too.
+        let answered = match timeout(wait, self.write_message(message)).await {
+            Ok(Ok(())) => timeout(wait, rx).await.is_ok(),
+            Ok(Err(error)) => {
+                self.take_pending(id).await;
+                return Err(error);
+            }
+            Err(_) => false,
+        };
+        if !answered {
+            self.take_pending(id).await;
+        }
+        Ok(answered)
     }
 
     pub(crate) async fn send_notification(
@@ -81,10 +281,194 @@ impl WorkspaceSession {
         self.write_message(json!({ "id": id, "result": result }))
             .await
     }
+
+    async fn take_pending(&self, id: u64) -> Option<oneshot::Sender<Result<Value, String>>> {
+        self.request_id_to_method.lock().await.remove(&id);
+        self.pending
+            .lock()
+            .await
+            .remove(&id)
+            .map(|pending| pending.sender)
+    }
+
+    /// Fails every in-flight request with `reason` so awaiting callers return promptly.
+    pub(crate) async fn fail_pending(&self, reason: &str) {
+        let drained: Vec<_> = self.pending.lock().await.drain().collect();
+        self.request_id_to_method.lock().await.clear();
+        for (_, pending) in drained {
+            let _ = pending.sender.send(Err(reason.to_string()));
+        }
+    }
+
+    pub(crate) async fn pending_requests(&self) -> Vec<PendingRequestInfo> {
+        let now = now_ms();
+        let pending = self.pending.lock().await;
+        let methods = self.request_id_to_method.lock().await;
+        let mut requests: Vec<PendingRequestInfo> = pending
+            .iter()
+            .map(|(id, request)| {
+                pending_request_info(*id, methods.get(id), request.enqueued_at_ms, now)
+            })
+            .collect();
+        requests.sort_by_key(|request| request.id);
+        requests
+    }
+
+    pub(crate) async fn current_turn_output(&self, thread_id: &str) -> Option<String> {
+        self.turn_output.lock().await.get(thread_id).cloned()
+    }
+
+    async fn record_thread_activity(&self, thread_id: Option<&str>) {
+        if let Some(thread_id) = thread_id {
+            let mut activity = self.thread_activity.lock().await;
+            record_activity(&mut activity, thread_id, now_ms());
+        }
+    }
+
+    /// Drops activity for threads that were archived so the map only tracks live threads.
+    pub(crate) async fn forget_thread_activity<'a>(
+        &self,
+        thread_ids: impl IntoIterator<Item = &'a String>,
+    ) {
+        let mut activity = self.thread_activity.lock().await;
+        for thread_id in thread_ids {
+            activity.remove(thread_id);
+        }
+    }
+
+    pub(crate) async fn thread_activity(&self) -> HashMap<String, u64> {
+        self.thread_activity.lock().await.clone()
+    }
+
+    pub(crate) async fn active_turn_count(&self) -> usize {
+        self.turn_output.lock().await.len()
+    }
+
+    pub(crate) fn last_event_at_ms(&self) -> Option<u64> {
+        match self.last_event_at_ms.load(Ordering::Relaxed) {
+            0 => None,
+            value => Some(value),
+        }
+    }
+
+    pub(crate) fn session_info(&self) -> SessionInfo {
+        session_info(self.started_at.elapsed(), self.last_event_at_ms(), now_ms())
+    }
+
+    /// True while the app-server process is still running.
+    pub(crate) async fn is_healthy(&self) -> bool {
+        matches!(self.child.lock().await.try_wait(), Ok(None))
+    }
+
+    pub(crate) async fn state(&self) -> SessionState {
+        if !self.is_healthy().await {
+            SessionState::Dead
+        } else if self.initialized_at.lock().await.is_some() {
+            SessionState::Ready
+        } else if self.initialize_result.lock().await.is_some() {
+            SessionState::Initializing
+        } else {
+            SessionState::Spawning
+        }
+    }
+
+    pub(crate) async fn supports_method(&self, method: &str) -> Option<bool> {
+        if self.unsupported_methods.lock().await.contains(method) {
+            return Some(false);
+        }
+        let initialize_result = self.initialize_result.lock().await;
+        method_support(initialize_result.as_ref(), method)
+    }
 }
 
-pub(crate) fn build_codex_path_env(codex_bin: Option<&str>) -> Option<String> {
-    let mut paths: Vec<String> = env::var("PATH")
+fn session_info(uptime: Duration, last_event_at_ms: Option<u64>, now_ms: u64) -> SessionInfo {
+    let uptime_secs = uptime.as_secs();
+    let idle_secs = match last_event_at_ms {
+        Some(last_event_at_ms) => now_ms.saturating_sub(last_event_at_ms) / 1000,
+        None => uptime_secs,
+    };
+    SessionInfo {
+        uptime_secs,
+        idle_secs,
+    }
+}
+
+const JSON_RPC_METHOD_NOT_FOUND: i64 = -32601;
+
+fn is_method_not_found(response: &Value) -> bool {
+    response
+        .get("error")
+        .and_then(|error| error.get("code"))
+        .and_then(Value::as_i64)
+        == Some(JSON_RPC_METHOD_NOT_FOUND)
+}
+
+/// Looks `method` up in the capabilities advertised by `initialize`. Returns `None`
+/// when the server does not enumerate its methods, since absence proves nothing.
+fn method_support(initialize_result: Option<&Value>, method: &str) -> Option<bool> {
+    let capabilities = initialize_result?.get("capabilities")?;
+    if let Some(methods) = capabilities.get("methods").and_then(|value| value.as_array()) {
+        return Some(methods.iter().any(|value| value.as_str() == Some(method)));
+    }
+    match capabilities.get(method)? {
+        Value::Bool(supported) => Some(*supported),
+        Value::Null => Some(false),
+        _ => Some(true),
+    }
+}
+
+fn record_activity(activity: &mut HashMap<String, u64>, thread_id: &str, now_ms: u64) {
+    activity.insert(thread_id.to_string(), now_ms);
+    if activity.len() > MAX_TRACKED_THREADS {
+        let oldest = activity
+            .iter()
+            .min_by_key(|(_, at)| **at)
+            .map(|(id, _)| id.clone());
+        if let Some(oldest) = oldest {
+            activity.remove(&oldest);
+        }
+    }
+}
+
+fn record_turn_output(buffers: &mut HashMap<String, String>, value: &Value) {
+    let Some(method) = value.get("method").and_then(|method| method.as_str()) else {
+        return;
+    };
+    let thread_id = extract_thread_id(value).or_else(|| {
+        value
+            .get("params")
+            .and_then(|params| params.get("turn"))
+            .and_then(|turn| turn.get("threadId").or_else(|| turn.get("thread_id")))
+            .and_then(|id| id.as_str())
+            .map(|id| id.to_string())
+    });
+    let Some(thread_id) = thread_id else {
+        return;
+    };
+    match method {
+        "turn/started" => {
+            buffers.insert(thread_id, String::new());
+        }
+        "item/agentMessage/delta" => {
+            let delta = value
+                .get("params")
+                .and_then(|params| params.get("delta"))
+                .and_then(|delta| delta.as_str())
+                .unwrap_or("");
+            if !delta.is_empty() {
+                buffers.entry(thread_id).or_default().push_str(delta);
+            }
+        }
+        "turn/completed" => {
+            buffers.remove(&thread_id);
+        }
+        _ => {}
+    }
+}
+
+/// Splits the search path into the inherited `PATH` entries and the ones the monitor appends.
+fn codex_path_entries(codex_bin: Option<&str>) -> (Vec<String>, Vec<String>) {
+    let inherited: Vec<String> = env::var("PATH")
         .unwrap_or_default()
         .split(':')
         .filter(|value| !value.is_empty())
@@ -122,11 +506,18 @@ pub(crate) fn build_codex_path_env(codex_bin: Option<&str>) -> Option<String> {
             extras.push(parent.to_string_lossy().to_string());
         }
     }
+    let mut added = Vec::new();
     for extra in extras {
-        if !paths.contains(&extra) {
-            paths.push(extra);
+        if !inherited.contains(&extra) && !added.contains(&extra) {
+            added.push(extra);
         }
     }
+    (inherited, added)
+}
+
+pub(crate) fn build_codex_path_env(codex_bin: Option<&str>) -> Option<String> {
+    let (mut paths, added) = codex_path_entries(codex_bin);
+    paths.extend(added);
     if paths.is_empty() {
         None
     } else {
@@ -134,6 +525,40 @@ pub(crate) fn build_codex_path_env(codex_bin: Option<&str>) -> Option<String> {
     }
 }
 
+pub(crate) fn codex_path_diagnostics(codex_bin: Option<String>) -> PathDiagnostics {
+    let (mut paths, added_entries) = codex_path_entries(codex_bin.as_deref());
+    paths.extend(added_entries.iter().cloned());
+    let bin = codex_bin
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "codex".into());
+    let bin_path = Path::new(&bin);
+    let matches: Vec<String> = if bin_path.components().count() > 1 {
+        if bin_path.is_file() {
+            vec![bin.clone()]
+        } else {
+            Vec::new()
+        }
+    } else {
+        paths
+            .iter()
+            .map(|dir| Path::new(dir).join(&bin))
+            .filter(|candidate| candidate.is_file())
+            .map(|candidate| candidate.to_string_lossy().to_string())
+            .collect()
+    };
+    PathDiagnostics {
+        path: if paths.is_empty() {
+            None
+        } else {
+            Some(paths.join(":"))
+        },
+        added_entries,
+        codex_bin: bin,
+        found: !matches.is_empty(),
+        matches,
+    }
+}
+
 pub(crate) fn build_codex_command_with_bin(codex_bin: Option<String>) -> Command {
     let bin = codex_bin
         .clone()
@@ -194,11 +619,118 @@ pub(crate) async fn check_codex_installation(
     Ok(if version.is_empty() { None } else { Some(version) })
 }
 
+/// Kills the child if spawning is abandoned before the handshake finishes, whether by
+/// error or because the caller dropped the future.
+struct SpawnGuard {
+    session: Option<Arc<WorkspaceSession>>,
+}
+
+impl SpawnGuard {
+    fn disarm(mut self) {
+        self.session = None;
+    }
+}
+
+impl Drop for SpawnGuard {
+    fn drop(&mut self) {
+        let Some(session) = self.session.take() else {
+            return;
+        };
+        // Signal the child right away so a cancelled connect never leaves it running; the
+        // reap (or the whole kill, if the lock is busy) finishes on a task.
+        let signalled = match session.child.try_lock() {
+            Ok(mut child) => child.start_kill().is_ok(),
+            Err(_) => false,
+        };
+        tokio::spawn(async move {
+            let mut child = session.child.lock().await;
+            if signalled {
+                let _ = child.wait().await;
+            } else {
+                let _ = child.kill().await;
+            }
+        });
+    }
+}
+
+/// Checks a keepalive setting; zero clears it and values above an hour are rejected.
+pub(crate) fn validate_keepalive_secs(
+    name: &str,
+    value: Option<u64>,
+) -> Result<Option<u64>, String> {
+    match value.filter(|secs| *secs > 0) {
+        Some(secs) if secs > MAX_KEEPALIVE_SECS => {
+            Err(format!("{name} must be at most {MAX_KEEPALIVE_SECS} seconds"))
+        }
+        value => Ok(value),
+    }
+}
+
+fn settings_duration(secs: Option<u64>, default: Duration) -> Duration {
+    secs.filter(|secs| *secs > 0)
+        .map(|secs| Duration::from_secs(secs.min(MAX_KEEPALIVE_SECS)))
+        .unwrap_or(default)
+}
+
+/// Sends a `ping` request every interval and emits `codex/sessionStalled` when nothing
+/// answers it within the stall timeout. Any reply counts, including method-not-found, so
+/// an idle but healthy server passes while a hung process that keeps its pipes open does
+/// not. Fires once per stall; the next answered ping re-arms it.
+fn spawn_keepalive<E: EventSink>(
+    session: &Arc<WorkspaceSession>,
+    event_sink: E,
+) -> JoinHandle<()> {
+    let settings = &session.entry.settings;
+    let ping_interval = settings_duration(settings.ping_interval_secs, DEFAULT_PING_INTERVAL);
+    let stall_timeout = settings_duration(settings.stall_timeout_secs, DEFAULT_STALL_TIMEOUT);
+    let workspace_id = session.entry.id.clone();
+    let weak_session = Arc::downgrade(session);
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(ping_interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        ticker.tick().await;
+        let mut reported = false;
+        loop {
+            ticker.tick().await;
+            let Some(session) = weak_session.upgrade() else {
+                break;
+            };
+            let answered = session.probe("ping", stall_timeout).await;
+            drop(session);
+            match answered {
+                // The write failed, so the process is gone; the session reports that itself.
+                Err(_) => break,
+                Ok(true) => reported = false,
+                Ok(false) if reported => {}
+                Ok(false) => {
+                    reported = true;
+                    tracing::warn!(
+                        workspace_id = %workspace_id,
+                        timeout_secs = stall_timeout.as_secs(),
+                        "app-server did not answer ping"
+                    );
+                    event_sink.emit_app_server_event(AppServerEvent::new(
+                        workspace_id.clone(),
+                        json!({
+                            "method": "codex/sessionStalled",
+                            "params": {
+                                "workspaceId": workspace_id,
+                                "timeoutSecs": stall_timeout.as_secs(),
+                            },
+                        }),
+                    ));
+                }
+            }
+        }
+    })
+}
+
 pub(crate) async fn spawn_workspace_session<E: EventSink>(
     entry: WorkspaceEntry,
     default_codex_bin: Option<String>,
     codex_args: Option<String>,
     codex_home: Option<PathBuf>,
+    data_dir: PathBuf,
     client_version: String,
     event_sink: E,
 ) -> Result<Arc<WorkspaceSession>, String> {
@@ -208,6 +740,7 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
         .filter(|value| !value.trim().is_empty())
         .or(default_codex_bin);
     let _ = check_codex_installation(codex_bin.clone()).await?;
+    let workspace_lock = acquire_workspace_lock(&data_dir, &entry.path)?;
 
     let mut command = build_codex_command_with_bin(codex_bin);
     apply_codex_args(&mut command, codex_args.as_deref())?;
@@ -221,6 +754,7 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
     command.stderr(std::process::Stdio::piped());
 
     let mut child = command.spawn().map_err(|e| e.to_string())?;
+    tracing::info!(workspace_id = %entry.id, pid = child.id(), "spawned codex app-server");
     let stdin = child.stdin.take().ok_or("missing stdin")?;
     let stdout = child.stdout.take().ok_or("missing stdout")?;
     let stderr = child.stderr.take().ok_or("missing stderr")?;
@@ -230,45 +764,93 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
         child: Mutex::new(child),
         stdin: Mutex::new(stdin),
         pending: Mutex::new(HashMap::new()),
+        request_id_to_method: Mutex::new(HashMap::new()),
         next_id: AtomicU64::new(1),
         background_thread_callbacks: Mutex::new(HashMap::new()),
+        turn_output: Mutex::new(HashMap::new()),
+        initialize_result: Mutex::new(None),
+        unsupported_methods: Mutex::new(HashSet::new()),
+        initialized_at: Mutex::new(None),
+        last_event_at_ms: AtomicU64::new(0),
+        started_at: Instant::now(),
+        approval_policy: Mutex::new(None),
+        models: Mutex::new(None),
+        thread_preferences: Mutex::new(HashMap::new()),
+        monitor_only: AtomicBool::new(entry.monitor_only),
+        thread_activity: Mutex::new(HashMap::new()),
+        keepalive: std::sync::Mutex::new(None),
+        removed: watch::Sender::new(false),
+        _workspace_lock: workspace_lock,
     });
+    let spawn_guard = SpawnGuard {
+        session: Some(Arc::clone(&session)),
+    };
 
     let session_clone = Arc::clone(&session);
     let workspace_id = entry.id.clone();
     let event_sink_clone = event_sink.clone();
     tokio::spawn(async move {
         let mut lines = BufReader::new(stdout).lines();
+        let mut parse_error_limiter =
+            EventRateLimiter::new(MAX_LOG_EVENTS_PER_WINDOW, LOG_EVENT_WINDOW);
         while let Ok(Some(line)) = lines.next_line().await {
             if line.trim().is_empty() {
                 continue;
             }
+            session_clone
+                .last_event_at_ms
+                .store(now_ms(), Ordering::Relaxed);
             let value: Value = match serde_json::from_str(&line) {
                 Ok(value) => value,
                 Err(err) => {
-                    let payload = AppServerEvent {
-                        workspace_id: workspace_id.clone(),
-                        message: json!({
+                    tracing::warn!(
+                        workspace_id = %workspace_id,
+                        "unparsable app-server line: {err}"
+                    );
+                    let (allowed, flushed) = parse_error_limiter.check(Instant::now());
+                    if flushed > 0 {
+                        event_sink_clone.emit_app_server_event(AppServerEvent::new(
+                            workspace_id.clone(),
+                            suppressed_event("codex/parseError", flushed),
+                        ));
+                    }
+                    if !allowed {
+                        continue;
+                    }
+                    let payload = AppServerEvent::new(
+                        workspace_id.clone(),
+                        json!({
                             "method": "codex/parseError",
                             "params": { "error": err.to_string(), "raw": line },
                         }),
-                    };
+                    );
                     event_sink_clone.emit_app_server_event(payload);
                     continue;
                 }
             };
 
             let maybe_id = value.get("id").and_then(|id| id.as_u64());
+            tracing::trace!(
+                workspace_id = %workspace_id,
+                id = maybe_id,
+                method = value.get("method").and_then(Value::as_str),
+                "app-server message"
+            );
             let has_method = value.get("method").is_some();
             let has_result_or_error = value.get("result").is_some() || value.get("error").is_some();
 
             // Check if this event is for a background thread
             let thread_id = extract_thread_id(&value);
 
+            if has_method && !has_result_or_error {
+                let mut buffers = session_clone.turn_output.lock().await;
+                record_turn_output(&mut buffers, &value);
+            }
+
             if let Some(id) = maybe_id {
                 if has_result_or_error {
-                    if let Some(tx) = session_clone.pending.lock().await.remove(&id) {
-                        let _ = tx.send(value);
+                    if let Some(tx) = session_clone.take_pending(id).await {
+                        let _ = tx.send(Ok(value));
                     }
                 } else if has_method {
                     // Check for background thread callback
@@ -282,14 +864,12 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
                     }
                     // Don't emit to frontend if this is a background thread event
                     if !sent_to_background {
-                        let payload = AppServerEvent {
-                            workspace_id: workspace_id.clone(),
-                            message: value,
-                        };
+                        session_clone.record_thread_activity(thread_id.as_deref()).await;
+                        let payload = AppServerEvent::new(workspace_id.clone(), value);
                         event_sink_clone.emit_app_server_event(payload);
                     }
-                } else if let Some(tx) = session_clone.pending.lock().await.remove(&id) {
-                    let _ = tx.send(value);
+                } else if let Some(tx) = session_clone.take_pending(id).await {
+                    let _ = tx.send(Ok(value));
                 }
             } else if has_method {
                 // Check for background thread callback
@@ -303,33 +883,51 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
                 }
                 // Don't emit to frontend if this is a background thread event
                 if !sent_to_background {
-                    let payload = AppServerEvent {
-                        workspace_id: workspace_id.clone(),
-                        message: value,
-                    };
+                    session_clone.record_thread_activity(thread_id.as_deref()).await;
+                    let payload = AppServerEvent::new(workspace_id.clone(), value);
                     event_sink_clone.emit_app_server_event(payload);
                 }
             }
         }
+        tracing::info!(workspace_id = %workspace_id, "app-server stdout closed");
     });
 
     let workspace_id = entry.id.clone();
     let event_sink_clone = event_sink.clone();
     tokio::spawn(async move {
         let mut lines = BufReader::new(stderr).lines();
+        let mut limiter = EventRateLimiter::new(MAX_LOG_EVENTS_PER_WINDOW, LOG_EVENT_WINDOW);
         while let Ok(Some(line)) = lines.next_line().await {
             if line.trim().is_empty() {
                 continue;
             }
-            let payload = AppServerEvent {
-                workspace_id: workspace_id.clone(),
-                message: json!({
+            tracing::debug!(workspace_id = %workspace_id, "codex stderr: {line}");
+            let (allowed, flushed) = limiter.check(Instant::now());
+            if flushed > 0 {
+                event_sink_clone.emit_app_server_event(AppServerEvent::new(
+                    workspace_id.clone(),
+                    suppressed_event("codex/stderr", flushed),
+                ));
+            }
+            if !allowed {
+                continue;
+            }
+            let payload = AppServerEvent::new(
+                workspace_id.clone(),
+                json!({
                     "method": "codex/stderr",
                     "params": { "message": line },
                 }),
-            };
+            );
             event_sink_clone.emit_app_server_event(payload);
         }
+        let remaining = limiter.take_suppressed();
+        if remaining > 0 {
+            event_sink_clone.emit_app_server_event(AppServerEvent::new(
+                workspace_id.clone(),
+                suppressed_event("codex/stderr", remaining),
+            ));
+        }
     });
 
     let init_params = json!({
@@ -347,6 +945,7 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
     let init_response = match init_result {
         Ok(response) => response,
         Err(_) => {
+            tracing::error!(workspace_id = %entry.id, "app-server did not answer initialize");
             let mut child = session.child.lock().await;
             let _ = child.kill().await;
             return Err(
@@ -355,25 +954,62 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
             );
         }
     };
-    init_response?;
+    let init_response = init_response?;
+    *session.initialize_result.lock().await = init_response.get("result").cloned();
     session.send_notification("initialized", None).await?;
+    *session.initialized_at.lock().await = Some(Instant::now());
 
-    let payload = AppServerEvent {
-        workspace_id: entry.id.clone(),
-        message: json!({
+    let keepalive = spawn_keepalive(&session, event_sink.clone());
+    if let Ok(mut slot) = session.keepalive.lock() {
+        *slot = Some(keepalive);
+    }
+
+    let payload = AppServerEvent::new(
+        entry.id.clone(),
+        json!({
             "method": "codex/connected",
             "params": { "workspaceId": entry.id.clone() }
         }),
-    };
+    );
     event_sink.emit_app_server_event(payload);
 
+    spawn_guard.disarm();
     Ok(session)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::extract_thread_id;
+    use super::{
+        codex_path_diagnostics, extract_thread_id, is_method_not_found, method_support,
+        pending_request_info,
+        record_activity, record_turn_output, session_info, settings_duration,
+        validate_keepalive_secs, EventRateLimiter, MAX_TRACKED_THREADS,
+    };
+    use crate::backend::events::AppServerEvent;
     use serde_json::json;
+    use std::collections::HashMap;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn path_diagnostics_checks_explicit_codex_bin() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        let bin = dir.join("codex");
+        std::fs::write(&bin, "#!/bin/sh\n").expect("write codex stub");
+        let bin = bin.to_string_lossy().to_string();
+        let dir = dir.to_string_lossy().to_string();
+
+        let diagnostics = codex_path_diagnostics(Some(bin.clone()));
+        assert!(diagnostics.found);
+        assert_eq!(diagnostics.codex_bin, bin);
+        assert_eq!(diagnostics.matches, vec![bin.clone()]);
+        assert!(diagnostics.path.expect("path").split(':').any(|entry| entry == dir));
+
+        let missing = format!("{dir}/missing-codex");
+        let diagnostics = codex_path_diagnostics(Some(missing));
+        assert!(!diagnostics.found);
+        assert!(diagnostics.matches.is_empty());
+    }
 
     #[test]
     fn extract_thread_id_reads_camel_case() {
@@ -392,4 +1028,143 @@ mod tests {
         let value = json!({ "params": {} });
         assert_eq!(extract_thread_id(&value), None);
     }
+
+    #[test]
+    fn app_server_event_lifts_thread_id_to_top_level() {
+        let event = AppServerEvent::new(
+            "ws-1".to_string(),
+            json!({ "method": "turn/started", "params": { "threadId": "thread-1" } }),
+        );
+        let value = serde_json::to_value(&event).expect("serialize");
+        assert_eq!(value["threadId"], "thread-1");
+
+        let event = AppServerEvent::new("ws-1".to_string(), json!({ "method": "codex/stderr" }));
+        let value = serde_json::to_value(&event).expect("serialize");
+        assert!(value.get("threadId").is_none());
+    }
+
+    #[test]
+    fn record_turn_output_accumulates_deltas_until_turn_completes() {
+        let mut buffers = HashMap::new();
+        record_turn_output(
+            &mut buffers,
+            &json!({
+                "method": "turn/started",
+                "params": { "turn": { "id": "t1", "threadId": "thread-1" } }
+            }),
+        );
+        assert_eq!(buffers.get("thread-1").map(String::as_str), Some(""));
+
+        for delta in ["Hel", "lo"] {
+            record_turn_output(
+                &mut buffers,
+                &json!({
+                    "method": "item/agentMessage/delta",
+                    "params": { "threadId": "thread-1", "itemId": "i1", "delta": delta }
+                }),
+            );
+        }
+        assert_eq!(buffers.get("thread-1").map(String::as_str), Some("Hello"));
+
+        record_turn_output(
+            &mut buffers,
+            &json!({ "method": "turn/completed", "params": { "threadId": "thread-1" } }),
+        );
+        assert!(buffers.get("thread-1").is_none());
+    }
+
+    #[test]
+    fn record_activity_evicts_the_least_recently_active_thread() {
+        let mut activity = HashMap::new();
+        for index in 0..MAX_TRACKED_THREADS {
+            record_activity(&mut activity, &format!("thread-{index}"), index as u64 + 1);
+        }
+        record_activity(&mut activity, "thread-0", 10_000);
+        record_activity(&mut activity, "thread-new", 10_001);
+
+        assert_eq!(activity.len(), MAX_TRACKED_THREADS);
+        assert_eq!(activity.get("thread-0"), Some(&10_000));
+        assert!(activity.get("thread-1").is_none());
+        assert_eq!(activity.get("thread-new"), Some(&10_001));
+    }
+
+    #[test]
+    fn pending_request_info_reports_age_and_unknown_method() {
+        let method = "thread/start".to_string();
+        let info = pending_request_info(7, Some(&method), 1_000, 4_500);
+        assert_eq!(info.id, 7);
+        assert_eq!(info.method, "thread/start");
+        assert_eq!(info.age_ms, 3_500);
+
+        let info = pending_request_info(8, None, 5_000, 4_500);
+        assert_eq!(info.method, "unknown");
+        assert_eq!(info.age_ms, 0);
+    }
+
+    #[test]
+    fn event_rate_limiter_drops_excess_and_reports_on_next_window() {
+        let start = Instant::now();
+        let mut limiter = EventRateLimiter::new(2, Duration::from_secs(1));
+        assert_eq!(limiter.check(start), (true, 0));
+        assert_eq!(limiter.check(start), (true, 0));
+        assert_eq!(limiter.check(start), (false, 0));
+        assert_eq!(limiter.check(start), (false, 0));
+
+        let next_window = start + Duration::from_secs(1);
+        assert_eq!(limiter.check(next_window), (true, 2));
+        assert_eq!(limiter.take_suppressed(), 0);
+    }
+
+    #[test]
+    fn method_support_reads_capabilities_or_reports_unknown() {
+        let listed = json!({ "capabilities": { "methods": ["thread/start", "review/start"] } });
+        assert_eq!(method_support(Some(&listed), "review/start"), Some(true));
+        assert_eq!(method_support(Some(&listed), "thread/fork"), Some(false));
+
+        let flagged = json!({ "capabilities": { "review/start": false, "thread/fork": {} } });
+        assert_eq!(method_support(Some(&flagged), "review/start"), Some(false));
+        assert_eq!(method_support(Some(&flagged), "thread/fork"), Some(true));
+        assert_eq!(method_support(Some(&flagged), "model/list"), None);
+
+        let bare = json!({ "userAgent": "codex" });
+        assert_eq!(method_support(Some(&bare), "model/list"), None);
+        assert_eq!(method_support(None, "model/list"), None);
+    }
+
+    #[test]
+    fn method_not_found_is_detected_from_error_code() {
+        assert!(is_method_not_found(
+            &json!({ "id": 1, "error": { "code": -32601, "message": "unknown method" } })
+        ));
+        assert!(!is_method_not_found(
+            &json!({ "id": 1, "error": { "code": -32603, "message": "internal" } })
+        ));
+        assert!(!is_method_not_found(&json!({ "id": 1, "result": { "data": [] } })));
+    }
+
+    #[test]
+    fn session_info_measures_idle_from_last_event() {
+        let info = session_info(Duration::from_secs(600), Some(100_000), 160_500);
+        assert_eq!(info.uptime_secs, 600);
+        assert_eq!(info.idle_secs, 60);
+
+        let silent = session_info(Duration::from_secs(30), None, 160_500);
+        assert_eq!(silent.idle_secs, 30);
+    }
+
+    #[test]
+    fn settings_duration_ignores_zero_and_missing_and_caps_large_values() {
+        let default = Duration::from_secs(30);
+        assert_eq!(settings_duration(None, default), default);
+        assert_eq!(settings_duration(Some(0), default), default);
+        assert_eq!(settings_duration(Some(5), default), Duration::from_secs(5));
+        assert_eq!(settings_duration(Some(u64::MAX), default), Duration::from_secs(3600));
+    }
+
+    #[test]
+    fn keepalive_settings_are_bounded() {
+        assert_eq!(validate_keepalive_secs("pingIntervalSecs", Some(0)), Ok(None));
+        assert_eq!(validate_keepalive_secs("pingIntervalSecs", Some(45)), Ok(Some(45)));
+        assert!(validate_keepalive_secs("stallTimeoutSecs", Some(3601)).is_err());
+    }
 }
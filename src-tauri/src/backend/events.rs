@@ -1,12 +1,28 @@
 use serde::Serialize;
 use serde_json::Value;
 
+use super::app_server::extract_thread_id;
+
 #[derive(Serialize, Clone)]
 pub(crate) struct AppServerEvent {
     pub(crate) workspace_id: String,
+    /// Lifted from the message params so the frontend can route events per thread.
+    #[serde(rename = "threadId", skip_serializing_if = "Option::is_none")]
+    pub(crate) thread_id: Option<String>,
     pub(crate) message: Value,
 }
 
+impl AppServerEvent {
+    pub(crate) fn new(workspace_id: String, message: Value) -> Self {
+        let thread_id = extract_thread_id(&message);
+        Self {
+            workspace_id,
+            thread_id,
+            message,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Clone)]
 pub(crate) struct TerminalOutput {
     #[serde(rename = "workspaceId")]
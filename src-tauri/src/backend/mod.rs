@@ -1,2 +1,4 @@
 pub(crate) mod app_server;
 pub(crate) mod events;
+pub(crate) mod process_info;
+pub(crate) mod workspace_lock;
@@ -0,0 +1,56 @@
+use crate::types::SessionProcessInfo;
+
+/// Looks up CPU, memory and run state for `pid`. Blocks for sysinfo's minimum CPU sampling
+/// interval, so call it off the async runtime.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+pub(crate) fn session_process_info(pid: u32) -> SessionProcessInfo {
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+    let sys_pid = Pid::from_u32(pid);
+    let refresh_kind = ProcessRefreshKind::nothing().with_cpu().with_memory();
+    let mut system = System::new();
+    // CPU usage is the delta between two refreshes.
+    system.refresh_processes_specifics(ProcessesToUpdate::Some(&[sys_pid]), true, refresh_kind);
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+    system.refresh_processes_specifics(ProcessesToUpdate::Some(&[sys_pid]), true, refresh_kind);
+
+    let Some(process) = system.process(sys_pid) else {
+        return unavailable(pid);
+    };
+    SessionProcessInfo {
+        pid,
+        cpu_percent: Some(process.cpu_usage()),
+        memory_bytes: Some(process.memory()),
+        status: Some(process.status().to_string()),
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+pub(crate) fn session_process_info(pid: u32) -> SessionProcessInfo {
+    unavailable(pid)
+}
+
+fn unavailable(pid: u32) -> SessionProcessInfo {
+    SessionProcessInfo {
+        pid,
+        cpu_percent: None,
+        memory_bytes: None,
+        status: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::session_process_info;
+
+    #[test]
+    fn reports_the_current_process() {
+        let pid = std::process::id();
+        let info = session_process_info(pid);
+        assert_eq!(info.pid, pid);
+        if cfg!(any(target_os = "macos", target_os = "linux")) {
+            assert!(info.memory_bytes.is_some_and(|bytes| bytes > 0));
+            assert!(info.status.is_some());
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, OnceLock, PoisonError};
+use std::time::{Duration, SystemTime};
+
+use crate::types::{StaleSessionCleanup, WorkspaceLockStatus};
+
+const SESSION_LOCKS_DIR: &str = "session-locks";
+/// Temp files younger than this may belong to a write that is still in flight.
+const TEMP_FILE_STALE_AFTER: Duration = Duration::from_secs(30);
+
+fn lock_path(data_dir: &Path, workspace_path: &str) -> PathBuf {
+    // FNV-1a keeps the file name stable across builds, unlike `DefaultHasher`.
+    let hash = workspace_path
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    data_dir.join(SESSION_LOCKS_DIR).join(format!("{hash:016x}.lock"))
+}
+
+/// Lock files this process holds, with one count per live session on the workspace. The
+/// `File` keeps the OS lock; closing it when the count drops to zero releases the lock.
+fn held_locks() -> MutexGuard<'static, HashMap<PathBuf, (File, usize)>> {
+    static HELD: OnceLock<Mutex<HashMap<PathBuf, (File, usize)>>> = OnceLock::new();
+    HELD.get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+fn read_holder_pid(path: &Path) -> Option<u32> {
+    std::fs::read_to_string(path).ok()?.trim().parse::<u32>().ok()
+}
+
+#[cfg(unix)]
+fn is_same_file(file: &File, path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (file.metadata(), std::fs::metadata(path)) {
+        (Ok(open), Ok(current)) => open.dev() == current.dev() && open.ino() == current.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn is_same_file(_file: &File, path: &Path) -> bool {
+    path.is_file()
+}
+
+fn held_elsewhere_error(pid: Option<u32>) -> String {
+    match pid {
+        Some(pid) => {
+            format!("Workspace is already open in another CodexMonitor instance (pid {pid}).")
+        }
+        None => "Workspace is already open in another CodexMonitor instance.".to_string(),
+    }
+}
+
+/// Opens the lock file and takes an exclusive OS lock on it, which the OS drops if the
+/// process dies, so a crash never leaves the workspace locked.
+fn lock_file(path: &Path) -> Result<File, String> {
+    // Stale cleanup may unlink the file between our open and lock; retry on the new file.
+    for _ in 0..3 {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .map_err(|err| format!("Failed to open session lock: {err}"))?;
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                return Err(held_elsewhere_error(read_holder_pid(path)));
+            }
+            Err(TryLockError::Error(err)) => {
+                return Err(format!("Failed to lock session: {err}"));
+            }
+        }
+        if !is_same_file(&file, path) {
+            continue;
+        }
+        file.set_len(0)
+            .and_then(|()| file.write_all(std::process::id().to_string().as_bytes()))
+            .map_err(|err| format!("Failed to write session lock: {err}"))?;
+        return Ok(file);
+    }
+    Err("Failed to lock session: lock file kept changing".to_string())
+}
+
+/// Keeps the workspace locked for as long as it lives; sessions hold one, so every way a
+/// session goes away (disconnect, eviction, reload, removal) releases the lock.
+#[derive(Debug)]
+pub(crate) struct WorkspaceLockGuard {
+    path: PathBuf,
+}
+
+impl Drop for WorkspaceLockGuard {
+    fn drop(&mut self) {
+        let mut held = held_locks();
+        if let Some((_, count)) = held.get_mut(&self.path) {
+            *count -= 1;
+            if *count == 0 {
+                held.remove(&self.path);
+            }
+        }
+    }
+}
+
+/// Reports whether another live monitor process has a session open for `workspace_path`.
+pub(crate) fn check_workspace_lock(data_dir: &Path, workspace_path: &str) -> WorkspaceLockStatus {
+    let path = lock_path(data_dir, workspace_path);
+    let free = WorkspaceLockStatus {
+        held_elsewhere: false,
+        pid: None,
+    };
+    if held_locks().contains_key(&path) {
+        return free;
+    }
+    let Ok(file) = OpenOptions::new().read(true).write(true).open(&path) else {
+        return free;
+    };
+    match file.try_lock() {
+        Err(TryLockError::WouldBlock) => WorkspaceLockStatus {
+            held_elsewhere: true,
+            pid: read_holder_pid(&path),
+        },
+        _ => free,
+    }
+}
+
+/// Locks `workspace_path` for this process, failing when another process holds it. Sessions
+/// in this process share the lock, so a reload can spawn before the old session is dropped.
+pub(crate) fn acquire_workspace_lock(
+    data_dir: &Path,
+    workspace_path: &str,
+) -> Result<WorkspaceLockGuard, String> {
+    let path = lock_path(data_dir, workspace_path);
+    let mut held = held_locks();
+    if let Some((_, count)) = held.get_mut(&path) {
+        *count += 1;
+        return Ok(WorkspaceLockGuard { path });
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to create session lock dir: {err}"))?;
+    }
+    let file = lock_file(&path)?;
+    held.insert(path.clone(), (file, 1));
+    Ok(WorkspaceLockGuard { path })
+}
+
+fn is_stale_temp_file(path: &Path, stale_after: Duration) -> bool {
+    if path.extension().and_then(|ext| ext.to_str()) != Some("tmp") {
+        return false;
+    }
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return false;
+    };
+    if !metadata.is_file() {
+        return false;
+    }
+    metadata
+        .modified()
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age >= stale_after)
+}
+
+fn clear_stale_session_state_with(
+    data_dir: &Path,
+    workspace_path: &str,
+    stale_after: Duration,
+) -> Result<StaleSessionCleanup, String> {
+    let mut cleanup = StaleSessionCleanup::default();
+    let lock = lock_path(data_dir, workspace_path);
+    if !held_locks().contains_key(&lock) {
+        if let Ok(file) = OpenOptions::new().read(true).write(true).open(&lock) {
+            // Holding the lock while unlinking keeps a concurrent acquire from using the file.
+            if file.try_lock().is_ok() {
+                let pid = read_holder_pid(&lock);
+                std::fs::remove_file(&lock)
+                    .map_err(|err| format!("Failed to remove session lock: {err}"))?;
+                cleanup.removed_lock = true;
+                cleanup.removed_lock_pid = pid;
+            }
+        }
+    }
+
+    // Atomic writes in the data dir and lock dir go through `*.tmp` siblings.
+    for dir in [data_dir.to_path_buf(), data_dir.join(SESSION_LOCKS_DIR)] {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if is_stale_temp_file(&path, stale_after) && std::fs::remove_file(&path).is_ok() {
+                cleanup
+                    .removed_temp_files
+                    .push(path.to_string_lossy().to_string());
+            }
+        }
+    }
+    cleanup.removed_temp_files.sort();
+    Ok(cleanup)
+}
+
+/// Removes the workspace's lock file when no process holds it, plus leftover temp files
+/// from interrupted atomic writes. Locks held by this or another live process are left alone.
+pub(crate) fn clear_stale_session_state(
+    data_dir: &Path,
+    workspace_path: &str,
+) -> Result<StaleSessionCleanup, String> {
+    clear_stale_session_state_with(data_dir, workspace_path, TEMP_FILE_STALE_AFTER)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::OpenOptions;
+    use std::time::Duration;
+
+    use uuid::Uuid;
+
+    use super::{
+        acquire_workspace_lock, check_workspace_lock, clear_stale_session_state_with, lock_path,
+    };
+
+    #[test]
+    fn workspace_lock_is_shared_in_process_and_exclusive_across_handles() {
+        let data_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let workspace = "/tmp/project";
+
+        let first = acquire_workspace_lock(&data_dir, workspace).expect("acquire");
+        let second = acquire_workspace_lock(&data_dir, workspace).expect("acquire again");
+        assert!(!check_workspace_lock(&data_dir, workspace).held_elsewhere);
+        let path = lock_path(&data_dir, workspace);
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read lock"),
+            std::process::id().to_string()
+        );
+
+        // The OS lock stays until the last guard is dropped.
+        let other = OpenOptions::new().write(true).open(&path).expect("open lock");
+        drop(first);
+        assert!(other.try_lock().is_err());
+        drop(second);
+        other.try_lock().expect("released");
+
+        // A lock held through another handle stands in for another process.
+        assert!(check_workspace_lock(&data_dir, workspace).held_elsewhere);
+        assert!(acquire_workspace_lock(&data_dir, workspace).is_err());
+        drop(other);
+        let guard = acquire_workspace_lock(&data_dir, workspace).expect("acquire after release");
+        drop(guard);
+    }
+
+    #[test]
+    fn clear_stale_session_state_keeps_held_locks() {
+        let data_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let workspace = "/tmp/project";
+
+        let guard = acquire_workspace_lock(&data_dir, workspace).expect("acquire");
+        std::fs::write(data_dir.join("global_settings.json.tmp"), "{").expect("write temp");
+        std::fs::write(data_dir.join("workspaces.json"), "[]").expect("write storage");
+
+        let cleanup =
+            clear_stale_session_state_with(&data_dir, workspace, Duration::ZERO).expect("clear");
+        assert!(!cleanup.removed_lock);
+        assert!(lock_path(&data_dir, workspace).exists());
+        assert_eq!(cleanup.removed_temp_files.len(), 1);
+        assert!(!data_dir.join("global_settings.json.tmp").exists());
+        assert!(data_dir.join("workspaces.json").exists());
+
+        drop(guard);
+        let cleanup =
+            clear_stale_session_state_with(&data_dir, workspace, Duration::ZERO).expect("clear");
+        assert!(cleanup.removed_lock);
+        assert_eq!(cleanup.removed_lock_pid, Some(std::process::id()));
+        assert!(!lock_path(&data_dir, workspace).exists());
+    }
+}
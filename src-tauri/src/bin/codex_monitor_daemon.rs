@@ -7,12 +7,28 @@ mod codex_args;
 mod codex_home;
 #[path = "../codex/config.rs"]
 mod codex_config;
+#[path = "../codex/export.rs"]
+mod codex_export;
+#[path = "../files/backup.rs"]
+mod file_backup;
+#[path = "../files/cache.rs"]
+mod file_cache;
+#[path = "../files/discover.rs"]
+mod file_discover;
 #[path = "../files/io.rs"]
 mod file_io;
+#[path = "../files/merge.rs"]
+mod file_merge;
 #[path = "../files/ops.rs"]
 mod file_ops;
 #[path = "../files/policy.rs"]
 mod file_policy;
+#[path = "../files/prune.rs"]
+mod file_prune;
+#[path = "../files/validate.rs"]
+mod file_validate;
+#[path = "../logging.rs"]
+mod logging;
 #[path = "../rules.rs"]
 mod rules;
 #[path = "../storage.rs"]
@@ -21,6 +37,8 @@ mod storage;
 mod shared;
 #[path = "../utils.rs"]
 mod utils;
+#[path = "../workspaces/cancel.rs"]
+mod workspace_cancel;
 #[path = "../workspaces/settings.rs"]
 mod workspace_settings;
 #[allow(dead_code)]
@@ -35,26 +53,47 @@ mod codex {
     pub(crate) mod config {
         pub(crate) use crate::codex_config::*;
     }
+    pub(crate) mod export {
+        pub(crate) use crate::codex_export::*;
+    }
     pub(crate) mod home {
         pub(crate) use crate::codex_home::*;
     }
 }
 
 mod files {
+    pub(crate) mod backup {
+        pub(crate) use crate::file_backup::*;
+    }
+    pub(crate) mod cache {
+        pub(crate) use crate::file_cache::*;
+    }
+    pub(crate) mod discover {
+        pub(crate) use crate::file_discover::*;
+    }
     pub(crate) mod io {
         pub(crate) use crate::file_io::*;
     }
+    pub(crate) mod merge {
+        pub(crate) use crate::file_merge::*;
+    }
     pub(crate) mod ops {
         pub(crate) use crate::file_ops::*;
     }
     pub(crate) mod policy {
         pub(crate) use crate::file_policy::*;
     }
+    pub(crate) mod prune {
+        pub(crate) use crate::file_prune::*;
+    }
+    pub(crate) mod validate {
+        pub(crate) use crate::file_validate::*;
+    }
 }
 
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::fs::File;
 use std::io::Read;
@@ -66,17 +105,20 @@ use ignore::WalkBuilder;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::{broadcast, mpsc, Mutex};
+use tokio::task::JoinSet;
 
 use backend::app_server::{
     spawn_workspace_session, WorkspaceSession,
 };
 use backend::events::{AppServerEvent, EventSink, TerminalExit, TerminalOutput};
-use storage::{read_settings, read_workspaces};
+use storage::{read_global_settings, read_pins, read_settings, read_workspaces, write_workspaces};
 use shared::{codex_core, files_core, git_core, settings_core, workspaces_core, worktree_core};
 use shared::codex_core::CodexLoginCancelState;
-use workspace_settings::apply_workspace_settings_update;
+use workspace_cancel::ConnectCancellations;
+use workspace_settings::{apply_workspace_pin_order, apply_workspace_settings_update};
 use types::{
-    AppSettings, WorkspaceEntry, WorkspaceInfo, WorkspaceSettings, WorktreeSetupStatus,
+    AppSettings, BulkOperationResult, WorkspaceEntry, WorkspaceInfo, WorkspaceSettings,
+    WorktreeSetupStatus,
 };
 
 const DEFAULT_LISTEN_ADDR: &str = "127.0.0.1:4732";
@@ -84,6 +126,7 @@ const DEFAULT_LISTEN_ADDR: &str = "127.0.0.1:4732";
 fn spawn_with_client(
     event_sink: DaemonEventSink,
     client_version: String,
+    data_dir: PathBuf,
     entry: WorkspaceEntry,
     default_bin: Option<String>,
     codex_args: Option<String>,
@@ -94,6 +137,7 @@ fn spawn_with_client(
         default_bin,
         codex_args,
         codex_home,
+        data_dir,
         client_version,
         event_sink,
     )
@@ -140,8 +184,16 @@ struct DaemonState {
     storage_path: PathBuf,
     settings_path: PathBuf,
     app_settings: Mutex<AppSettings>,
+    global_settings_path: PathBuf,
+    global_settings: Mutex<types::GlobalSettings>,
+    thread_preferences_path: PathBuf,
+    pins_path: PathBuf,
+    pins: Mutex<HashMap<String, HashSet<String>>>,
     event_sink: DaemonEventSink,
     codex_login_cancels: Mutex<HashMap<String, CodexLoginCancelState>>,
+    connect_cancellations: ConnectCancellations,
+    file_cache: Mutex<file_cache::FileCache>,
+    log_levels: logging::WorkspaceLogLevels,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -151,11 +203,20 @@ struct WorkspaceFileResponse {
 }
 
 impl DaemonState {
-    fn load(config: &DaemonConfig, event_sink: DaemonEventSink) -> Self {
+    fn load(
+        config: &DaemonConfig,
+        event_sink: DaemonEventSink,
+        log_levels: logging::WorkspaceLogLevels,
+    ) -> Self {
         let storage_path = config.data_dir.join("workspaces.json");
         let settings_path = config.data_dir.join("settings.json");
+        let global_settings_path = config.data_dir.join("global_settings.json");
         let workspaces = read_workspaces(&storage_path).unwrap_or_default();
         let app_settings = read_settings(&settings_path).unwrap_or_default();
+        let global_settings = read_global_settings(&global_settings_path).unwrap_or_default();
+        let pins_path = config.data_dir.join(storage::PINS_FILENAME);
+        let pins = read_pins(&pins_path).unwrap_or_default();
+        log_levels.seed(workspaces.values());
         Self {
             data_dir: config.data_dir.clone(),
             workspaces: Mutex::new(workspaces),
@@ -163,8 +224,16 @@ impl DaemonState {
             storage_path,
             settings_path,
             app_settings: Mutex::new(app_settings),
+            global_settings_path,
+            global_settings: Mutex::new(global_settings),
+            thread_preferences_path: config.data_dir.join(storage::THREAD_PREFERENCES_FILENAME),
+            pins_path,
+            pins: Mutex::new(pins),
             event_sink,
             codex_login_cancels: Mutex::new(HashMap::new()),
+            connect_cancellations: ConnectCancellations::default(),
+            file_cache: Mutex::new(file_cache::FileCache::default()),
+            log_levels,
         }
     }
 
@@ -172,6 +241,10 @@ impl DaemonState {
         workspaces_core::list_workspaces_core(&self.workspaces, &self.sessions).await
     }
 
+    async fn get_global_status(&self) -> Vec<types::WorkspaceActivity> {
+        workspaces_core::get_global_status_core(&self.workspaces, &self.sessions).await
+    }
+
     async fn is_workspace_path_dir(&self, path: String) -> bool {
         workspaces_core::is_workspace_path_dir_core(&path)
     }
@@ -180,10 +253,15 @@ impl DaemonState {
         &self,
         path: String,
         codex_bin: Option<String>,
+        operation_id: Option<String>,
         client_version: String,
     ) -> Result<WorkspaceInfo, String> {
         let client_version = client_version.clone();
-        workspaces_core::add_workspace_core(
+        let codex_bin = match codex_bin {
+            Some(codex_bin) => Some(codex_bin),
+            None => self.global_settings.lock().await.default_codex_bin.clone(),
+        };
+        let add = workspaces_core::add_workspace_core(
             path,
             codex_bin,
             &self.workspaces,
@@ -194,14 +272,15 @@ impl DaemonState {
                 spawn_with_client(
                     self.event_sink.clone(),
                     client_version.clone(),
+                    self.data_dir.clone(),
                     entry,
                     default_bin,
                     codex_args,
                     codex_home,
                 )
             },
-        )
-        .await
+        );
+        self.connect_cancellations.run(operation_id, add).await
     }
 
     async fn add_worktree(
@@ -238,6 +317,7 @@ impl DaemonState {
                 spawn_with_client(
                     self.event_sink.clone(),
                     client_version.clone(),
+                    self.data_dir.clone(),
                     entry,
                     default_bin,
                     codex_args,
@@ -275,7 +355,8 @@ impl DaemonState {
             true,
             true,
         )
-        .await
+        .await?;
+        codex_core::prune_pins_core(&self.workspaces, &self.pins, &self.pins_path).await
     }
 
     async fn remove_worktree(&self, id: String) -> Result<(), String> {
@@ -293,7 +374,8 @@ impl DaemonState {
                     .map_err(|err| format!("Failed to remove worktree folder: {err}"))
             },
         )
-        .await
+        .await?;
+        codex_core::prune_pins_core(&self.workspaces, &self.pins, &self.pins_path).await
     }
 
     async fn rename_worktree(
@@ -330,6 +412,7 @@ impl DaemonState {
                 spawn_with_client(
                     self.event_sink.clone(),
                     client_version.clone(),
+                    self.data_dir.clone(),
                     entry,
                     default_bin,
                     codex_args,
@@ -389,13 +472,12 @@ impl DaemonState {
         client_version: String,
     ) -> Result<WorkspaceInfo, String> {
         let client_version = client_version.clone();
-        workspaces_core::update_workspace_settings_core(
+        let workspace = workspaces_core::update_workspace_settings_core(
             id,
             settings,
             &self.workspaces,
             &self.sessions,
             &self.app_settings,
-            &self.storage_path,
             |workspaces, workspace_id, next_settings| {
                 apply_workspace_settings_update(workspaces, workspace_id, next_settings)
             },
@@ -403,14 +485,18 @@ impl DaemonState {
                 spawn_with_client(
                     self.event_sink.clone(),
                     client_version.clone(),
+                    self.data_dir.clone(),
                     entry,
                     default_bin,
                     codex_args,
                     codex_home,
                 )
             },
+            |list| write_workspaces(&self.storage_path, list),
         )
-        .await
+        .await?;
+        self.log_levels.apply(&workspace.id, workspace.settings.log_level.as_deref())?;
+        Ok(workspace)
     }
 
     async fn update_workspace_codex_bin(
@@ -428,7 +514,72 @@ impl DaemonState {
         .await
     }
 
-    async fn connect_workspace(&self, id: String, client_version: String) -> Result<(), String> {
+    async fn rename_workspace(&self, id: String, name: String) -> Result<WorkspaceInfo, String> {
+        workspaces_core::rename_workspace_core(
+            id,
+            name,
+            &self.workspaces,
+            &self.sessions,
+            &self.storage_path,
+        )
+        .await
+    }
+
+    async fn set_workspace_description(
+        &self,
+        id: String,
+        text: String,
+    ) -> Result<WorkspaceInfo, String> {
+        workspaces_core::set_workspace_description_core(
+            id,
+            text,
+            &self.workspaces,
+            &self.sessions,
+            &self.storage_path,
+        )
+        .await
+    }
+
+    async fn set_monitor_only(&self, id: String, value: bool) -> Result<WorkspaceInfo, String> {
+        workspaces_core::set_monitor_only_core(
+            id,
+            value,
+            &self.workspaces,
+            &self.sessions,
+            &self.storage_path,
+        )
+        .await
+    }
+
+    async fn reload_workspaces(&self) -> Result<Vec<WorkspaceInfo>, String> {
+        let workspaces = workspaces_core::reload_workspaces_core(
+            &self.workspaces,
+            &self.sessions,
+            &self.storage_path,
+        )
+        .await?;
+        self.log_levels.seed(self.workspaces.lock().await.values());
+        Ok(workspaces)
+    }
+
+    async fn set_workspace_pin_order(&self, id: String, order: u32) -> Result<(), String> {
+        workspaces_core::set_workspace_pin_order_core(
+            id,
+            order,
+            &self.workspaces,
+            &self.storage_path,
+            apply_workspace_pin_order,
+        )
+        .await
+    }
+
+    async fn connect_workspace(
+        &self,
+        id: String,
+        restore: bool,
+        operation_id: Option<String>,
+        client_version: String,
+    ) -> Result<(), String> {
         {
             let sessions = self.sessions.lock().await;
             if sessions.contains_key(&id) {
@@ -437,23 +588,54 @@ impl DaemonState {
         }
 
         let client_version = client_version.clone();
-        workspaces_core::connect_workspace_core(
+        let connect = workspaces_core::connect_workspace_core(
             id,
             &self.workspaces,
             &self.sessions,
             &self.app_settings,
+            restore,
             move |entry, default_bin, codex_args, codex_home| {
                 spawn_with_client(
                     self.event_sink.clone(),
                     client_version.clone(),
+                    self.data_dir.clone(),
                     entry,
                     default_bin,
                     codex_args,
                     codex_home,
                 )
             },
-        )
-        .await
+        );
+        self.connect_cancellations.run(operation_id, connect).await
+    }
+
+    async fn reconnect_workspace(
+        &self,
+        id: String,
+        max_attempts: Option<u32>,
+        operation_id: Option<String>,
+        client_version: String,
+    ) -> Result<(), String> {
+        let reconnect = workspaces_core::reconnect_workspace_core(
+            id,
+            max_attempts,
+            &self.workspaces,
+            &self.sessions,
+            &self.app_settings,
+            &self.event_sink,
+            move |entry, default_bin, codex_args, codex_home| {
+                spawn_with_client(
+                    self.event_sink.clone(),
+                    client_version.clone(),
+                    self.data_dir.clone(),
+                    entry,
+                    default_bin,
+                    codex_args,
+                    codex_home,
+                )
+            },
+        );
+        self.connect_cancellations.run(operation_id, reconnect).await
     }
 
     async fn get_app_settings(&self) -> AppSettings {
@@ -465,6 +647,19 @@ impl DaemonState {
             .await
     }
 
+    async fn get_global_settings(&self) -> types::GlobalSettings {
+        self.global_settings.lock().await.clone()
+    }
+
+    async fn update_global_settings(&self, settings: types::GlobalSettings) -> Result<(), String> {
+        settings_core::update_global_settings_core(
+            settings,
+            &self.global_settings,
+            &self.global_settings_path,
+        )
+        .await
+    }
+
     async fn list_workspace_files(&self, workspace_id: String) -> Result<Vec<String>, String> {
         workspaces_core::list_workspace_files_core(&self.workspaces, &workspace_id, |root| {
             list_workspace_files_inner(root, 20000)
@@ -491,8 +686,33 @@ impl DaemonState {
         scope: file_policy::FileScope,
         kind: file_policy::FileKind,
         workspace_id: Option<String>,
+        subpath: Option<String>,
     ) -> Result<file_io::TextFileResponse, String> {
-        files_core::file_read_core(&self.workspaces, scope, kind, workspace_id).await
+        files_core::file_read_core(
+            &self.workspaces,
+            &self.file_cache,
+            scope,
+            kind,
+            workspace_id,
+            subpath,
+        )
+        .await
+    }
+
+    async fn file_read_chunk(
+        &self,
+        request: FileReadChunkRequest,
+    ) -> Result<file_io::TextFileChunk, String> {
+        files_core::file_read_chunk_core(
+            &self.workspaces,
+            request.scope,
+            request.kind,
+            request.workspace_id,
+            request.subpath,
+            request.offset.unwrap_or(0),
+            request.max_bytes,
+        )
+        .await
     }
 
     async fn file_write(
@@ -500,9 +720,78 @@ impl DaemonState {
         scope: file_policy::FileScope,
         kind: file_policy::FileKind,
         workspace_id: Option<String>,
+        subpath: Option<String>,
         content: String,
-    ) -> Result<(), String> {
-        files_core::file_write_core(&self.workspaces, scope, kind, workspace_id, content).await
+    ) -> Result<file_validate::FileWriteResponse, String> {
+        files_core::file_write_core(
+            &self.workspaces,
+            &self.file_cache,
+            scope,
+            kind,
+            workspace_id,
+            subpath,
+            content,
+        )
+        .await
+    }
+
+    async fn file_preview_write(
+        &self,
+        scope: file_policy::FileScope,
+        kind: file_policy::FileKind,
+        workspace_id: Option<String>,
+        subpath: Option<String>,
+        content: String,
+    ) -> Result<file_merge::WritePreview, String> {
+        files_core::file_preview_write_core(
+            &self.workspaces,
+            &self.file_cache,
+            scope,
+            kind,
+            workspace_id,
+            subpath,
+            content,
+        )
+        .await
+    }
+
+    async fn find_agents_files(
+        &self,
+        workspace_id: String,
+    ) -> Result<Vec<file_discover::AgentsFileEntry>, String> {
+        files_core::find_agents_files_core(&self.workspaces, &workspace_id).await
+    }
+
+    async fn file_merge(
+        &self,
+        workspace_id: String,
+        kind: file_policy::FileKind,
+    ) -> Result<file_merge::MergeResult, String> {
+        files_core::file_merge_core(&self.workspaces, workspace_id, kind).await
+    }
+
+    async fn prune_claude_json(
+        &self,
+        keep_recent: usize,
+    ) -> Result<file_prune::PruneResponse, String> {
+        files_core::prune_claude_json_core(&self.file_cache, keep_recent).await
+    }
+
+    async fn backup_configs(&self) -> Result<file_backup::ConfigBackupResponse, String> {
+        files_core::backup_configs_core(&self.workspaces, &self.file_cache, &self.data_dir).await
+    }
+
+    async fn restore_configs(
+        &self,
+        backup_path: String,
+    ) -> Result<file_backup::ConfigBackupResponse, String> {
+        files_core::restore_configs_core(
+            &self.workspaces,
+            &self.file_cache,
+            &self.data_dir,
+            &backup_path,
+        )
+        .await
     }
 
     async fn start_thread(&self, workspace_id: String) -> Result<Value, String> {
@@ -523,7 +812,17 @@ impl DaemonState {
         cursor: Option<String>,
         limit: Option<u32>,
     ) -> Result<Value, String> {
-        codex_core::list_threads_core(&self.sessions, workspace_id, cursor, limit).await
+        codex_core::list_threads_core(&self.sessions, &self.pins, workspace_id, cursor, limit)
+            .await
+    }
+
+    async fn search_threads(
+        &self,
+        workspace_id: String,
+        query: String,
+        limit: Option<u32>,
+    ) -> Result<Value, String> {
+        codex_core::search_threads_core(&self.sessions, workspace_id, query, limit).await
     }
 
     async fn list_mcp_server_status(
@@ -539,6 +838,39 @@ impl DaemonState {
         codex_core::archive_thread_core(&self.sessions, workspace_id, thread_id).await
     }
 
+    async fn set_thread_pinned(
+        &self,
+        workspace_id: String,
+        thread_id: String,
+        pinned: bool,
+    ) -> Result<Value, String> {
+        codex_core::set_thread_pinned_core(
+            &self.sessions,
+            &self.pins,
+            &self.pins_path,
+            workspace_id,
+            thread_id,
+            pinned,
+        )
+        .await
+    }
+
+    async fn export_thread_markdown(
+        &self,
+        workspace_id: String,
+        thread_id: String,
+    ) -> Result<String, String> {
+        codex_core::export_thread_markdown_core(&self.sessions, workspace_id, thread_id).await
+    }
+
+    async fn bulk_archive_threads(
+        &self,
+        workspace_id: String,
+        thread_ids: Vec<String>,
+    ) -> Result<BulkOperationResult, String> {
+        codex_core::bulk_archive_threads_core(&self.sessions, workspace_id, thread_ids).await
+    }
+
     async fn set_thread_name(
         &self,
         workspace_id: String,
@@ -558,8 +890,10 @@ impl DaemonState {
         access_mode: Option<String>,
         images: Option<Vec<String>>,
         collaboration_mode: Option<Value>,
+        focus_paths: Option<Vec<String>>,
     ) -> Result<Value, String> {
         codex_core::send_user_message_core(
+            &self.workspaces,
             &self.sessions,
             workspace_id,
             thread_id,
@@ -569,6 +903,42 @@ impl DaemonState {
             access_mode,
             images,
             collaboration_mode,
+            focus_paths,
+            &self.thread_preferences_path,
+        )
+        .await
+    }
+
+    async fn get_thread_preferences(
+        &self,
+        workspace_id: String,
+        thread_id: String,
+    ) -> Result<types::ThreadPreferences, String> {
+        codex_core::get_thread_preferences_core(
+            &self.sessions,
+            workspace_id,
+            thread_id,
+            &self.thread_preferences_path,
+        )
+        .await
+    }
+
+    async fn set_thread_preferences(
+        &self,
+        workspace_id: String,
+        thread_id: String,
+        model: Option<String>,
+        effort: Option<String>,
+        persist: bool,
+    ) -> Result<types::ThreadPreferences, String> {
+        codex_core::set_thread_preferences_core(
+            &self.sessions,
+            workspace_id,
+            thread_id,
+            model,
+            effort,
+            persist,
+            &self.thread_preferences_path,
         )
         .await
     }
@@ -582,6 +952,72 @@ impl DaemonState {
         codex_core::turn_interrupt_core(&self.sessions, workspace_id, thread_id, turn_id).await
     }
 
+    async fn get_current_turn_output(
+        &self,
+        workspace_id: String,
+        thread_id: String,
+    ) -> Result<Value, String> {
+        codex_core::get_current_turn_output_core(&self.sessions, workspace_id, thread_id).await
+    }
+
+    async fn get_session_pending_requests(
+        &self,
+        workspace_id: String,
+    ) -> Result<Vec<types::PendingRequestInfo>, String> {
+        codex_core::get_session_pending_requests_core(&self.sessions, workspace_id).await
+    }
+
+    async fn supports_method(
+        &self,
+        workspace_id: String,
+        method: String,
+    ) -> Result<Option<bool>, String> {
+        codex_core::supports_method_core(&self.sessions, workspace_id, method).await
+    }
+
+    async fn set_runtime_approval_policy(
+        &self,
+        workspace_id: String,
+        policy: String,
+    ) -> Result<(), String> {
+        codex_core::set_runtime_approval_policy_core(&self.sessions, workspace_id, policy).await
+    }
+
+    async fn get_session_info(&self, workspace_id: String) -> Result<types::SessionInfo, String> {
+        codex_core::get_session_info_core(&self.sessions, workspace_id).await
+    }
+
+    async fn get_session_process_info(
+        &self,
+        workspace_id: String,
+    ) -> Result<types::SessionProcessInfo, String> {
+        codex_core::get_session_process_info_core(&self.sessions, workspace_id).await
+    }
+
+    async fn get_path_diagnostics(
+        &self,
+        workspace_id: String,
+    ) -> Result<types::PathDiagnostics, String> {
+        codex_core::get_path_diagnostics_core(&self.workspaces, &self.app_settings, workspace_id)
+            .await
+    }
+
+    async fn get_unread_threads(
+        &self,
+        workspace_id: String,
+        last_viewed: HashMap<String, u64>,
+    ) -> Result<Vec<String>, String> {
+        codex_core::get_unread_threads_core(&self.sessions, workspace_id, last_viewed).await
+    }
+
+    async fn get_recent_threads(&self, limit: Option<u32>) -> Result<Vec<Value>, String> {
+        codex_core::get_recent_threads_core(&self.sessions, limit).await
+    }
+
+    async fn recheck_codex(&self, workspace_id: String) -> Result<Option<String>, String> {
+        codex_core::recheck_codex_core(&self.workspaces, &self.app_settings, workspace_id).await
+    }
+
     async fn start_review(
         &self,
         workspace_id: String,
@@ -593,6 +1029,14 @@ impl DaemonState {
             .await
     }
 
+    async fn list_models(
+        &self,
+        workspace_id: String,
+        refresh: bool,
+    ) -> Result<Vec<types::ModelInfo>, String> {
+        codex_core::list_models_core(&self.sessions, workspace_id, refresh).await
+    }
+
     async fn model_list(&self, workspace_id: String) -> Result<Value, String> {
         codex_core::model_list_core(&self.sessions, workspace_id).await
     }
@@ -908,6 +1352,13 @@ fn parse_optional_u32(value: &Value, key: &str) -> Option<u32> {
     }
 }
 
+fn parse_optional_bool(value: &Value, key: &str) -> Option<bool> {
+    match value {
+        Value::Object(map) => map.get(key).and_then(|value| value.as_bool()),
+        _ => None,
+    }
+}
+
 fn parse_optional_string_array(value: &Value, key: &str) -> Option<Vec<String>> {
     match value {
         Value::Object(map) => map.get(key).and_then(|value| value.as_array()).map(|items| {
@@ -937,6 +1388,18 @@ struct FileReadRequest {
     scope: file_policy::FileScope,
     kind: file_policy::FileKind,
     workspace_id: Option<String>,
+    subpath: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FileReadChunkRequest {
+    scope: file_policy::FileScope,
+    kind: file_policy::FileKind,
+    workspace_id: Option<String>,
+    subpath: Option<String>,
+    offset: Option<u64>,
+    max_bytes: Option<u32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -945,17 +1408,33 @@ struct FileWriteRequest {
     scope: file_policy::FileScope,
     kind: file_policy::FileKind,
     workspace_id: Option<String>,
+    subpath: Option<String>,
     content: String,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FileMergeRequest {
+    workspace_id: String,
+    kind: file_policy::FileKind,
+}
+
 fn parse_file_read_request(params: &Value) -> Result<FileReadRequest, String> {
     serde_json::from_value(params.clone()).map_err(|err| err.to_string())
 }
 
+fn parse_file_read_chunk_request(params: &Value) -> Result<FileReadChunkRequest, String> {
+    serde_json::from_value(params.clone()).map_err(|err| err.to_string())
+}
+
 fn parse_file_write_request(params: &Value) -> Result<FileWriteRequest, String> {
     serde_json::from_value(params.clone()).map_err(|err| err.to_string())
 }
 
+fn parse_file_merge_request(params: &Value) -> Result<FileMergeRequest, String> {
+    serde_json::from_value(params.clone()).map_err(|err| err.to_string())
+}
+
 async fn handle_rpc_request(
     state: &DaemonState,
     method: &str,
@@ -964,6 +1443,10 @@ async fn handle_rpc_request(
 ) -> Result<Value, String> {
     match method {
         "ping" => Ok(json!({ "ok": true })),
+        "get_global_status" => {
+            let status = state.get_global_status().await;
+            serde_json::to_value(status).map_err(|err| err.to_string())
+        }
         "list_workspaces" => {
             let workspaces = state.list_workspaces().await;
             serde_json::to_value(workspaces).map_err(|err| err.to_string())
@@ -976,7 +1459,10 @@ async fn handle_rpc_request(
         "add_workspace" => {
             let path = parse_string(&params, "path")?;
             let codex_bin = parse_optional_string(&params, "codex_bin");
-            let workspace = state.add_workspace(path, codex_bin, client_version).await?;
+            let operation_id = parse_optional_string(&params, "operationId");
+            let workspace = state
+                .add_workspace(path, codex_bin, operation_id, client_version)
+                .await?;
             serde_json::to_value(workspace).map_err(|err| err.to_string())
         }
         "add_worktree" => {
@@ -999,7 +1485,25 @@ async fn handle_rpc_request(
         }
         "connect_workspace" => {
             let id = parse_string(&params, "id")?;
-            state.connect_workspace(id, client_version).await?;
+            let restore = parse_optional_bool(&params, "restore").unwrap_or(false);
+            let operation_id = parse_optional_string(&params, "operationId");
+            state
+                .connect_workspace(id, restore, operation_id, client_version)
+                .await?;
+            Ok(json!({ "ok": true }))
+        }
+        "reconnect_workspace" => {
+            let id = parse_string(&params, "id")?;
+            let max_attempts = parse_optional_u32(&params, "maxAttempts");
+            let operation_id = parse_optional_string(&params, "operationId");
+            state
+                .reconnect_workspace(id, max_attempts, operation_id, client_version)
+                .await?;
+            Ok(json!({ "ok": true }))
+        }
+        "cancel_connect" => {
+            let operation_id = parse_string(&params, "operationId")?;
+            state.connect_cancellations.cancel(&operation_id).await?;
             Ok(json!({ "ok": true }))
         }
         "remove_workspace" => {
@@ -1046,6 +1550,41 @@ async fn handle_rpc_request(
             let workspace = state.update_workspace_codex_bin(id, codex_bin).await?;
             serde_json::to_value(workspace).map_err(|err| err.to_string())
         }
+        "reload_log_level" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let level = parse_string(&params, "level")?;
+            state.log_levels.set(&workspace_id, logging::parse_log_level(&level)?);
+            Ok(json!({ "ok": true }))
+        }
+        "rename_workspace" => {
+            let id = parse_string(&params, "id")?;
+            let name = parse_string(&params, "name")?;
+            let workspace = state.rename_workspace(id, name).await?;
+            serde_json::to_value(workspace).map_err(|err| err.to_string())
+        }
+        "set_workspace_description" => {
+            let id = parse_string(&params, "id")?;
+            let text = parse_optional_string(&params, "text").unwrap_or_default();
+            let workspace = state.set_workspace_description(id, text).await?;
+            serde_json::to_value(workspace).map_err(|err| err.to_string())
+        }
+        "set_monitor_only" => {
+            let id = parse_string(&params, "id")?;
+            let value = parse_optional_bool(&params, "value").ok_or("missing `value`")?;
+            let workspace = state.set_monitor_only(id, value).await?;
+            serde_json::to_value(workspace).map_err(|err| err.to_string())
+        }
+        "reload_workspaces" => {
+            let workspaces = state.reload_workspaces().await?;
+            serde_json::to_value(workspaces).map_err(|err| err.to_string())
+        }
+        "set_workspace_pin_order" => {
+            let id = parse_string(&params, "id")?;
+            let order = parse_optional_u32(&params, "order")
+                .ok_or_else(|| "missing or invalid `order`".to_string())?;
+            state.set_workspace_pin_order(id, order).await?;
+            Ok(json!({ "ok": true }))
+        }
         "list_workspace_files" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             let files = state.list_workspace_files(workspace_id).await?;
@@ -1060,21 +1599,70 @@ async fn handle_rpc_request(
         "file_read" => {
             let request = parse_file_read_request(&params)?;
             let response = state
-                .file_read(request.scope, request.kind, request.workspace_id)
+                .file_read(
+                    request.scope,
+                    request.kind,
+                    request.workspace_id,
+                    request.subpath,
+                )
                 .await?;
             serde_json::to_value(response).map_err(|err| err.to_string())
         }
+        "file_read_chunk" => {
+            let request = parse_file_read_chunk_request(&params)?;
+            let response = state.file_read_chunk(request).await?;
+            serde_json::to_value(response).map_err(|err| err.to_string())
+        }
         "file_write" => {
             let request = parse_file_write_request(&params)?;
-            state
+            let response = state
                 .file_write(
                     request.scope,
                     request.kind,
                     request.workspace_id,
+                    request.subpath,
                     request.content,
                 )
                 .await?;
-            serde_json::to_value(json!({ "ok": true })).map_err(|err| err.to_string())
+            serde_json::to_value(response).map_err(|err| err.to_string())
+        }
+        "file_preview_write" => {
+            let request = parse_file_write_request(&params)?;
+            let response = state
+                .file_preview_write(
+                    request.scope,
+                    request.kind,
+                    request.workspace_id,
+                    request.subpath,
+                    request.content,
+                )
+                .await?;
+            serde_json::to_value(response).map_err(|err| err.to_string())
+        }
+        "find_agents_files" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let files = state.find_agents_files(workspace_id).await?;
+            serde_json::to_value(files).map_err(|err| err.to_string())
+        }
+        "file_merge" => {
+            let request = parse_file_merge_request(&params)?;
+            let response = state.file_merge(request.workspace_id, request.kind).await?;
+            serde_json::to_value(response).map_err(|err| err.to_string())
+        }
+        "prune_claude_json" => {
+            let keep_recent =
+                parse_optional_u32(&params, "keepRecent").ok_or("missing `keepRecent`")?;
+            let response = state.prune_claude_json(keep_recent as usize).await?;
+            serde_json::to_value(response).map_err(|err| err.to_string())
+        }
+        "backup_configs" => {
+            let response = state.backup_configs().await?;
+            serde_json::to_value(response).map_err(|err| err.to_string())
+        }
+        "restore_configs" => {
+            let backup_path = parse_string(&params, "backupPath")?;
+            let response = state.restore_configs(backup_path).await?;
+            serde_json::to_value(response).map_err(|err| err.to_string())
         }
         "get_app_settings" => {
             let settings = state.get_app_settings().await;
@@ -1090,6 +1678,20 @@ async fn handle_rpc_request(
             let updated = state.update_app_settings(settings).await?;
             serde_json::to_value(updated).map_err(|err| err.to_string())
         }
+        "get_global_settings" => {
+            let settings = state.get_global_settings().await;
+            serde_json::to_value(settings).map_err(|err| err.to_string())
+        }
+        "update_global_settings" => {
+            let settings_value = match params {
+                Value::Object(map) => map.get("settings").cloned().unwrap_or(Value::Null),
+                _ => Value::Null,
+            };
+            let settings: types::GlobalSettings =
+                serde_json::from_value(settings_value).map_err(|err| err.to_string())?;
+            state.update_global_settings(settings).await?;
+            Ok(json!({ "ok": true }))
+        }
         "get_codex_config_path" => {
             let path = settings_core::get_codex_config_path_core()?;
             Ok(Value::String(path))
@@ -1118,6 +1720,12 @@ async fn handle_rpc_request(
             let limit = parse_optional_u32(&params, "limit");
             state.list_threads(workspace_id, cursor, limit).await
         }
+        "search_threads" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let query = parse_string(&params, "query")?;
+            let limit = parse_optional_u32(&params, "limit");
+            state.search_threads(workspace_id, query, limit).await
+        }
         "list_mcp_server_status" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             let cursor = parse_optional_string(&params, "cursor");
@@ -1129,6 +1737,24 @@ async fn handle_rpc_request(
             let thread_id = parse_string(&params, "threadId")?;
             state.archive_thread(workspace_id, thread_id).await
         }
+        "pin_thread" | "unpin_thread" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let thread_id = parse_string(&params, "threadId")?;
+            let pinned = method == "pin_thread";
+            state.set_thread_pinned(workspace_id, thread_id, pinned).await
+        }
+        "export_thread_markdown" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let thread_id = parse_string(&params, "threadId")?;
+            let markdown = state.export_thread_markdown(workspace_id, thread_id).await?;
+            Ok(Value::String(markdown))
+        }
+        "bulk_archive_threads" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let thread_ids = parse_string_array(&params, "threadIds")?;
+            let result = state.bulk_archive_threads(workspace_id, thread_ids).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
         "set_thread_name" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             let thread_id = parse_string(&params, "threadId")?;
@@ -1144,6 +1770,7 @@ async fn handle_rpc_request(
             let access_mode = parse_optional_string(&params, "accessMode");
             let images = parse_optional_string_array(&params, "images");
             let collaboration_mode = parse_optional_value(&params, "collaborationMode");
+            let focus_paths = parse_optional_string_array(&params, "focusPaths");
             state
                 .send_user_message(
                     workspace_id,
@@ -1154,15 +1781,91 @@ async fn handle_rpc_request(
                     access_mode,
                     images,
                     collaboration_mode,
+                    focus_paths,
                 )
                 .await
         }
+        "get_thread_preferences" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let thread_id = parse_string(&params, "threadId")?;
+            let preferences = state.get_thread_preferences(workspace_id, thread_id).await?;
+            serde_json::to_value(preferences).map_err(|err| err.to_string())
+        }
+        "set_thread_preferences" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let thread_id = parse_string(&params, "threadId")?;
+            let model = parse_optional_string(&params, "model");
+            let effort = parse_optional_string(&params, "effort");
+            let persist = parse_optional_bool(&params, "persist").unwrap_or(false);
+            let preferences = state
+                .set_thread_preferences(workspace_id, thread_id, model, effort, persist)
+                .await?;
+            serde_json::to_value(preferences).map_err(|err| err.to_string())
+        }
         "turn_interrupt" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             let thread_id = parse_string(&params, "threadId")?;
             let turn_id = parse_string(&params, "turnId")?;
             state.turn_interrupt(workspace_id, thread_id, turn_id).await
         }
+        "get_current_turn_output" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let thread_id = parse_string(&params, "threadId")?;
+            state.get_current_turn_output(workspace_id, thread_id).await
+        }
+        "get_session_pending_requests" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let requests = state.get_session_pending_requests(workspace_id).await?;
+            serde_json::to_value(requests).map_err(|err| err.to_string())
+        }
+        "supports_method" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let method = parse_string(&params, "method")?;
+            let supported = state.supports_method(workspace_id, method).await?;
+            serde_json::to_value(supported).map_err(|err| err.to_string())
+        }
+        "set_runtime_approval_policy" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let policy = parse_string(&params, "policy")?;
+            state
+                .set_runtime_approval_policy(workspace_id, policy)
+                .await?;
+            Ok(json!({ "ok": true }))
+        }
+        "get_session_info" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let info = state.get_session_info(workspace_id).await?;
+            serde_json::to_value(info).map_err(|err| err.to_string())
+        }
+        "get_session_process_info" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let info = state.get_session_process_info(workspace_id).await?;
+            serde_json::to_value(info).map_err(|err| err.to_string())
+        }
+        "get_path_diagnostics" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let diagnostics = state.get_path_diagnostics(workspace_id).await?;
+            serde_json::to_value(diagnostics).map_err(|err| err.to_string())
+        }
+        "get_unread_threads" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let last_viewed = match parse_optional_value(&params, "lastViewed") {
+                Some(value) => serde_json::from_value(value).map_err(|err| err.to_string())?,
+                None => HashMap::new(),
+            };
+            let unread = state.get_unread_threads(workspace_id, last_viewed).await?;
+            serde_json::to_value(unread).map_err(|err| err.to_string())
+        }
+        "get_recent_threads" => {
+            let limit = parse_optional_u32(&params, "limit");
+            let threads = state.get_recent_threads(limit).await?;
+            Ok(Value::Array(threads))
+        }
+        "recheck_codex" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let version = state.recheck_codex(workspace_id).await?;
+            Ok(json!(version))
+        }
         "start_review" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             let thread_id = parse_string(&params, "threadId")?;
@@ -1174,6 +1877,12 @@ async fn handle_rpc_request(
             let delivery = parse_optional_string(&params, "delivery");
             state.start_review(workspace_id, thread_id, target, delivery).await
         }
+        "list_models" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let refresh = parse_optional_bool(&params, "refresh").unwrap_or(false);
+            let models = state.list_models(workspace_id, refresh).await?;
+            serde_json::to_value(models).map_err(|err| err.to_string())
+        }
         "model_list" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             state.model_list(workspace_id).await
@@ -1251,6 +1960,24 @@ async fn forward_events(
     }
 }
 
+/// Connects can take a while, so they run beside the read loop and a `cancel_connect` sent
+/// after one isn't queued behind it. Every other request is answered in order.
+const CANCELLABLE_METHODS: [&str; 3] =
+    ["add_workspace", "connect_workspace", "reconnect_workspace"];
+
+async fn rpc_response(
+    state: &DaemonState,
+    id: Option<u64>,
+    method: &str,
+    params: Value,
+    client_version: String,
+) -> Option<String> {
+    match handle_rpc_request(state, method, params, client_version).await {
+        Ok(result) => build_result_response(id, result),
+        Err(message) => build_error_response(id, &message),
+    }
+}
+
 async fn handle_client(
     socket: TcpStream,
     config: Arc<DaemonConfig>,
@@ -1274,6 +2001,7 @@ async fn handle_client(
 
     let mut authenticated = config.token.is_none();
     let mut events_task: Option<tokio::task::JoinHandle<()>> = None;
+    let mut connect_tasks = JoinSet::new();
 
     if authenticated {
         let rx = events.subscribe();
@@ -1329,17 +2057,26 @@ async fn handle_client(
             continue;
         }
 
+        while connect_tasks.try_join_next().is_some() {}
         let client_version = format!("daemon-{}", env!("CARGO_PKG_VERSION"));
-        let result = handle_rpc_request(&state, &method, params, client_version).await;
-        let response = match result {
-            Ok(result) => build_result_response(id, result),
-            Err(message) => build_error_response(id, &message),
-        };
-        if let Some(response) = response {
+        if CANCELLABLE_METHODS.contains(&method.as_str()) {
+            let state = Arc::clone(&state);
+            let out_tx = out_tx.clone();
+            connect_tasks.spawn(async move {
+                if let Some(response) =
+                    rpc_response(&state, id, &method, params, client_version).await
+                {
+                    let _ = out_tx.send(response);
+                }
+            });
+            continue;
+        }
+        if let Some(response) = rpc_response(&state, id, &method, params, client_version).await {
             let _ = out_tx.send(response);
         }
     }
 
+    connect_tasks.abort_all();
     drop(out_tx);
     if let Some(task) = events_task {
         task.abort();
@@ -1366,7 +2103,9 @@ fn main() {
         let event_sink = DaemonEventSink {
             tx: events_tx.clone(),
         };
-        let state = Arc::new(DaemonState::load(&config, event_sink));
+        let log_levels = logging::WorkspaceLogLevels::default();
+        logging::init_tracing(log_levels.clone());
+        let state = Arc::new(DaemonState::load(&config, event_sink, log_levels));
         let config = Arc::new(config);
 
         let listener = TcpListener::bind(config.listen)
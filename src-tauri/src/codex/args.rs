@@ -98,6 +98,9 @@ mod tests {
                 codex_args: Some("--profile parent".to_string()),
                 ..WorkspaceSettings::default()
             },
+            last_error: None,
+            monitor_only: false,
+            description: None,
         };
 
         let child = WorkspaceEntry {
@@ -109,6 +112,9 @@ mod tests {
             parent_id: Some(parent.id.clone()),
             worktree: None,
             settings: WorkspaceSettings::default(),
+            last_error: None,
+            monitor_only: false,
+            description: None,
         };
 
         let resolved = resolve_workspace_codex_args(&child, Some(&parent), Some(&app_settings));
@@ -129,6 +135,9 @@ mod tests {
             parent_id: None,
             worktree: None,
             settings: WorkspaceSettings::default(),
+            last_error: None,
+            monitor_only: false,
+            description: None,
         };
         let resolved_main = resolve_workspace_codex_args(&main, None, Some(&app_settings));
         assert_eq!(resolved_main.as_deref(), Some("--profile app"));
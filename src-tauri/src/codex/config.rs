@@ -72,6 +72,9 @@ pub(crate) fn write_personality(personality: &str) -> Result<(), String> {
         policy.filename,
         policy.allow_external_symlink_target,
     )?;
+    if response.truncated {
+        return Err(format!("{} is too large to update", policy.filename));
+    }
     let contents = if response.exists {
         response.content
     } else {
@@ -106,6 +109,9 @@ fn write_feature_flag(key: &str, enabled: bool) -> Result<(), String> {
         policy.filename,
         policy.allow_external_symlink_target,
     )?;
+    if response.truncated {
+        return Err(format!("{} is too large to update", policy.filename));
+    }
     let contents = if response.exists {
         response.content
     } else {
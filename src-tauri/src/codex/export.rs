@@ -0,0 +1,304 @@
+use serde_json::Value;
+
+fn first_str<'a>(value: &'a Value, keys: &[&str]) -> Option<&'a str> {
+    keys.iter()
+        .find_map(|key| value.get(key).and_then(Value::as_str))
+        .filter(|text| !text.trim().is_empty())
+}
+
+/// Unix ms of an app-server timestamp, reported as RFC 3339 or as epoch seconds/milliseconds
+/// (possibly as a numeric string). Numbers below 1e12 are taken to be seconds.
+pub(crate) fn timestamp_ms(raw: &Value) -> Option<i64> {
+    let number = match raw {
+        Value::String(text) => match text.trim().parse::<f64>() {
+            Ok(number) => number,
+            Err(_) => {
+                return chrono::DateTime::parse_from_rfc3339(text.trim())
+                    .ok()
+                    .map(|time| time.timestamp_millis());
+            }
+        },
+        value => value.as_f64()?,
+    };
+    if !number.is_finite() || number <= 0.0 {
+        return None;
+    }
+    if number < 1_000_000_000_000.0 {
+        Some((number * 1000.0) as i64)
+    } else {
+        Some(number as i64)
+    }
+}
+
+/// Formats `createdAt`-style fields. RFC 3339 strings are kept as reported.
+fn timestamp(value: &Value) -> Option<String> {
+    let raw = ["createdAt", "created_at", "timestamp", "startedAt"]
+        .iter()
+        .find_map(|key| value.get(key))?;
+    if let Some(text) = raw.as_str().filter(|text| text.trim().parse::<f64>().is_err()) {
+        return Some(text.to_string()).filter(|text| !text.trim().is_empty());
+    }
+    chrono::DateTime::from_timestamp_millis(timestamp_ms(raw)?).map(|time| time.to_rfc3339())
+}
+
+/// A backtick fence longer than any run of backticks inside `content`.
+fn fence_for(content: &str) -> String {
+    let mut longest = 0;
+    let mut run = 0;
+    for ch in content.chars() {
+        run = if ch == '`' { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+    "`".repeat((longest + 1).max(3))
+}
+
+fn fenced(content: &str, language: &str) -> String {
+    let fence = fence_for(content);
+    format!("{fence}{language}\n{}\n{fence}", content.trim_end_matches('\n'))
+}
+
+fn user_text(item: &Value) -> String {
+    let Some(inputs) = item.get("content").and_then(Value::as_array) else {
+        return first_str(item, &["text"]).unwrap_or_default().to_string();
+    };
+    let parts: Vec<String> = inputs
+        .iter()
+        .filter_map(|input| match input.get("type").and_then(Value::as_str) {
+            Some("text") => first_str(input, &["text"]).map(str::to_string),
+            Some("skill") => first_str(input, &["name"]).map(|name| format!("${name}")),
+            _ => None,
+        })
+        .collect();
+    parts.join(" ").trim().to_string()
+}
+
+/// Agent messages carry plain Markdown in `text`; when structured `content` is present, its
+/// code entries are fenced with their language hint.
+fn assistant_text(item: &Value) -> String {
+    let Some(blocks) = item.get("content").and_then(Value::as_array) else {
+        return first_str(item, &["text"]).unwrap_or_default().trim().to_string();
+    };
+    let parts: Vec<String> = blocks
+        .iter()
+        .filter_map(|block| match block.get("type").and_then(Value::as_str) {
+            Some("code" | "codeBlock") => {
+                let code = first_str(block, &["code", "text"])?;
+                Some(fenced(code, first_str(block, &["language", "lang"]).unwrap_or_default()))
+            }
+            _ => first_str(block, &["text"]).map(|text| text.trim().to_string()),
+        })
+        .collect();
+    parts.join("\n\n")
+}
+
+fn tool_call(item: &Value, item_type: &str) -> Option<(String, String)> {
+    let detail = |key: &str| match item.get(key) {
+        Some(Value::String(text)) => text.clone(),
+        Some(value) if !value.is_null() => {
+            serde_json::to_string_pretty(value).unwrap_or_default()
+        }
+        _ => String::new(),
+    };
+    match item_type {
+        "commandExecution" => {
+            let command = match item.get("command") {
+                Some(Value::Array(parts)) => parts
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                _ => detail("command"),
+            };
+            Some((format!("Command: {command}"), detail("aggregatedOutput")))
+        }
+        "mcpToolCall" => {
+            let server = first_str(item, &["server"]).unwrap_or_default();
+            let tool = first_str(item, &["tool"]).unwrap_or("tool");
+            let name = if server.is_empty() {
+                tool.to_string()
+            } else {
+                format!("{server}/{tool}")
+            };
+            let mut body = detail("arguments");
+            let result = item.get("result").or_else(|| item.get("error"));
+            if let Some(result) = result.filter(|result| !result.is_null()) {
+                let result = match result {
+                    Value::String(text) => text.clone(),
+                    value => serde_json::to_string_pretty(value).unwrap_or_default(),
+                };
+                body = [body, result]
+                    .into_iter()
+                    .filter(|part| !part.is_empty())
+                    .collect::<Vec<_>>()
+                    .join("\n\n");
+            }
+            Some((format!("Tool: {name}"), body))
+        }
+        "fileChange" => {
+            let paths: Vec<&str> = item
+                .get("changes")
+                .and_then(Value::as_array)
+                .map(|changes| {
+                    changes
+                        .iter()
+                        .filter_map(|change| first_str(change, &["path"]))
+                        .collect()
+                })
+                .unwrap_or_default();
+            Some(("File changes".to_string(), paths.join("\n")))
+        }
+        "webSearch" => Some(("Web search".to_string(), detail("query"))),
+        "collabToolCall" | "collabAgentToolCall" => {
+            let tool = first_str(item, &["tool"]).unwrap_or("collab");
+            Some((format!("Tool: {tool}"), detail("prompt")))
+        }
+        _ => None,
+    }
+}
+
+/// Escapes text placed inside raw HTML such as `<summary>`, where Markdown renderers do not
+/// escape it for us.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn push_message(out: &mut String, role: &str, stamp: Option<&str>, text: &str) {
+    out.push_str(&format!("## {role}\n\n"));
+    if let Some(stamp) = stamp {
+        out.push_str(&format!("_{stamp}_\n\n"));
+    }
+    out.push_str(text);
+    out.push_str("\n\n");
+}
+
+/// Renders a thread's turns as Markdown: a `## User` / `## Assistant` section per message
+/// and a collapsed `<details>` block per tool call. Reasoning and other items are skipped.
+pub(crate) fn thread_markdown(thread_id: &str, turns: &[Value]) -> String {
+    let mut out = format!("# Thread {thread_id}\n\n");
+    for turn in turns {
+        let turn_stamp = timestamp(turn);
+        let Some(items) = turn.get("items").and_then(Value::as_array) else {
+            continue;
+        };
+        for item in items {
+            let stamp = timestamp(item).or_else(|| turn_stamp.clone());
+            let item_type = item.get("type").and_then(Value::as_str).unwrap_or_default();
+            match item_type {
+                "userMessage" => {
+                    let text = user_text(item);
+                    if !text.is_empty() {
+                        push_message(&mut out, "User", stamp.as_deref(), &text);
+                    }
+                }
+                "agentMessage" => {
+                    let text = assistant_text(item);
+                    if !text.is_empty() {
+                        push_message(&mut out, "Assistant", stamp.as_deref(), &text);
+                    }
+                }
+                _ => {
+                    let Some((summary, body)) = tool_call(item, item_type) else {
+                        continue;
+                    };
+                    let summary = escape_html(&summary);
+                    out.push_str(&format!("<details>\n<summary>{summary}</summary>\n\n"));
+                    if !body.trim().is_empty() {
+                        out.push_str(&fenced(&body, ""));
+                        out.push_str("\n\n");
+                    }
+                    out.push_str("</details>\n\n");
+                }
+            }
+        }
+    }
+    format!("{}\n", out.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{escape_html, fence_for, thread_markdown, timestamp_ms};
+
+    #[test]
+    fn thread_markdown_renders_messages_code_and_tools() {
+        let turns = vec![json!({
+            "createdAt": 1_700_000_000,
+            "items": [
+                {
+                    "id": "1",
+                    "type": "userMessage",
+                    "content": [{ "type": "text", "text": "Fix the build" }],
+                },
+                { "id": "2", "type": "reasoning", "summary": "thinking" },
+                {
+                    "id": "3",
+                    "type": "commandExecution",
+                    "command": ["cargo", "build"],
+                    "aggregatedOutput": "error[E0425]",
+                },
+                {
+                    "id": "4",
+                    "type": "agentMessage",
+                    "createdAt": "2024-01-01T00:00:00Z",
+                    "content": [
+                        { "type": "text", "text": "Renamed the import:" },
+                        { "type": "code", "language": "rust", "code": "use crate::a;" },
+                    ],
+                },
+            ],
+        })];
+        let markdown = thread_markdown("t1", &turns);
+        assert!(markdown.starts_with("# Thread t1\n\n## User\n\n_2023-11-14T22:13:20+00:00_\n"));
+        assert!(markdown.contains("Fix the build"));
+        assert!(!markdown.contains("thinking"));
+        assert!(markdown.contains(
+            "<details>\n<summary>Command: cargo build</summary>\n\n```\nerror[E0425]\n```"
+        ));
+        assert!(markdown.contains("## Assistant\n\n_2024-01-01T00:00:00Z_\n"));
+        assert!(markdown.contains("Renamed the import:\n\n```rust\nuse crate::a;\n```"));
+        assert!(markdown.ends_with("```\n"));
+    }
+
+    #[test]
+    fn fences_outgrow_backticks_in_content() {
+        assert_eq!(fence_for("plain"), "```");
+        assert_eq!(fence_for("has ```` inside"), "`````");
+    }
+
+    #[test]
+    fn timestamps_accept_seconds_millis_and_rfc3339() {
+        assert_eq!(timestamp_ms(&json!(1_700_000_000)), Some(1_700_000_000_000));
+        assert_eq!(timestamp_ms(&json!(1_700_000_000_123u64)), Some(1_700_000_000_123));
+        // Seconds past 2286 must not be mistaken for milliseconds.
+        assert_eq!(timestamp_ms(&json!(20_000_000_000u64)), Some(20_000_000_000_000));
+        assert_eq!(timestamp_ms(&json!("1700000000")), Some(1_700_000_000_000));
+        assert_eq!(timestamp_ms(&json!("1970-01-01T00:00:01Z")), Some(1000));
+        assert_eq!(timestamp_ms(&json!(0)), None);
+        assert_eq!(timestamp_ms(&json!("soon")), None);
+    }
+
+    #[test]
+    fn tool_summaries_are_html_escaped() {
+        assert_eq!(escape_html(r#"echo "<b>" && ls"#), "echo &quot;&lt;b&gt;&quot; &amp;&amp; ls");
+        let turns = vec![json!({
+            "items": [{
+                "id": "1",
+                "type": "commandExecution",
+                "command": ["grep", "</summary>", "x"],
+            }],
+        })];
+        let markdown = thread_markdown("t1", &turns);
+        assert!(markdown.contains("<summary>Command: grep &lt;/summary&gt; x</summary>"));
+    }
+}
@@ -216,6 +216,9 @@ mod tests {
                 codex_home: codex_home.map(|value| value.to_string()),
                 ..WorkspaceSettings::default()
             },
+            last_error: None,
+            monitor_only: false,
+            description: None,
         }
     }
 
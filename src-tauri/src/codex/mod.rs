@@ -1,15 +1,17 @@
 use serde_json::{json, Map, Value};
+use std::collections::HashMap;
 use std::io::ErrorKind;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::sync::mpsc;
 use tokio::time::timeout;
 
 pub(crate) mod args;
 pub(crate) mod config;
+pub(crate) mod export;
 pub(crate) mod home;
 
 pub(crate) use crate::backend::app_server::WorkspaceSession;
@@ -20,10 +22,14 @@ use crate::backend::app_server::{
 };
 use crate::shared::process_core::tokio_command;
 use crate::event_sink::TauriEventSink;
+use crate::git::watcher::sync_git_status_watcher;
 use crate::remote_backend;
 use crate::shared::codex_core;
 use crate::state::AppState;
-use crate::types::WorkspaceEntry;
+use crate::types::{
+    BulkOperationResult, ModelInfo, PathDiagnostics, PendingRequestInfo, SessionInfo,
+    SessionProcessInfo, ThreadPreferences, WorkspaceEntry,
+};
 use self::args::apply_codex_args;
 
 pub(crate) async fn spawn_workspace_session(
@@ -34,16 +40,23 @@ pub(crate) async fn spawn_workspace_session(
     codex_home: Option<PathBuf>,
 ) -> Result<Arc<WorkspaceSession>, String> {
     let client_version = app_handle.package_info().version.to_string();
-    let event_sink = TauriEventSink::new(app_handle);
-    spawn_workspace_session_inner(
-        entry,
+    let data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    let event_sink = TauriEventSink::new(app_handle.clone());
+    let session = spawn_workspace_session_inner(
+        entry.clone(),
         default_codex_bin,
         codex_args,
         codex_home,
+        data_dir,
         client_version,
         event_sink,
     )
-    .await
+    .await?;
+    sync_git_status_watcher(&app_handle, &entry, Some(&session)).await;
+    Ok(session)
 }
 
 #[tauri::command]
@@ -221,7 +234,33 @@ pub(crate) async fn list_threads(
         .await;
     }
 
-    codex_core::list_threads_core(&state.sessions, workspace_id, cursor, limit).await
+    codex_core::list_threads_core(&state.sessions, &state.pins, workspace_id, cursor, limit)
+        .await
+}
+
+/// Searches thread titles and previews. Uses the app-server's `thread/search` when it exists;
+/// otherwise falls back to filtering one large `thread/list` page here, in which case threads
+/// beyond that page are not searched. The response's `searchMethod` is `"server"` or
+/// `"client"` accordingly.
+#[tauri::command]
+pub(crate) async fn search_threads(
+    workspace_id: String,
+    query: String,
+    limit: Option<u32>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return remote_backend::call_remote(
+            &*state,
+            app,
+            "search_threads",
+            json!({ "workspaceId": workspace_id, "query": query, "limit": limit }),
+        )
+        .await;
+    }
+
+    codex_core::search_threads_core(&state.sessions, workspace_id, query, limit).await
 }
 
 #[tauri::command]
@@ -265,6 +304,99 @@ pub(crate) async fn archive_thread(
     codex_core::archive_thread_core(&state.sessions, workspace_id, thread_id).await
 }
 
+#[tauri::command]
+pub(crate) async fn pin_thread(
+    workspace_id: String,
+    thread_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    set_thread_pinned(workspace_id, thread_id, true, state, app).await
+}
+
+#[tauri::command]
+pub(crate) async fn unpin_thread(
+    workspace_id: String,
+    thread_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    set_thread_pinned(workspace_id, thread_id, false, state, app).await
+}
+
+async fn set_thread_pinned(
+    workspace_id: String,
+    thread_id: String,
+    pinned: bool,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let method = if pinned { "pin_thread" } else { "unpin_thread" };
+        return remote_backend::call_remote(
+            &*state,
+            app,
+            method,
+            json!({ "workspaceId": workspace_id, "threadId": thread_id }),
+        )
+        .await;
+    }
+
+    codex_core::set_thread_pinned_core(
+        &state.sessions,
+        &state.pins,
+        &state.pins_path,
+        workspace_id,
+        thread_id,
+        pinned,
+    )
+    .await
+}
+
+/// Renders the thread as a Markdown document; the frontend saves it via the dialog plugin.
+#[tauri::command]
+pub(crate) async fn export_thread_markdown(
+    workspace_id: String,
+    thread_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "export_thread_markdown",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_core::export_thread_markdown_core(&state.sessions, workspace_id, thread_id).await
+}
+
+/// Archives every thread in `thread_ids`, reporting per-thread failures instead of stopping.
+#[tauri::command]
+pub(crate) async fn bulk_archive_threads(
+    workspace_id: String,
+    thread_ids: Vec<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<BulkOperationResult, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "bulk_archive_threads",
+            json!({ "workspaceId": workspace_id, "threadIds": thread_ids }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_core::bulk_archive_threads_core(&state.sessions, workspace_id, thread_ids).await
+}
+
 #[tauri::command]
 pub(crate) async fn set_thread_name(
     workspace_id: String,
@@ -296,6 +428,7 @@ pub(crate) async fn send_user_message(
     access_mode: Option<String>,
     images: Option<Vec<String>>,
     collaboration_mode: Option<Value>,
+    focus_paths: Option<Vec<String>>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Value, String> {
@@ -306,6 +439,12 @@ pub(crate) async fn send_user_message(
                 .map(remote_backend::normalize_path_for_remote)
                 .collect::<Vec<_>>()
         });
+        let focus_paths = focus_paths.map(|paths| {
+            paths
+                .into_iter()
+                .map(remote_backend::normalize_path_for_remote)
+                .collect::<Vec<_>>()
+        });
         let mut payload = Map::new();
         payload.insert("workspaceId".to_string(), json!(workspace_id));
         payload.insert("threadId".to_string(), json!(thread_id));
@@ -314,6 +453,9 @@ pub(crate) async fn send_user_message(
         payload.insert("effort".to_string(), json!(effort));
         payload.insert("accessMode".to_string(), json!(access_mode));
         payload.insert("images".to_string(), json!(images));
+        if let Some(focus_paths) = focus_paths {
+            payload.insert("focusPaths".to_string(), json!(focus_paths));
+        }
         if let Some(mode) = collaboration_mode {
             if !mode.is_null() {
                 payload.insert("collaborationMode".to_string(), mode);
@@ -329,6 +471,7 @@ pub(crate) async fn send_user_message(
     }
 
     codex_core::send_user_message_core(
+        &state.workspaces,
         &state.sessions,
         workspace_id,
         thread_id,
@@ -338,6 +481,74 @@ pub(crate) async fn send_user_message(
         access_mode,
         images,
         collaboration_mode,
+        focus_paths,
+        &state.thread_preferences_path,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn get_thread_preferences(
+    workspace_id: String,
+    thread_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ThreadPreferences, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_thread_preferences",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_core::get_thread_preferences_core(
+        &state.sessions,
+        workspace_id,
+        thread_id,
+        &state.thread_preferences_path,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn set_thread_preferences(
+    workspace_id: String,
+    thread_id: String,
+    model: Option<String>,
+    effort: Option<String>,
+    persist: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ThreadPreferences, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "set_thread_preferences",
+            json!({
+                "workspaceId": workspace_id,
+                "threadId": thread_id,
+                "model": model,
+                "effort": effort,
+                "persist": persist,
+            }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_core::set_thread_preferences_core(
+        &state.sessions,
+        workspace_id,
+        thread_id,
+        model,
+        effort,
+        persist.unwrap_or(false),
+        &state.thread_preferences_path,
     )
     .await
 }
@@ -382,6 +593,217 @@ pub(crate) async fn turn_interrupt(
     codex_core::turn_interrupt_core(&state.sessions, workspace_id, thread_id, turn_id).await
 }
 
+#[tauri::command]
+pub(crate) async fn get_current_turn_output(
+    workspace_id: String,
+    thread_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return remote_backend::call_remote(
+            &*state,
+            app,
+            "get_current_turn_output",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id }),
+        )
+        .await;
+    }
+
+    codex_core::get_current_turn_output_core(&state.sessions, workspace_id, thread_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn get_session_pending_requests(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<PendingRequestInfo>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_session_pending_requests",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_core::get_session_pending_requests_core(&state.sessions, workspace_id).await
+}
+
+/// Returns `None` when the app-server's capabilities don't say either way.
+#[tauri::command]
+pub(crate) async fn supports_method(
+    workspace_id: String,
+    method: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Option<bool>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "supports_method",
+            json!({ "workspaceId": workspace_id, "method": method }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_core::supports_method_core(&state.sessions, workspace_id, method).await
+}
+
+#[tauri::command]
+pub(crate) async fn set_runtime_approval_policy(
+    workspace_id: String,
+    policy: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "set_runtime_approval_policy",
+            json!({ "workspaceId": workspace_id, "policy": policy }),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    codex_core::set_runtime_approval_policy_core(&state.sessions, workspace_id, policy).await
+}
+
+/// Uptime and idle time for a connected workspace's app-server.
+#[tauri::command]
+pub(crate) async fn get_session_info(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<SessionInfo, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_session_info",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_core::get_session_info_core(&state.sessions, workspace_id).await
+}
+
+/// PID and resource usage of a connected workspace's app-server process.
+#[tauri::command]
+pub(crate) async fn get_session_process_info(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<SessionProcessInfo, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_session_process_info",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_core::get_session_process_info_core(&state.sessions, workspace_id).await
+}
+
+/// Where the monitor looks for the workspace's Codex binary and whether it finds one.
+#[tauri::command]
+pub(crate) async fn get_path_diagnostics(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<PathDiagnostics, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_path_diagnostics",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_core::get_path_diagnostics_core(&state.workspaces, &state.app_settings, workspace_id)
+        .await
+}
+
+/// `last_viewed` maps thread ids to the unix ms the user last opened them.
+#[tauri::command]
+pub(crate) async fn get_unread_threads(
+    workspace_id: String,
+    last_viewed: HashMap<String, u64>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<String>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_unread_threads",
+            json!({ "workspaceId": workspace_id, "lastViewed": last_viewed }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_core::get_unread_threads_core(&state.sessions, workspace_id, last_viewed).await
+}
+
+/// The most recently active threads across all connected workspaces, newest first.
+#[tauri::command]
+pub(crate) async fn get_recent_threads(
+    limit: Option<u32>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<Value>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_recent_threads",
+            json!({ "limit": limit }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_core::get_recent_threads_core(&state.sessions, limit).await
+}
+
+/// Re-runs the Codex install check for a workspace; returns the reported version.
+#[tauri::command]
+pub(crate) async fn recheck_codex(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Option<String>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "recheck_codex",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_core::recheck_codex_core(&state.workspaces, &state.app_settings, workspace_id).await
+}
+
 #[tauri::command]
 pub(crate) async fn start_review(
     workspace_id: String,
@@ -409,6 +831,27 @@ pub(crate) async fn start_review(
     codex_core::start_review_core(&state.sessions, workspace_id, thread_id, target, delivery).await
 }
 
+#[tauri::command]
+pub(crate) async fn list_models(
+    workspace_id: String,
+    refresh: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<ModelInfo>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "list_models",
+            json!({ "workspaceId": workspace_id, "refresh": refresh }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_core::list_models_core(&state.sessions, workspace_id, refresh.unwrap_or(false)).await
+}
+
 #[tauri::command]
 pub(crate) async fn model_list(
     workspace_id: String,
@@ -684,16 +1127,16 @@ pub(crate) async fn generate_commit_message(
     // Hide background helper threads from the sidebar, even if a thread/started event leaked.
     let _ = app.emit(
         "app-server-event",
-        AppServerEvent {
-            workspace_id: workspace_id.clone(),
-            message: json!({
+        AppServerEvent::new(
+            workspace_id.clone(),
+            json!({
                 "method": "codex/backgroundThread",
                 "params": {
                     "threadId": thread_id,
                     "action": "hide"
                 }
             }),
-        },
+        ),
     );
 
     // Create channel for receiving events
@@ -882,16 +1325,16 @@ Task:\n{cleaned_prompt}"
     // Hide background helper threads from the sidebar, even if a thread/started event leaked.
     let _ = app.emit(
         "app-server-event",
-        AppServerEvent {
-            workspace_id: workspace_id.clone(),
-            message: json!({
+        AppServerEvent::new(
+            workspace_id.clone(),
+            json!({
                 "method": "codex/backgroundThread",
                 "params": {
                     "threadId": thread_id,
                     "action": "hide"
                 }
             }),
-        },
+        ),
     );
 
     let (tx, mut rx) = mpsc::unbounded_channel::<Value>();
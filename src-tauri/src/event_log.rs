@@ -0,0 +1,124 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::json;
+
+use crate::backend::events::AppServerEvent;
+
+pub(crate) const EVENT_LOG_FILENAME: &str = "events.jsonl";
+const MAX_EVENT_LOG_BYTES: u64 = 5 * 1024 * 1024;
+const RETAINED_EVENT_LOGS: usize = 3;
+
+/// Opt-in JSONL mirror of emitted app-server events, rotated by size.
+pub(crate) struct EventLog {
+    path: PathBuf,
+    max_bytes: u64,
+    enabled: AtomicBool,
+    // Serializes rotation and appends across event emitters.
+    write_lock: Mutex<()>,
+}
+
+impl EventLog {
+    pub(crate) fn new(path: PathBuf, enabled: bool) -> Self {
+        Self {
+            path,
+            max_bytes: MAX_EVENT_LOG_BYTES,
+            enabled: AtomicBool::new(enabled),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub(crate) fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub(crate) fn append(&self, event: &AppServerEvent) {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return;
+        }
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or(0);
+        let line = json!({ "timestamp": timestamp, "event": event }).to_string();
+        let Ok(_guard) = self.write_lock.lock() else {
+            return;
+        };
+        if let Err(error) = self.write_line(&line) {
+            eprintln!("event log: {error}");
+        }
+    }
+
+    fn write_line(&self, line: &str) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let size = fs::metadata(&self.path).map(|meta| meta.len()).unwrap_or(0);
+        if size > 0 && size + line.len() as u64 + 1 > self.max_bytes {
+            self.rotate()?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{line}")
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.as_os_str().to_owned();
+        name.push(format!(".{index}"));
+        PathBuf::from(name)
+    }
+
+    /// Shifts `events.jsonl` to `events.jsonl.1`, dropping the oldest retained file.
+    fn rotate(&self) -> std::io::Result<()> {
+        let _ = fs::remove_file(self.rotated_path(RETAINED_EVENT_LOGS));
+        for index in (1..RETAINED_EVENT_LOGS).rev() {
+            let from = self.rotated_path(index);
+            if from.exists() {
+                fs::rename(&from, self.rotated_path(index + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated_path(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{EventLog, RETAINED_EVENT_LOGS};
+    use crate::backend::events::AppServerEvent;
+
+    #[test]
+    fn event_log_rotates_and_retains_a_few_files() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", uuid::Uuid::new_v4()));
+        let mut log = EventLog::new(dir.join("events.jsonl"), false);
+        log.max_bytes = 200;
+        let event = AppServerEvent::new("ws-1".to_string(), json!({ "method": "turn/started" }));
+
+        log.append(&event);
+        assert!(!log.path().exists());
+
+        log.set_enabled(true);
+        for _ in 0..40 {
+            log.append(&event);
+        }
+        let content = std::fs::read_to_string(log.path()).expect("read log");
+        let first: serde_json::Value =
+            serde_json::from_str(content.lines().next().expect("line")).expect("json");
+        assert_eq!(first["event"]["workspace_id"], "ws-1");
+        assert!(first["timestamp"].as_u64().is_some());
+
+        assert!(log.rotated_path(RETAINED_EVENT_LOGS).exists());
+        assert!(!log.rotated_path(RETAINED_EVENT_LOGS + 1).exists());
+    }
+}
@@ -1,6 +1,7 @@
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
 
 use crate::backend::events::{AppServerEvent, EventSink, TerminalExit, TerminalOutput};
+use crate::state::AppState;
 
 #[derive(Clone)]
 pub(crate) struct TauriEventSink {
@@ -15,6 +16,9 @@ impl TauriEventSink {
 
 impl EventSink for TauriEventSink {
     fn emit_app_server_event(&self, event: AppServerEvent) {
+        if let Some(state) = self.app.try_state::<AppState>() {
+            state.event_log.append(&event);
+        }
         let _ = self.app.emit("app-server-event", event);
     }
 
@@ -0,0 +1,193 @@
+use std::fs::Permissions;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::files::io::write_new_file;
+use crate::files::policy::{policy_for, FileKind, FileScope};
+use crate::files::validate::validate_agents_content;
+
+pub(crate) const CONFIG_BACKUPS_DIR: &str = "config-backups";
+/// Global files included in a config backup, in the order they are restored.
+pub(crate) const BACKUP_FILE_KINDS: [FileKind; 3] =
+    [FileKind::Agents, FileKind::Config, FileKind::ClaudeJson];
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ConfigBackupResponse {
+    pub path: String,
+    /// File names that were backed up or restored.
+    pub files: Vec<String>,
+}
+
+pub(crate) fn new_backup_dir(data_dir: &Path) -> PathBuf {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S-%3f");
+    data_dir.join(CONFIG_BACKUPS_DIR).join(stamp.to_string())
+}
+
+/// Canonicalizes `backup_path` and checks it is a backup directory under `data_dir`.
+pub(crate) fn resolve_backup_dir(data_dir: &Path, backup_path: &str) -> Result<PathBuf, String> {
+    let backups_root = data_dir
+        .join(CONFIG_BACKUPS_DIR)
+        .canonicalize()
+        .map_err(|_| "No config backups found".to_string())?;
+    let backup_dir = Path::new(backup_path.trim())
+        .canonicalize()
+        .map_err(|err| format!("Failed to resolve backup: {err}"))?;
+    if backup_dir == backups_root
+        || !backup_dir.starts_with(&backups_root)
+        || !backup_dir.is_dir()
+    {
+        return Err("Backup path must be a config backup directory".to_string());
+    }
+    Ok(backup_dir)
+}
+
+/// Creates `backup_dir` and writes each `(filename, content)` pair into it. Backups can hold
+/// credentials, so on Unix the directory is owner-only and each file is 0600.
+pub(crate) fn write_backup_files(
+    backup_dir: &Path,
+    files: &[(&'static str, String)],
+) -> Result<(), String> {
+    create_private_dir(backup_dir)
+        .map_err(|err| format!("Failed to create backup directory: {err}"))?;
+    let permissions = private_file_permissions();
+    for (filename, content) in files {
+        write_new_file(&backup_dir.join(filename), content.as_bytes(), permissions.as_ref())
+            .map_err(|err| format!("Failed to back up {filename}: {err}"))?;
+    }
+    Ok(())
+}
+
+/// Reads and validates every managed file in the backup at `backup_path`, returning the
+/// resolved directory and the files to restore in `BACKUP_FILE_KINDS` order.
+pub(crate) fn read_backup_files(
+    data_dir: &Path,
+    backup_path: &str,
+) -> Result<(PathBuf, Vec<(FileKind, String)>), String> {
+    let backup_dir = resolve_backup_dir(data_dir, backup_path)?;
+    let mut files = Vec::new();
+    for kind in BACKUP_FILE_KINDS {
+        let filename = policy_for(FileScope::Global, kind)?.filename;
+        let source = backup_dir.join(filename);
+        if !source.is_file() {
+            continue;
+        }
+        let content = std::fs::read_to_string(&source)
+            .map_err(|err| format!("Failed to read {filename} from backup: {err}"))?;
+        validate_backup_content(kind, filename, &content)?;
+        files.push((kind, content));
+    }
+    if files.is_empty() {
+        return Err("Backup contains no config files".to_string());
+    }
+    Ok((backup_dir, files))
+}
+
+fn create_private_dir(path: &Path) -> std::io::Result<()> {
+    let mut builder = std::fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    builder.create(path)
+}
+
+#[cfg(unix)]
+fn private_file_permissions() -> Option<Permissions> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(Permissions::from_mode(0o600))
+}
+
+#[cfg(not(unix))]
+fn private_file_permissions() -> Option<Permissions> {
+    None
+}
+
+pub(crate) fn validate_backup_content(
+    kind: FileKind,
+    filename: &str,
+    content: &str,
+) -> Result<(), String> {
+    match kind {
+        FileKind::Agents => validate_agents_content(content).map(|_| ()),
+        FileKind::Config | FileKind::ClaudeJson => serde_json::from_str::<Value>(content)
+            .map(|_| ())
+            .map_err(|err| format!("{filename} in backup is not valid JSON: {err}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use uuid::Uuid;
+
+    use super::{
+        new_backup_dir, read_backup_files, resolve_backup_dir, validate_backup_content,
+        write_backup_files,
+    };
+    use crate::files::policy::FileKind;
+
+    #[test]
+    fn backup_dir_must_live_under_the_backups_root() {
+        let data_dir =
+            std::env::temp_dir().join(format!("codex-monitor-backup-{}", Uuid::new_v4()));
+        assert!(resolve_backup_dir(&data_dir, "/tmp").is_err());
+
+        let backup_dir = new_backup_dir(&data_dir);
+        fs::create_dir_all(&backup_dir).expect("create backup dir");
+        let resolved = resolve_backup_dir(&data_dir, &backup_dir.to_string_lossy())
+            .expect("resolve backup");
+        assert!(resolved.ends_with(backup_dir.file_name().expect("name")));
+
+        let root = backup_dir.parent().expect("backups root");
+        assert!(resolve_backup_dir(&data_dir, &root.to_string_lossy()).is_err());
+        let escape = format!("{}/../..", backup_dir.to_string_lossy());
+        assert!(resolve_backup_dir(&data_dir, &escape).is_err());
+
+        let _ = fs::remove_dir_all(&data_dir);
+    }
+
+    #[test]
+    fn backup_files_round_trip_privately() {
+        let data_dir =
+            std::env::temp_dir().join(format!("codex-monitor-backup-{}", Uuid::new_v4()));
+        let backup_dir = new_backup_dir(&data_dir);
+        let files = [("CLAUDE.md", "# Notes\n".to_string()), (".claude.json", "{}".to_string())];
+        write_backup_files(&backup_dir, &files).expect("write backup");
+
+        let (resolved, restores) =
+            read_backup_files(&data_dir, &backup_dir.to_string_lossy()).expect("read backup");
+        assert!(resolved.ends_with(backup_dir.file_name().expect("name")));
+        assert_eq!(
+            restores,
+            vec![
+                (FileKind::Agents, "# Notes\n".to_string()),
+                (FileKind::ClaudeJson, "{}".to_string()),
+            ]
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = |path: &std::path::Path| {
+                fs::metadata(path).expect("metadata").permissions().mode() & 0o777
+            };
+            assert_eq!(mode(&backup_dir), 0o700);
+            assert_eq!(mode(&backup_dir.join("CLAUDE.md")), 0o600);
+        }
+
+        let _ = fs::remove_dir_all(&data_dir);
+    }
+
+    #[test]
+    fn backup_json_files_must_parse() {
+        assert!(validate_backup_content(FileKind::Config, "settings.json", "{}").is_ok());
+        assert!(validate_backup_content(FileKind::ClaudeJson, ".claude.json", "{oops").is_err());
+        assert!(validate_backup_content(FileKind::Agents, "CLAUDE.md", "# Notes\n").is_ok());
+        assert!(validate_backup_content(FileKind::Agents, "CLAUDE.md", "a\0b").is_err());
+    }
+}
@@ -0,0 +1,106 @@
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::files::io::TextFileResponse;
+
+const MAX_CACHE_ENTRIES: usize = 50;
+
+/// Recently read files keyed by path, reused while the file's mtime is unchanged.
+#[derive(Default)]
+pub(crate) struct FileCache {
+    entries: HashMap<PathBuf, (SystemTime, TextFileResponse)>,
+    // Least recently used first.
+    order: VecDeque<PathBuf>,
+}
+
+impl FileCache {
+    pub(crate) fn get(&mut self, path: &Path, modified: SystemTime) -> Option<TextFileResponse> {
+        let (cached_modified, response) = self.entries.get(path)?;
+        if *cached_modified != modified {
+            return None;
+        }
+        let response = response.clone();
+        self.touch(path);
+        Some(response)
+    }
+
+    pub(crate) fn insert(
+        &mut self,
+        path: PathBuf,
+        modified: SystemTime,
+        response: TextFileResponse,
+    ) {
+        if self.entries.insert(path.clone(), (modified, response)).is_some() {
+            self.touch(&path);
+            return;
+        }
+        self.order.push_back(path);
+        while self.order.len() > MAX_CACHE_ENTRIES {
+            if let Some(evicted) = self.order.pop_front() {
+                self.entries.remove(&evicted);
+            }
+        }
+    }
+
+    pub(crate) fn invalidate(&mut self, path: &Path) {
+        if self.entries.remove(path).is_some() {
+            self.order.retain(|entry| entry != path);
+        }
+    }
+
+    fn touch(&mut self, path: &Path) {
+        if let Some(index) = self.order.iter().position(|entry| entry == path) {
+            if let Some(entry) = self.order.remove(index) {
+                self.order.push_back(entry);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime};
+
+    use super::{FileCache, MAX_CACHE_ENTRIES};
+    use crate::files::io::TextFileResponse;
+
+    fn response(content: &str) -> TextFileResponse {
+        TextFileResponse {
+            exists: true,
+            content: content.to_string(),
+            truncated: false,
+        }
+    }
+
+    #[test]
+    fn file_cache_hits_only_for_matching_mtime() {
+        let mut cache = FileCache::default();
+        let path = PathBuf::from("/tmp/CLAUDE.md");
+        let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(10);
+        cache.insert(path.clone(), modified, response("cached"));
+
+        assert_eq!(cache.get(&path, modified), Some(response("cached")));
+        assert_eq!(cache.get(&path, modified + Duration::from_secs(1)), None);
+
+        cache.invalidate(&path);
+        assert_eq!(cache.get(&path, modified), None);
+    }
+
+    #[test]
+    fn file_cache_evicts_least_recently_used() {
+        let mut cache = FileCache::default();
+        let modified = SystemTime::UNIX_EPOCH;
+        for index in 0..MAX_CACHE_ENTRIES {
+            cache.insert(PathBuf::from(format!("/tmp/{index}")), modified, response("x"));
+        }
+        // Reading the oldest entry keeps it alive past the next insert.
+        assert!(cache.get(&PathBuf::from("/tmp/0"), modified).is_some());
+        cache.insert(PathBuf::from("/tmp/new"), modified, response("x"));
+
+        assert!(cache.get(&PathBuf::from("/tmp/0"), modified).is_some());
+        assert!(cache.get(&PathBuf::from("/tmp/1"), modified).is_none());
+        assert!(cache.get(&PathBuf::from("/tmp/new"), modified).is_some());
+    }
+}
@@ -0,0 +1,86 @@
+use std::path::Path;
+
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::normalize_git_path;
+
+const AGENTS_FILENAMES: [&str; 2] = ["AGENTS.md", "CLAUDE.md"];
+pub(crate) const MAX_AGENTS_SEARCH_DEPTH: usize = 8;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct AgentsFileEntry {
+    pub(crate) path: String,
+    pub(crate) size: u64,
+}
+
+fn should_skip_dir(name: &str) -> bool {
+    matches!(name, ".git" | "node_modules" | "dist" | "target")
+}
+
+/// Walks `root` honoring ignore files and returns every agents file found, sorted by path.
+pub(crate) fn find_agents_files_inner(root: &Path, max_depth: usize) -> Vec<AgentsFileEntry> {
+    let walker = WalkBuilder::new(root)
+        .hidden(false)
+        .follow_links(false)
+        .require_git(false)
+        .max_depth(Some(max_depth))
+        .filter_entry(|entry| {
+            if entry.depth() == 0 || !entry.file_type().is_some_and(|ft| ft.is_dir()) {
+                return true;
+            }
+            !should_skip_dir(&entry.file_name().to_string_lossy())
+        })
+        .build();
+
+    let mut results = Vec::new();
+    for entry in walker.flatten() {
+        if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy();
+        if !AGENTS_FILENAMES.contains(&name.as_ref()) {
+            continue;
+        }
+        let Ok(rel_path) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        let size = entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+        results.push(AgentsFileEntry {
+            path: normalize_git_path(&rel_path.to_string_lossy()),
+            size,
+        });
+    }
+    results.sort_by(|a, b| a.path.cmp(&b.path));
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use uuid::Uuid;
+
+    use super::find_agents_files_inner;
+
+    #[test]
+    fn finds_nested_agents_files_and_respects_gitignore() {
+        let root = std::env::temp_dir().join(format!("codex-monitor-agents-{}", Uuid::new_v4()));
+        fs::create_dir_all(root.join("packages/foo")).expect("create package");
+        fs::create_dir_all(root.join("vendor/lib")).expect("create vendor");
+        fs::create_dir_all(root.join("a/b/c")).expect("create deep dir");
+        fs::write(root.join(".gitignore"), "vendor/\n").expect("write gitignore");
+        fs::write(root.join("AGENTS.md"), "root").expect("write root agents");
+        fs::write(root.join("packages/foo/CLAUDE.md"), "package").expect("write package");
+        fs::write(root.join("vendor/lib/AGENTS.md"), "ignored").expect("write vendor");
+        fs::write(root.join("a/b/c/AGENTS.md"), "deep").expect("write deep");
+        fs::write(root.join("packages/foo/README.md"), "readme").expect("write readme");
+
+        let found = find_agents_files_inner(&root, 3);
+        let paths: Vec<&str> = found.iter().map(|entry| entry.path.as_str()).collect();
+        assert_eq!(paths, vec!["AGENTS.md", "packages/foo/CLAUDE.md"]);
+        assert_eq!(found[1].size, "package".len() as u64);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
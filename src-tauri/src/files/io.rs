@@ -1,8 +1,10 @@
-use std::fs::File;
-use std::io::Read;
+use std::fs::{File, OpenOptions, Permissions};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub(crate) struct TextFileResponse {
@@ -11,6 +13,20 @@ pub(crate) struct TextFileResponse {
     pub truncated: bool,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TextFileChunk {
+    pub exists: bool,
+    pub content: String,
+    pub offset: u64,
+    pub next_offset: u64,
+    pub total_bytes: u64,
+    pub eof: bool,
+}
+
+/// Largest managed file read whole; `.claude.json` is the one that grows toward it.
+pub(crate) const MAX_TEXT_FILE_BYTES: u64 = 64 * 1024 * 1024;
+
 fn missing_response() -> TextFileResponse {
     TextFileResponse {
         exists: false,
@@ -48,21 +64,23 @@ fn resolve_or_create_root(root: &Path, root_context: &str) -> Result<PathBuf, St
     Ok(canonical_root)
 }
 
-pub(crate) fn read_text_file_within(
+/// Resolves `filename` under `root`, enforcing the containment policy. Returns `None` when
+/// the root (if allowed to be missing) or the file does not exist.
+fn resolve_existing_file_within(
     root: &Path,
     filename: &str,
     root_may_be_missing: bool,
     root_context: &str,
     file_context: &str,
     allow_external_symlink_target: bool,
-) -> Result<TextFileResponse, String> {
+) -> Result<Option<PathBuf>, String> {
     let Some(canonical_root) = resolve_root(root, root_context, root_may_be_missing)? else {
-        return Ok(missing_response());
+        return Ok(None);
     };
 
     let candidate = canonical_root.join(filename);
     if !candidate.exists() {
-        return Ok(missing_response());
+        return Ok(None);
     }
 
     let candidate_is_symlink = std::fs::symlink_metadata(&candidate)
@@ -77,31 +95,152 @@ pub(crate) fn read_text_file_within(
     {
         return Err(format!("Invalid {file_context} path"));
     }
+    Ok(Some(canonical_path))
+}
 
-    let mut file =
+pub(crate) fn read_text_file_within(
+    root: &Path,
+    filename: &str,
+    root_may_be_missing: bool,
+    root_context: &str,
+    file_context: &str,
+    allow_external_symlink_target: bool,
+) -> Result<TextFileResponse, String> {
+    let Some(canonical_path) = resolve_existing_file_within(
+        root,
+        filename,
+        root_may_be_missing,
+        root_context,
+        file_context,
+        allow_external_symlink_target,
+    )?
+    else {
+        return Ok(missing_response());
+    };
+
+    let file =
         File::open(&canonical_path).map_err(|err| format!("Failed to open {file_context}: {err}"))?;
+    let (content, truncated) = read_capped(file, MAX_TEXT_FILE_BYTES, file_context)?;
+
+    Ok(TextFileResponse {
+        exists: true,
+        content,
+        truncated,
+    })
+}
+
+/// Reads at most `max_bytes`, backing off to the last whole UTF-8 character when the cap
+/// splits one, and reports whether anything was left unread.
+fn read_capped(
+    reader: impl Read,
+    max_bytes: u64,
+    file_context: &str,
+) -> Result<(String, bool), String> {
     let mut buffer = Vec::new();
-    file.read_to_end(&mut buffer)
+    reader
+        .take(max_bytes + 1)
+        .read_to_end(&mut buffer)
+        .map_err(|err| format!("Failed to read {file_context}: {err}"))?;
+    let truncated = buffer.len() as u64 > max_bytes;
+    if truncated {
+        buffer.truncate(max_bytes as usize);
+    }
+    let content = match String::from_utf8(buffer) {
+        Ok(content) => content,
+        Err(err) if truncated && err.utf8_error().error_len().is_none() => {
+            let valid = err.utf8_error().valid_up_to();
+            let mut bytes = err.into_bytes();
+            bytes.truncate(valid);
+            String::from_utf8(bytes).map_err(|_| format!("{file_context} is not valid UTF-8"))?
+        }
+        Err(_) => return Err(format!("{file_context} is not valid UTF-8")),
+    };
+    Ok((content, truncated))
+}
+
+/// Reads up to `max_bytes` starting at `offset`. The chunk ends on a UTF-8 character
+/// boundary, so `next_offset` may be slightly less than `offset + max_bytes`. The path is
+/// resolved again for every chunk so a file swapped for a symlink mid-read is rejected.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn read_text_chunk_within(
+    root: &Path,
+    filename: &str,
+    root_may_be_missing: bool,
+    root_context: &str,
+    file_context: &str,
+    allow_external_symlink_target: bool,
+    offset: u64,
+    max_bytes: usize,
+) -> Result<TextFileChunk, String> {
+    let Some(canonical_path) = resolve_existing_file_within(
+        root,
+        filename,
+        root_may_be_missing,
+        root_context,
+        file_context,
+        allow_external_symlink_target,
+    )?
+    else {
+        return Ok(TextFileChunk {
+            exists: false,
+            content: String::new(),
+            offset,
+            next_offset: offset,
+            total_bytes: 0,
+            eof: true,
+        });
+    };
+
+    let mut file =
+        File::open(&canonical_path).map_err(|err| format!("Failed to open {file_context}: {err}"))?;
+    let total_bytes = file
+        .metadata()
+        .map_err(|err| format!("Failed to read {file_context}: {err}"))?
+        .len();
+    if offset > total_bytes {
+        return Err(format!("Offset is past the end of {file_context}"));
+    }
+    file.seek(SeekFrom::Start(offset))
         .map_err(|err| format!("Failed to read {file_context}: {err}"))?;
+    let mut buffer = Vec::with_capacity(max_bytes);
+    file.take(max_bytes as u64)
+        .read_to_end(&mut buffer)
+        .map_err(|err| format!("Failed to read {file_context}: {err}"))?;
+
+    let at_end = offset + buffer.len() as u64 >= total_bytes;
+    let valid_len = match std::str::from_utf8(&buffer) {
+        Ok(_) => buffer.len(),
+        // A multi-byte character split by the chunk boundary is left for the next chunk.
+        Err(err) if err.error_len().is_none() && !at_end && err.valid_up_to() > 0 => {
+            err.valid_up_to()
+        }
+        Err(_) => return Err(format!("{file_context} is not valid UTF-8")),
+    };
+    buffer.truncate(valid_len);
+    let next_offset = offset + valid_len as u64;
     let content = String::from_utf8(buffer)
         .map_err(|_| format!("{file_context} is not valid UTF-8"))?;
 
-    Ok(TextFileResponse {
+    Ok(TextFileChunk {
         exists: true,
         content,
-        truncated: false,
+        offset,
+        next_offset,
+        total_bytes,
+        eof: next_offset >= total_bytes,
     })
 }
 
-pub(crate) fn write_text_file_within(
+/// Resolves where a write to `filename` under `root` should land, enforcing the same
+/// containment policy as reads. Existing symlinks resolve to their target.
+pub(crate) fn resolve_write_target_within(
     root: &Path,
     filename: &str,
-    content: &str,
     create_root: bool,
     root_context: &str,
     file_context: &str,
     allow_external_symlink_target: bool,
-) -> Result<(), String> {
+) -> Result<PathBuf, String> {
     let canonical_root = if create_root {
         resolve_or_create_root(root, root_context)?
     } else {
@@ -114,28 +253,122 @@ pub(crate) fn write_text_file_within(
         return Err(format!("Invalid {file_context} path"));
     }
 
-    let target_path = if candidate.exists() {
-        let candidate_is_symlink = std::fs::symlink_metadata(&candidate)
-            .map_err(|err| format!("Failed to resolve {file_context}: {err}"))?
-            .file_type()
-            .is_symlink();
-        let canonical_path = candidate
-            .canonicalize()
-            .map_err(|err| format!("Failed to resolve {file_context}: {err}"))?;
-        if !canonical_path.starts_with(&canonical_root)
-            && !(allow_external_symlink_target && candidate_is_symlink)
-        {
-            return Err(format!("Invalid {file_context} path"));
-        }
-        canonical_path
-    } else {
-        candidate
-    };
+    if !candidate.exists() {
+        return Ok(candidate);
+    }
+    let candidate_is_symlink = std::fs::symlink_metadata(&candidate)
+        .map_err(|err| format!("Failed to resolve {file_context}: {err}"))?
+        .file_type()
+        .is_symlink();
+    let canonical_path = candidate
+        .canonicalize()
+        .map_err(|err| format!("Failed to resolve {file_context}: {err}"))?;
+    if !canonical_path.starts_with(&canonical_root)
+        && !(allow_external_symlink_target && candidate_is_symlink)
+    {
+        return Err(format!("Invalid {file_context} path"));
+    }
+    Ok(canonical_path)
+}
 
+pub(crate) fn write_text_file_within(
+    root: &Path,
+    filename: &str,
+    content: &str,
+    create_root: bool,
+    root_context: &str,
+    file_context: &str,
+    allow_external_symlink_target: bool,
+) -> Result<(), String> {
+    let target_path = resolve_write_target_within(
+        root,
+        filename,
+        create_root,
+        root_context,
+        file_context,
+        allow_external_symlink_target,
+    )?;
     std::fs::write(&target_path, content)
         .map_err(|err| format!("Failed to write {file_context}: {err}"))
 }
 
+/// `path` with `suffix` appended to its file name, e.g. `.claude.json` -> `.claude.json.bak`.
+pub(crate) fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// Creates `path`, failing if it exists. With `permissions` the file is created with that
+/// mode, so private files are never briefly readable by others.
+pub(crate) fn write_new_file(
+    path: &Path,
+    content: &[u8],
+    permissions: Option<&Permissions>,
+) -> std::io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    if let Some(permissions) = permissions {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(permissions.mode());
+    }
+    let mut file = options.open(path)?;
+    file.write_all(content)?;
+    if let Some(permissions) = permissions {
+        // The umask may have narrowed the mode passed to `open`.
+        file.set_permissions(permissions.clone())?;
+    }
+    Ok(())
+}
+
+/// Writes `content` to a temporary sibling and renames it over `path`, so readers never see
+/// a partially written file. An existing file's permissions carry over to the replacement.
+pub(crate) fn replace_file_atomically(
+    path: &Path,
+    content: &str,
+    file_context: &str,
+) -> Result<(), String> {
+    replace_file_checked(path, content, file_context, None)
+}
+
+/// Like `replace_file_atomically`, but fails instead of replacing a file whose modification
+/// time is no longer `expected_modified`, e.g. because another program rewrote it meanwhile.
+pub(crate) fn replace_file_if_unchanged(
+    path: &Path,
+    content: &str,
+    file_context: &str,
+    expected_modified: SystemTime,
+) -> Result<(), String> {
+    replace_file_checked(path, content, file_context, Some(expected_modified))
+}
+
+fn replace_file_checked(
+    path: &Path,
+    content: &str,
+    file_context: &str,
+    expected_modified: Option<SystemTime>,
+) -> Result<(), String> {
+    let permissions = std::fs::metadata(path).ok().map(|metadata| metadata.permissions());
+    let temp_path = sibling_path(path, &format!(".{}.tmp", Uuid::new_v4()));
+    if let Err(err) = write_new_file(&temp_path, content.as_bytes(), permissions.as_ref()) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(format!("Failed to write {file_context}: {err}"));
+    }
+    if let Some(expected) = expected_modified {
+        let modified = std::fs::metadata(path).and_then(|metadata| metadata.modified());
+        if modified.ok() != Some(expected) {
+            let _ = std::fs::remove_file(&temp_path);
+            return Err(format!("{file_context} changed on disk; try again"));
+        }
+    }
+    if let Err(err) = std::fs::rename(&temp_path, path) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(format!("Failed to replace {file_context}: {err}"));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -156,6 +389,19 @@ mod tests {
         assert!(response.content.is_empty());
     }
 
+    #[test]
+    fn capped_read_truncates_on_a_char_boundary() {
+        let content = "ab\u{e9}cd";
+        let (read, truncated) = read_capped(content.as_bytes(), 3, "file").expect("read");
+        assert_eq!(read, "ab");
+        assert!(truncated);
+
+        let (read, truncated) =
+            read_capped(content.as_bytes(), content.len() as u64, "file").expect("read");
+        assert_eq!(read, content);
+        assert!(!truncated);
+    }
+
     #[test]
     fn write_creates_root_and_round_trips() {
         let root = temp_dir();
@@ -174,6 +420,73 @@ mod tests {
         assert_eq!(response.content, "hello");
     }
 
+    #[test]
+    fn chunked_read_splits_on_char_boundaries() {
+        let root = temp_dir();
+        std::fs::create_dir_all(&root).expect("create root");
+        let content = "ab\u{e9}cd\u{1f600}ef";
+        std::fs::write(root.join(".claude.json"), content).expect("seed file");
+
+        let mut offset = 0;
+        let mut collected = String::new();
+        loop {
+            let chunk = read_text_chunk_within(
+                &root,
+                ".claude.json",
+                false,
+                "HOME",
+                ".claude.json",
+                false,
+                offset,
+                4,
+            )
+            .expect("chunk read should succeed");
+            assert!(chunk.exists);
+            assert_eq!(chunk.offset, offset);
+            assert_eq!(chunk.total_bytes, content.len() as u64);
+            assert!(chunk.content.len() <= 4);
+            collected.push_str(&chunk.content);
+            offset = chunk.next_offset;
+            if chunk.eof {
+                break;
+            }
+        }
+        assert_eq!(collected, content);
+
+        let error = read_text_chunk_within(
+            &root,
+            ".claude.json",
+            false,
+            "HOME",
+            ".claude.json",
+            false,
+            content.len() as u64 + 1,
+            4,
+        )
+        .expect_err("offset past the end should fail");
+        assert!(error.contains("past the end"));
+    }
+
+    #[test]
+    fn chunked_read_reports_missing_file() {
+        let root = temp_dir();
+        std::fs::create_dir_all(&root).expect("create root");
+        let chunk = read_text_chunk_within(
+            &root,
+            ".claude.json",
+            false,
+            "HOME",
+            ".claude.json",
+            false,
+            0,
+            8,
+        )
+        .expect("chunk read should succeed");
+        assert!(!chunk.exists);
+        assert!(chunk.eof);
+        assert!(chunk.content.is_empty());
+    }
+
     #[cfg(unix)]
     #[test]
     fn write_rejects_symlink_escape() {
@@ -225,6 +538,34 @@ mod tests {
         assert!(error.contains("Invalid AGENTS.md path"));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn chunked_read_rejects_symlink_escape() {
+        use std::os::unix::fs::symlink;
+
+        let root = temp_dir();
+        let outside = temp_dir();
+        std::fs::create_dir_all(&root).expect("create root");
+        std::fs::create_dir_all(&outside).expect("create outside");
+
+        let outside_file = outside.join(".claude.json");
+        std::fs::write(&outside_file, "{}").expect("seed outside file");
+        symlink(&outside_file, root.join(".claude.json")).expect("create symlink");
+
+        let error = read_text_chunk_within(
+            &root,
+            ".claude.json",
+            false,
+            "HOME",
+            ".claude.json",
+            false,
+            0,
+            8,
+        )
+        .expect_err("should reject symlink escape");
+        assert!(error.contains("Invalid .claude.json path"));
+    }
+
     #[cfg(unix)]
     #[test]
     fn read_allows_external_symlink_when_enabled() {
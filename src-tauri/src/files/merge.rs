@@ -0,0 +1,152 @@
+use diffy::{ConflictStyle, DiffOptions, Line, MergeOptions};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WritePreview {
+    pub exists: bool,
+    pub changed: bool,
+    pub diff: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ConflictRange {
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MergeResult {
+    pub merged_content: String,
+    pub had_conflicts: bool,
+    pub conflict_markers: Vec<ConflictRange>,
+}
+
+/// Lines shared by both texts, in order, from diffy's Myers diff; unlike an LCS table this
+/// stays linear in memory however large the inputs are.
+fn common_lines(left: &str, right: &str) -> String {
+    if left == right {
+        return left.to_string();
+    }
+    let context = left.lines().count().max(right.lines().count());
+    let mut options = DiffOptions::new();
+    options.set_context_len(context);
+    let patch = options.create_patch(left, right);
+    patch
+        .hunks()
+        .iter()
+        .flat_map(|hunk| hunk.lines())
+        .filter_map(|line| match line {
+            Line::Context(text) => Some(*text),
+            Line::Delete(_) | Line::Insert(_) => None,
+        })
+        .collect()
+}
+
+fn conflict_ranges(merged: &str) -> Vec<ConflictRange> {
+    let mut ranges = Vec::new();
+    let mut start = None;
+    for (index, line) in merged.lines().enumerate() {
+        if line.starts_with("<<<<<<<") {
+            start = Some(index);
+        } else if line.starts_with(">>>>>>>") {
+            if let Some(start_line) = start.take() {
+                ranges.push(ConflictRange {
+                    start_line,
+                    end_line: index,
+                });
+            }
+        }
+    }
+    ranges
+}
+
+/// Merges the workspace and global copies, using their common lines as the ancestor.
+pub(crate) fn merge_texts(workspace: &str, global: &str) -> MergeResult {
+    let ancestor = common_lines(workspace, global);
+
+    let mut options = MergeOptions::new();
+    options.set_conflict_style(ConflictStyle::Merge);
+    match options.merge(&ancestor, workspace, global) {
+        Ok(merged_content) => MergeResult {
+            merged_content,
+            had_conflicts: false,
+            conflict_markers: Vec::new(),
+        },
+        Err(merged_content) => {
+            let conflict_markers = conflict_ranges(&merged_content);
+            MergeResult {
+                merged_content,
+                had_conflicts: true,
+                conflict_markers,
+            }
+        }
+    }
+}
+
+/// Unified diff from the on-disk content to `proposed`; empty when nothing would change.
+pub(crate) fn preview_write(exists: bool, current: &str, proposed: &str) -> WritePreview {
+    let changed = current != proposed;
+    let diff = if changed {
+        diffy::create_patch(current, proposed).to_string()
+    } else {
+        String::new()
+    };
+    WritePreview {
+        exists,
+        changed,
+        diff,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{common_lines, merge_texts, preview_write};
+
+    #[test]
+    fn merge_keeps_additions_from_both_sides() {
+        let result = merge_texts(
+            "# Rules\nshared\nworkspace only\n",
+            "# Rules\nglobal only\nshared\n",
+        );
+        assert!(!result.had_conflicts);
+        assert!(result.merged_content.contains("workspace only"));
+        assert!(result.merged_content.contains("global only"));
+        assert!(result.merged_content.contains("shared"));
+    }
+
+    #[test]
+    fn common_lines_keep_shared_lines_in_order() {
+        assert_eq!(
+            common_lines("a\nb\nc\nd\n", "a\nx\nc\nd\ny\n"),
+            "a\nc\nd\n"
+        );
+        assert_eq!(common_lines("same\n", "same\n"), "same\n");
+        assert_eq!(common_lines("", "new\n"), "");
+    }
+
+    #[test]
+    fn merge_reports_conflicting_edits() {
+        let result = merge_texts("# Rules\nuse tabs\n", "# Rules\nuse spaces\n");
+        assert!(result.had_conflicts);
+        assert_eq!(result.conflict_markers.len(), 1);
+        let range = &result.conflict_markers[0];
+        let lines: Vec<&str> = result.merged_content.lines().collect();
+        assert!(lines[range.start_line].starts_with("<<<<<<<"));
+        assert!(lines[range.end_line].starts_with(">>>>>>>"));
+    }
+
+    #[test]
+    fn preview_write_reports_unified_diff() {
+        let preview = preview_write(true, "{\n  \"model\": \"a\"\n}\n", "{\n  \"model\": \"b\"\n}\n");
+        assert!(preview.changed);
+        assert!(preview.diff.contains("-  \"model\": \"a\""));
+        assert!(preview.diff.contains("+  \"model\": \"b\""));
+
+        let unchanged = preview_write(true, "same\n", "same\n");
+        assert!(!unchanged.changed);
+        assert!(unchanged.diff.is_empty());
+    }
+}
@@ -2,19 +2,36 @@ use serde_json::json;
 use tauri::{AppHandle, State};
 
 use crate::remote_backend;
-use crate::shared::files_core::{file_read_core, file_write_core};
+use crate::shared::files_core::{
+    backup_configs_core, file_merge_core, file_preview_write_core, file_read_chunk_core,
+    file_read_core, file_write_core, find_agents_files_core, prune_claude_json_core,
+    restore_configs_core,
+};
 use crate::state::AppState;
-use self::io::TextFileResponse;
+use crate::workspaces::app_data_dir;
+use self::backup::ConfigBackupResponse;
+use self::discover::AgentsFileEntry;
+use self::io::{TextFileChunk, TextFileResponse};
+use self::merge::{MergeResult, WritePreview};
 use self::policy::{FileKind, FileScope};
+use self::prune::PruneResponse;
+use self::validate::FileWriteResponse;
 
+pub(crate) mod backup;
+pub(crate) mod cache;
+pub(crate) mod discover;
 pub(crate) mod io;
+pub(crate) mod merge;
 pub(crate) mod ops;
 pub(crate) mod policy;
+pub(crate) mod prune;
+pub(crate) mod validate;
 
 async fn file_read_impl(
     scope: FileScope,
     kind: FileKind,
     workspace_id: Option<String>,
+    subpath: Option<String>,
     state: &AppState,
     app: &AppHandle,
 ) -> Result<TextFileResponse, String> {
@@ -23,25 +40,39 @@ async fn file_read_impl(
             state,
             app.clone(),
             "file_read",
-            json!({ "scope": scope, "kind": kind, "workspaceId": workspace_id }),
+            json!({
+                "scope": scope,
+                "kind": kind,
+                "workspaceId": workspace_id,
+                "subpath": subpath,
+            }),
         )
         .await?;
         return serde_json::from_value(response).map_err(|err| err.to_string());
     }
 
-    file_read_core(&state.workspaces, scope, kind, workspace_id).await
+    file_read_core(
+        &state.workspaces,
+        &state.file_cache,
+        scope,
+        kind,
+        workspace_id,
+        subpath,
+    )
+    .await
 }
 
 async fn file_write_impl(
     scope: FileScope,
     kind: FileKind,
     workspace_id: Option<String>,
+    subpath: Option<String>,
     content: String,
     state: &AppState,
     app: &AppHandle,
-) -> Result<(), String> {
+) -> Result<FileWriteResponse, String> {
     if remote_backend::is_remote_mode(state).await {
-        remote_backend::call_remote(
+        let response = remote_backend::call_remote(
             state,
             app.clone(),
             "file_write",
@@ -49,25 +80,81 @@ async fn file_write_impl(
                 "scope": scope,
                 "kind": kind,
                 "workspaceId": workspace_id,
+                "subpath": subpath,
                 "content": content,
             }),
         )
         .await?;
-        return Ok(());
+        return serde_json::from_value(response).map_err(|err| err.to_string());
     }
 
-    file_write_core(&state.workspaces, scope, kind, workspace_id, content).await
+    file_write_core(
+        &state.workspaces,
+        &state.file_cache,
+        scope,
+        kind,
+        workspace_id,
+        subpath,
+        content,
+    )
+    .await
 }
 
+/// `subpath` targets an agents file in a workspace subdirectory, e.g. `packages/foo`.
 #[tauri::command]
 pub(crate) async fn file_read(
     scope: FileScope,
     kind: FileKind,
     workspace_id: Option<String>,
+    subpath: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<TextFileResponse, String> {
-    file_read_impl(scope, kind, workspace_id, &*state, &app).await
+    file_read_impl(scope, kind, workspace_id, subpath, &*state, &app).await
+}
+
+/// Reads the file progressively; pass the previous chunk's `nextOffset` until `eof`.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn file_read_chunk(
+    scope: FileScope,
+    kind: FileKind,
+    workspace_id: Option<String>,
+    subpath: Option<String>,
+    offset: Option<u64>,
+    max_bytes: Option<u32>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<TextFileChunk, String> {
+    let offset = offset.unwrap_or(0);
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "file_read_chunk",
+            json!({
+                "scope": scope,
+                "kind": kind,
+                "workspaceId": workspace_id,
+                "subpath": subpath,
+                "offset": offset,
+                "maxBytes": max_bytes,
+            }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    file_read_chunk_core(
+        &state.workspaces,
+        scope,
+        kind,
+        workspace_id,
+        subpath,
+        offset,
+        max_bytes,
+    )
+    .await
 }
 
 #[tauri::command]
@@ -75,9 +162,148 @@ pub(crate) async fn file_write(
     scope: FileScope,
     kind: FileKind,
     workspace_id: Option<String>,
+    subpath: Option<String>,
     content: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
-    file_write_impl(scope, kind, workspace_id, content, &*state, &app).await
+) -> Result<FileWriteResponse, String> {
+    file_write_impl(scope, kind, workspace_id, subpath, content, &*state, &app).await
+}
+
+#[tauri::command]
+pub(crate) async fn file_preview_write(
+    scope: FileScope,
+    kind: FileKind,
+    workspace_id: Option<String>,
+    subpath: Option<String>,
+    content: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WritePreview, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "file_preview_write",
+            json!({
+                "scope": scope,
+                "kind": kind,
+                "workspaceId": workspace_id,
+                "subpath": subpath,
+                "content": content,
+            }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    file_preview_write_core(
+        &state.workspaces,
+        &state.file_cache,
+        scope,
+        kind,
+        workspace_id,
+        subpath,
+        content,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn file_merge(
+    workspace_id: String,
+    kind: FileKind,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<MergeResult, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "file_merge",
+            json!({ "workspaceId": workspace_id, "kind": kind }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    file_merge_core(&state.workspaces, workspace_id, kind).await
+}
+
+#[tauri::command]
+pub(crate) async fn find_agents_files(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<AgentsFileEntry>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "find_agents_files",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    find_agents_files_core(&state.workspaces, &workspace_id).await
+}
+
+/// Trims per-project history in `~/.claude.json` to the `keep_recent` newest entries.
+#[tauri::command]
+pub(crate) async fn prune_claude_json(
+    keep_recent: usize,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<PruneResponse, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "prune_claude_json",
+            json!({ "keepRecent": keep_recent }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    prune_claude_json_core(&state.file_cache, keep_recent).await
+}
+
+/// Copies the global CLAUDE.md, settings.json and .claude.json into a timestamped backup.
+#[tauri::command]
+pub(crate) async fn backup_configs(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ConfigBackupResponse, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "backup_configs", json!({})).await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let data_dir = app_data_dir(&app)?;
+    backup_configs_core(&state.workspaces, &state.file_cache, &data_dir).await
+}
+
+#[tauri::command]
+pub(crate) async fn restore_configs(
+    backup_path: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ConfigBackupResponse, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "restore_configs",
+            json!({ "backupPath": backup_path }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let data_dir = app_data_dir(&app)?;
+    restore_configs_core(&state.workspaces, &state.file_cache, &data_dir, &backup_path).await
 }
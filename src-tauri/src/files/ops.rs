@@ -1,6 +1,9 @@
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 
-use crate::files::io::{read_text_file_within, write_text_file_within, TextFileResponse};
+use crate::files::io::{
+    read_text_chunk_within, read_text_file_within, replace_file_atomically,
+    resolve_write_target_within, write_text_file_within, TextFileChunk, TextFileResponse,
+};
 use crate::files::policy::FilePolicy;
 
 pub(crate) fn read_with_policy(root: &PathBuf, policy: FilePolicy) -> Result<TextFileResponse, String> {
@@ -14,15 +17,73 @@ pub(crate) fn read_with_policy(root: &PathBuf, policy: FilePolicy) -> Result<Tex
     )
 }
 
+pub(crate) fn read_chunk_with_policy(
+    root: &PathBuf,
+    policy: FilePolicy,
+    offset: u64,
+    max_bytes: usize,
+) -> Result<TextFileChunk, String> {
+    read_text_chunk_within(
+        root,
+        policy.filename,
+        policy.root_may_be_missing,
+        policy.root_context,
+        policy.filename,
+        policy.allow_external_symlink_target,
+        offset,
+        max_bytes,
+    )
+}
+
+/// Rewrites every line break as `\n` or `\r\n`.
+fn normalize_line_endings(content: &str, crlf: bool) -> String {
+    let normalized = content.replace("\r\n", "\n");
+    if crlf {
+        normalized.replace('\n', "\r\n")
+    } else {
+        normalized
+    }
+}
+
+fn uses_crlf(content: &str) -> bool {
+    let crlf = content.matches("\r\n").count();
+    crlf > 0 && crlf * 2 >= content.matches('\n').count()
+}
+
+/// Applies the policy's line-ending and final-newline rules relative to the file on disk.
+fn apply_eol_policy(content: &str, existing: Option<&str>, policy: FilePolicy) -> String {
+    let crlf = existing.is_some_and(uses_crlf);
+    let mut result = if policy.normalize_line_endings {
+        normalize_line_endings(content, crlf)
+    } else {
+        content.to_string()
+    };
+    let keep_final_newline = policy.preserve_final_newline
+        && existing.is_some_and(|existing| existing.ends_with('\n'));
+    if keep_final_newline && !result.is_empty() && !result.ends_with('\n') {
+        result.push_str(if crlf { "\r\n" } else { "\n" });
+    }
+    result
+}
+
 pub(crate) fn write_with_policy(
     root: &PathBuf,
     policy: FilePolicy,
     content: &str,
 ) -> Result<(), String> {
+    let existing = if policy.normalize_line_endings || policy.preserve_final_newline {
+        read_with_policy(root, policy)
+            .ok()
+            .filter(|existing| existing.exists)
+            .map(|existing| existing.content)
+    } else {
+        None
+    };
+    let content = apply_eol_policy(content, existing.as_deref(), policy);
     write_text_file_within(
         root,
         policy.filename,
-        content,
+        &content,
         policy.create_root,
         policy.root_context,
         policy.filename,
@@ -30,6 +91,49 @@ pub(crate) fn write_with_policy(
     )
 }
 
+/// Writes `content` verbatim (no line-ending policy) by atomically replacing the file.
+pub(crate) fn restore_with_policy(
+    root: &PathBuf,
+    policy: FilePolicy,
+    content: &str,
+) -> Result<(), String> {
+    let target_path = resolve_write_target_within(
+        root,
+        policy.filename,
+        policy.create_root,
+        policy.root_context,
+        policy.filename,
+        policy.allow_external_symlink_target,
+    )?;
+    replace_file_atomically(&target_path, content, policy.filename)
+}
+
+/// Resolves a relative directory beneath `root`, rejecting anything that escapes it
+/// (including through symlinked directories).
+pub(crate) fn resolve_subpath_root(root: &Path, subpath: &str) -> Result<PathBuf, String> {
+    let relative = Path::new(subpath.trim());
+    if !relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+    {
+        return Err("subpath must be relative to the workspace".to_string());
+    }
+    let canonical_root = root
+        .canonicalize()
+        .map_err(|err| format!("Failed to resolve workspace root: {err}"))?;
+    let canonical_subpath = canonical_root
+        .join(relative)
+        .canonicalize()
+        .map_err(|err| format!("Failed to resolve subpath: {err}"))?;
+    if !canonical_subpath.starts_with(&canonical_root) {
+        return Err("subpath must stay within the workspace".to_string());
+    }
+    if !canonical_subpath.is_dir() {
+        return Err("subpath is not a directory".to_string());
+    }
+    Ok(canonical_subpath)
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs;
@@ -38,7 +142,9 @@ mod tests {
 
     use crate::files::policy::{policy_for, FileKind, FileScope};
 
-    use super::{read_with_policy, write_with_policy};
+    use super::{
+        normalize_line_endings, read_with_policy, resolve_subpath_root, write_with_policy,
+    };
 
     fn temp_dir(prefix: &str) -> std::path::PathBuf {
         let dir = std::env::temp_dir().join(format!("codex-monitor-{prefix}-{}", Uuid::new_v4()));
@@ -105,4 +211,47 @@ mod tests {
 
         let _ = fs::remove_dir_all(&root);
     }
+
+    #[test]
+    fn subpath_root_stays_within_workspace() {
+        let root = temp_dir("workspace-subpath");
+        fs::create_dir_all(root.join("packages/foo")).expect("create package dir");
+        let policy = policy_for(FileScope::Workspace, FileKind::Agents).expect("policy");
+
+        let package = resolve_subpath_root(&root, "packages/foo").expect("resolve subpath");
+        write_with_policy(&package, policy, "package agents").expect("write agents");
+        assert!(root.join("packages/foo").join(policy.filename).exists());
+
+        assert!(resolve_subpath_root(&root, "../outside").is_err());
+        assert!(resolve_subpath_root(&root, "/etc").is_err());
+        assert!(resolve_subpath_root(&root, "packages/missing").is_err());
+
+        #[cfg(unix)]
+        {
+            let outside = temp_dir("workspace-subpath-outside");
+            fs::create_dir_all(&outside).expect("create outside dir");
+            std::os::unix::fs::symlink(&outside, root.join("linked")).expect("symlink");
+            assert!(resolve_subpath_root(&root, "linked").is_err());
+            let _ = fs::remove_dir_all(&outside);
+        }
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn normalize_line_endings_follows_existing_file() {
+        assert_eq!(normalize_line_endings("a\r\nb\nc", false), "a\nb\nc");
+        assert_eq!(normalize_line_endings("a\nb\r\n", true), "a\r\nb\r\n");
+
+        let root = temp_dir("workspace-crlf");
+        fs::create_dir_all(&root).expect("create workspace root");
+        let policy = policy_for(FileScope::Workspace, FileKind::Agents).expect("policy");
+        fs::write(root.join(policy.filename), "old\r\nfile\r\n").expect("seed crlf file");
+
+        write_with_policy(&root, policy, "new\nlines").expect("write agents");
+        let written = fs::read_to_string(root.join(policy.filename)).expect("read back");
+        assert_eq!(written, "new\r\nlines\r\n");
+
+        let _ = fs::remove_dir_all(&root);
+    }
 }
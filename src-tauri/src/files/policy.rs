@@ -22,6 +22,10 @@ pub(crate) struct FilePolicy {
     pub(crate) root_may_be_missing: bool,
     pub(crate) create_root: bool,
     pub(crate) allow_external_symlink_target: bool,
+    /// Rewrite line endings to match the existing file (LF when it is new) before writing.
+    pub(crate) normalize_line_endings: bool,
+    /// Keep a trailing newline when the existing file ends with one.
+    pub(crate) preserve_final_newline: bool,
 }
 
 const AGENTS_FILENAME: &str = "CLAUDE.md";
@@ -35,6 +39,8 @@ pub(crate) fn policy_for(scope: FileScope, kind: FileKind) -> Result<FilePolicy,
             root_may_be_missing: false,
             create_root: false,
             allow_external_symlink_target: false,
+            normalize_line_endings: true,
+            preserve_final_newline: true,
         }),
         (FileScope::Global, FileKind::Agents) => Ok(FilePolicy {
             filename: AGENTS_FILENAME,
@@ -42,6 +48,8 @@ pub(crate) fn policy_for(scope: FileScope, kind: FileKind) -> Result<FilePolicy,
             root_may_be_missing: true,
             create_root: true,
             allow_external_symlink_target: true,
+            normalize_line_endings: true,
+            preserve_final_newline: true,
         }),
         (FileScope::Global, FileKind::Config) => Ok(FilePolicy {
             filename: CONFIG_FILENAME,
@@ -49,6 +57,8 @@ pub(crate) fn policy_for(scope: FileScope, kind: FileKind) -> Result<FilePolicy,
             root_may_be_missing: true,
             create_root: true,
             allow_external_symlink_target: false,
+            normalize_line_endings: true,
+            preserve_final_newline: false,
         }),
         (FileScope::Global, FileKind::ClaudeJson) => Ok(FilePolicy {
             filename: ".claude.json",
@@ -56,6 +66,8 @@ pub(crate) fn policy_for(scope: FileScope, kind: FileKind) -> Result<FilePolicy,
             root_may_be_missing: false,
             create_root: false,
             allow_external_symlink_target: false,
+            normalize_line_endings: false,
+            preserve_final_newline: false,
         }),
         (FileScope::Workspace, FileKind::ClaudeJson) => {
             Err(".claude.json is only supported for global scope".to_string())
@@ -78,6 +90,8 @@ mod tests {
         assert!(!policy.root_may_be_missing);
         assert!(!policy.create_root);
         assert!(!policy.allow_external_symlink_target);
+        assert!(policy.normalize_line_endings);
+        assert!(policy.preserve_final_newline);
     }
 
     #[test]
@@ -114,6 +128,7 @@ mod tests {
         assert!(!policy.root_may_be_missing);
         assert!(!policy.create_root);
         assert!(!policy.allow_external_symlink_target);
+        assert!(!policy.normalize_line_endings);
     }
 
     #[test]
@@ -0,0 +1,330 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::de::{self, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::files::io::{replace_file_if_unchanged, sibling_path, write_new_file};
+use crate::files::ops::read_with_policy;
+use crate::files::policy::FilePolicy;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PruneResponse {
+    pub removed: usize,
+    /// Copy of the file as it was before pruning; unset when nothing was removed.
+    pub backup_path: Option<String>,
+}
+
+/// A JSON document that keeps object keys in the order they appear on disk, so pruning
+/// `~/.claude.json` rewrites only the history arrays instead of re-sorting every key
+/// (`serde_json::Map` is a `BTreeMap` in this crate).
+#[derive(Debug)]
+enum OrderedJson {
+    Object(Vec<(String, OrderedJson)>),
+    Array(Vec<OrderedJson>),
+    Scalar(Value),
+}
+
+impl OrderedJson {
+    fn get_mut(&mut self, key: &str) -> Option<&mut OrderedJson> {
+        match self {
+            OrderedJson::Object(entries) => entries
+                .iter_mut()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+}
+
+impl Serialize for OrderedJson {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            OrderedJson::Object(entries) => {
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+            OrderedJson::Array(items) => items.serialize(serializer),
+            OrderedJson::Scalar(value) => value.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for OrderedJson {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(OrderedJsonVisitor)
+    }
+}
+
+struct OrderedJsonVisitor;
+
+impl<'de> Visitor<'de> for OrderedJsonVisitor {
+    type Value = OrderedJson;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any JSON value")
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<OrderedJson, E> {
+        Ok(OrderedJson::Scalar(Value::Bool(value)))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<OrderedJson, E> {
+        Ok(OrderedJson::Scalar(Value::from(value)))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<OrderedJson, E> {
+        Ok(OrderedJson::Scalar(Value::from(value)))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<OrderedJson, E> {
+        Ok(OrderedJson::Scalar(Value::from(value)))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<OrderedJson, E> {
+        Ok(OrderedJson::Scalar(Value::from(value)))
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<OrderedJson, E> {
+        Ok(OrderedJson::Scalar(Value::String(value)))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<OrderedJson, E> {
+        Ok(OrderedJson::Scalar(Value::Null))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<OrderedJson, E> {
+        Ok(OrderedJson::Scalar(Value::Null))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<OrderedJson, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(OrderedJson::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<OrderedJson, A::Error> {
+        let mut entries = Vec::new();
+        while let Some((key, value)) = map.next_entry()? {
+            entries.push((key, value));
+        }
+        Ok(OrderedJson::Object(entries))
+    }
+}
+
+/// Truncates every `projects.<path>.history` array to its first `keep_recent` entries
+/// (Claude Code prepends new history, so these are the most recent) and returns how many
+/// entries were dropped. Everything else in the document is left untouched.
+fn prune_project_history(document: &mut OrderedJson, keep_recent: usize) -> usize {
+    let Some(OrderedJson::Object(projects)) = document.get_mut("projects") else {
+        return 0;
+    };
+    let mut removed = 0;
+    for (_, project) in projects.iter_mut() {
+        let Some(OrderedJson::Array(history)) = project.get_mut("history") else {
+            continue;
+        };
+        if history.len() > keep_recent {
+            removed += history.len() - keep_recent;
+            history.truncate(keep_recent);
+        }
+    }
+    removed
+}
+
+/// Prunes the policy's JSON file in place. The original is saved to a new timestamped
+/// `<file>.<time>.bak` and the pruned document is written to a temporary file that is renamed
+/// over the original, unless the original changed on disk since it was read.
+pub(crate) fn prune_json_history_with_policy(
+    root: &PathBuf,
+    policy: FilePolicy,
+    keep_recent: usize,
+) -> Result<PruneResponse, String> {
+    let filename = policy.filename;
+    let modified_before_read = std::fs::metadata(root.join(filename))
+        .and_then(|metadata| metadata.modified())
+        .ok();
+    let current = read_with_policy(root, policy)?;
+    if !current.exists {
+        return Err(format!("{filename} not found"));
+    }
+    if current.truncated {
+        return Err(format!("{filename} is too large to prune"));
+    }
+    let Some(modified_before_read) = modified_before_read else {
+        return Err(format!("{filename} changed on disk; try again"));
+    };
+    let mut document: OrderedJson = serde_json::from_str(&current.content)
+        .map_err(|err| format!("{filename} is not valid JSON: {err}"))?;
+    let removed = prune_project_history(&mut document, keep_recent);
+    if removed == 0 {
+        return Ok(PruneResponse {
+            removed,
+            backup_path: None,
+        });
+    }
+
+    let mut content = serde_json::to_string_pretty(&document).map_err(|err| err.to_string())?;
+    if current.content.ends_with('\n') {
+        content.push('\n');
+    }
+    serde_json::from_str::<Value>(&content)
+        .map_err(|err| format!("Pruned {filename} is not valid JSON: {err}"))?;
+
+    // read_with_policy has already checked the path stays within the root.
+    let path = root
+        .join(filename)
+        .canonicalize()
+        .map_err(|err| format!("Failed to resolve {filename}: {err}"))?;
+    let backup_path = write_backup(&path, &current.content)
+        .map_err(|err| format!("Failed to back up {filename}: {err}"))?;
+    replace_file_if_unchanged(&path, &content, filename, modified_before_read)?;
+
+    Ok(PruneResponse {
+        removed,
+        backup_path: Some(backup_path.to_string_lossy().to_string()),
+    })
+}
+
+/// Writes `content` to a fresh `<file>.<time>.bak` next to `path` with the same permissions,
+/// so earlier backups are kept and a private file's backup is private too.
+fn write_backup(path: &Path, content: &str) -> std::io::Result<PathBuf> {
+    let permissions = std::fs::metadata(path)?.permissions();
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let mut attempt = 0;
+    loop {
+        let suffix = if attempt == 0 {
+            format!(".{stamp}.bak")
+        } else {
+            format!(".{stamp}-{attempt}.bak")
+        };
+        let backup_path = sibling_path(path, &suffix);
+        match write_new_file(&backup_path, content.as_bytes(), Some(&permissions)) {
+            Ok(()) => return Ok(backup_path),
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => attempt += 1,
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use serde_json::{json, Value};
+    use uuid::Uuid;
+
+    use super::{prune_json_history_with_policy, prune_project_history, OrderedJson};
+    use crate::files::policy::{policy_for, FileKind, FileScope};
+
+    #[test]
+    fn prune_keeps_most_recent_history_per_project() {
+        let mut document: OrderedJson = serde_json::from_value(json!({
+            "numStartups": 3,
+            "projects": {
+                "/a": { "history": [1, 2, 3, 4], "allowedTools": [] },
+                "/b": { "history": [1] },
+                "/c": { "mcpServers": {} },
+            },
+        }))
+        .expect("ordered document");
+        assert_eq!(prune_project_history(&mut document, 2), 2);
+        let document = serde_json::to_value(&document).expect("to value");
+        assert_eq!(document["projects"]["/a"]["history"], json!([1, 2]));
+        assert_eq!(document["projects"]["/a"]["allowedTools"], json!([]));
+        assert_eq!(document["projects"]["/b"]["history"], json!([1]));
+        assert_eq!(document["projects"]["/c"], json!({ "mcpServers": {} }));
+        assert_eq!(document["numStartups"], json!(3));
+
+        let mut projects_list: OrderedJson =
+            serde_json::from_value(json!({ "projects": [] })).expect("ordered document");
+        assert_eq!(prune_project_history(&mut projects_list, 0), 0);
+    }
+
+    #[test]
+    fn prune_writes_pruned_file_and_backup() {
+        let root = std::env::temp_dir().join(format!("codex-monitor-prune-{}", Uuid::new_v4()));
+        fs::create_dir_all(&root).expect("create root");
+        let policy = policy_for(FileScope::Global, FileKind::ClaudeJson).expect("policy");
+        let path = root.join(policy.filename);
+        let original = "{\"projects\":{\"/a\":{\"history\":[\"c\",\"b\",\"a\"]}}}\n";
+        fs::write(&path, original).expect("seed file");
+
+        let response = prune_json_history_with_policy(&root, policy, 1).expect("prune");
+        assert_eq!(response.removed, 2);
+        let backup_path = response.backup_path.expect("backup path");
+        assert_eq!(fs::read_to_string(&backup_path).expect("read backup"), original);
+
+        let written = fs::read_to_string(&path).expect("read pruned");
+        assert!(written.ends_with('\n'));
+        let document: Value = serde_json::from_str(&written).expect("valid json");
+        assert_eq!(document["projects"]["/a"]["history"], json!(["c"]));
+
+        let response = prune_json_history_with_policy(&root, policy, 1).expect("prune again");
+        assert_eq!(response.removed, 0);
+        assert!(response.backup_path.is_none());
+
+        fs::write(&path, "{\"projects\":{\"/a\":{\"history\":[\"d\",\"c\"]}}}").expect("reseed");
+        let second = prune_json_history_with_policy(&root, policy, 1).expect("prune reseeded");
+        let second_backup = second.backup_path.expect("second backup path");
+        assert_ne!(second_backup, backup_path);
+        assert_eq!(fs::read_to_string(&backup_path).expect("first backup kept"), original);
+
+        fs::write(&path, "{not json").expect("corrupt file");
+        let error = prune_json_history_with_policy(&root, policy, 1).expect_err("invalid json");
+        assert!(error.contains("not valid JSON"));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn prune_preserves_key_order() {
+        let root = std::env::temp_dir().join(format!("codex-monitor-prune-{}", Uuid::new_v4()));
+        fs::create_dir_all(&root).expect("create root");
+        let policy = policy_for(FileScope::Global, FileKind::ClaudeJson).expect("policy");
+        let path = root.join(policy.filename);
+        let original = r#"{"zeta":1,"projects":{"/b":{"history":[2,1]},"/a":{}},"alpha":2}"#;
+        fs::write(&path, original).expect("seed file");
+
+        prune_json_history_with_policy(&root, policy, 1).expect("prune");
+        let written = fs::read_to_string(&path).expect("read pruned");
+        let positions: Vec<usize> = ["zeta", "projects", "/b", "/a", "alpha"]
+            .iter()
+            .map(|key| written.find(&format!("\"{key}\"")).expect("key present"))
+            .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn prune_preserves_private_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = std::env::temp_dir().join(format!("codex-monitor-prune-{}", Uuid::new_v4()));
+        fs::create_dir_all(&root).expect("create root");
+        let policy = policy_for(FileScope::Global, FileKind::ClaudeJson).expect("policy");
+        let path = root.join(policy.filename);
+        fs::write(&path, "{\"projects\":{\"/a\":{\"history\":[2,1]}}}").expect("seed file");
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).expect("chmod");
+
+        let response = prune_json_history_with_policy(&root, policy, 1).expect("prune");
+        let mode = |path: &std::path::Path| {
+            fs::metadata(path).expect("metadata").permissions().mode() & 0o777
+        };
+        assert_eq!(mode(&path), 0o600);
+        let backup_path = response.backup_path.expect("backup path");
+        assert_eq!(mode(std::path::Path::new(&backup_path)), 0o600);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
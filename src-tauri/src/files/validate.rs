@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+const LARGE_AGENTS_FILE_BYTES: usize = 256 * 1024;
+const CONFLICT_MARKERS: [&str; 3] = ["<<<<<<<", "=======", ">>>>>>>"];
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct FileWriteWarning {
+    pub code: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct FileWriteResponse {
+    pub warnings: Vec<FileWriteWarning>,
+}
+
+fn warning(code: &str, message: String, line: Option<usize>) -> FileWriteWarning {
+    FileWriteWarning {
+        code: code.to_string(),
+        message,
+        line,
+    }
+}
+
+/// Checks an agents file before it is saved. UTF-8 is already guaranteed by the string type,
+/// so only NUL bytes are fatal; everything else is reported as a warning.
+pub(crate) fn validate_agents_content(content: &str) -> Result<Vec<FileWriteWarning>, String> {
+    if content.contains('\0') {
+        return Err("CLAUDE.md contains NUL bytes and looks like binary data".to_string());
+    }
+    let mut warnings = Vec::new();
+    if content.len() > LARGE_AGENTS_FILE_BYTES {
+        warnings.push(warning(
+            "large_file",
+            format!(
+                "CLAUDE.md is {} KB; large instructions files slow down every session",
+                content.len() / 1024
+            ),
+            None,
+        ));
+    }
+    for (index, line) in content.lines().enumerate() {
+        let is_marker = CONFLICT_MARKERS.iter().any(|marker| {
+            line.strip_prefix(marker)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+        });
+        if is_marker {
+            warnings.push(warning(
+                "conflict_marker",
+                format!("Unresolved merge-conflict marker on line {}", index + 1),
+                Some(index + 1),
+            ));
+        }
+    }
+    Ok(warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_agents_content, LARGE_AGENTS_FILE_BYTES};
+
+    #[test]
+    fn agents_validation_rejects_nul_bytes() {
+        assert!(validate_agents_content("# Agent\0").is_err());
+    }
+
+    #[test]
+    fn agents_validation_reports_conflict_markers_and_size() {
+        let content = "# Agent\n<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> main\n========\n";
+        let warnings = validate_agents_content(content).expect("validate");
+        let lines: Vec<_> = warnings.iter().map(|warning| warning.line).collect();
+        assert_eq!(lines, vec![Some(2), Some(4), Some(6)]);
+        assert!(warnings
+            .iter()
+            .all(|warning| warning.code == "conflict_marker"));
+
+        let large = "a".repeat(LARGE_AGENTS_FILE_BYTES + 1);
+        let warnings = validate_agents_content(&large).expect("validate");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].code, "large_file");
+
+        assert!(validate_agents_content("# Agent\n").expect("validate").is_empty());
+    }
+}
@@ -1,26 +1,51 @@
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
 
 use base64::{engine::general_purpose::STANDARD, Engine as _};
-use git2::{BranchType, DiffOptions, Repository, Sort, Status, StatusOptions};
+use git2::{BranchType, DiffFormat, DiffOptions, Repository, Sort, Status, StatusOptions};
 use serde_json::json;
+use tauri::ipc::Channel;
 use tauri::State;
 
+use crate::shared::codex_core::MONITOR_ONLY_ERROR;
 use crate::shared::process_core::tokio_command;
 use crate::git_utils::{
-    checkout_branch, commit_to_entry, diff_patch_to_string, diff_stats_for_path,
-    image_mime_type, list_git_roots as scan_git_roots, parse_github_repo, resolve_git_root,
+    checkout_branch, commit_to_entry, current_branch_name, diff_patch_hunks, diff_patch_to_string,
+    diff_stats_for_path, git_push, head_is_unborn, head_tree, image_mime_type,
+    list_git_roots as scan_git_roots, open_repository, parse_github_repo, resolve_git_root,
+    GitRepoError,
 };
 use crate::state::AppState;
 use crate::types::{
-    BranchInfo, GitCommitDiff, GitFileDiff, GitFileStatus, GitHubIssue, GitHubIssuesResponse,
-    GitHubPullRequest, GitHubPullRequestComment, GitHubPullRequestDiff,
-    GitHubPullRequestsResponse, GitLogResponse,
+    EditorConfigDefaults, GitChangedFile, GitCommitDetails, GitCommitDiff, GitCommitError,
+    GitCommitFileStat, GitDiffHunkPage, GitOperationState,
+    GitBranchInfo, GitDiffMode, GitFileDiff, GitFileStatus, GitPathStatus,
+    GitHubIssue, GitHubIssuesResponse, GitHubPullRequest, GitHubPullRequestComment,
+    GitHubPullRequestDiff, GitHubPullRequestsResponse, GitLogEntry, GitLogResponse, GitReflogEntry,
+    GitRemoteInfo, GitResetMode, GitResetResult, GitReviewBundle, GitSignatureInfo, GitStagedFile,
+    GitStashEntry,
+    GitStatusChunk, ProjectLanguageStat, ProjectSummary, RepoConventions, WorkspaceEntry,
+    WorkspaceSettings,
 };
 use crate::utils::{git_env_path, normalize_git_path, resolve_git_binary};
 
+pub(crate) mod watcher;
+
 const INDEX_SKIP_WORKTREE_FLAG: u16 = 0x4000;
 const MAX_IMAGE_BYTES: usize = 10 * 1024 * 1024;
+const DEFAULT_GIT_LOG_LIMIT: usize = 40;
+const MAX_GIT_LOG_LIMIT: usize = 1000;
+const GIT_STATUS_CHUNK_SIZE: usize = 20;
+const DEFAULT_HUNK_PAGE_SIZE: usize = 50;
+const MAX_HUNK_PAGE_SIZE: usize = 500;
+const PROJECT_SUMMARY_LANGUAGES: usize = 5;
+// Same heuristic git uses: a NUL byte near the start marks the file as binary.
+const BINARY_SNIFF_BYTES: usize = 8000;
+const CONVENTION_SAMPLE_FILES: usize = 200;
+const CONVENTION_SAMPLE_BYTES: u64 = 64 * 1024;
 
 fn encode_image_base64(data: &[u8]) -> Option<String> {
     if data.len() > MAX_IMAGE_BYTES {
@@ -143,7 +168,7 @@ fn parse_upstream_ref(name: &str) -> Option<(String, String)> {
 }
 
 fn upstream_remote_and_branch(repo_root: &Path) -> Result<Option<(String, String)>, String> {
-    let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+    let repo = open_repository(repo_root).map_err(|e| e.to_string())?;
     let head = match repo.head() {
         Ok(head) => head,
         Err(_) => return Ok(None),
@@ -259,11 +284,8 @@ fn build_combined_diff(diff: &git2::Diff) -> String {
 }
 
 fn collect_workspace_diff(repo_root: &Path) -> Result<String, String> {
-    let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
-    let head_tree = repo
-        .head()
-        .ok()
-        .and_then(|head| head.peel_to_tree().ok());
+    let repo = open_repository(repo_root).map_err(|e| e.to_string())?;
+    let head_tree = head_tree(&repo);
 
     let mut options = DiffOptions::new();
     let index = repo.index().map_err(|e| e.to_string())?;
@@ -297,7 +319,7 @@ fn collect_workspace_diff(repo_root: &Path) -> Result<String, String> {
 }
 
 fn github_repo_from_path(path: &Path) -> Result<String, String> {
-    let repo = Repository::open(path).map_err(|e| e.to_string())?;
+    let repo = open_repository(path).map_err(|e| e.to_string())?;
     let remotes = repo.remotes().map_err(|e| e.to_string())?;
     let name = if remotes.iter().any(|remote| remote == Some("origin")) {
         "origin".to_string()
@@ -410,27 +432,244 @@ fn parse_pr_diff(diff: &str) -> Vec<GitHubPullRequestDiff> {
 
     entries
 }
-#[tauri::command]
-pub(crate) async fn get_git_status(
-    workspace_id: String,
-    state: State<'_, AppState>,
-) -> Result<serde_json::Value, String> {
-    let workspaces = state.workspaces.lock().await;
-    let entry = workspaces
-        .get(&workspace_id)
-        .ok_or("workspace not found")?
-        .clone();
-    drop(workspaces);
 
-    let repo_root = resolve_git_root(&entry)?;
-    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+fn resolve_git_log_limit(limit: Option<usize>) -> Result<usize, String> {
+    let limit = limit.unwrap_or(DEFAULT_GIT_LOG_LIMIT);
+    if limit > MAX_GIT_LOG_LIMIT {
+        return Err(format!(
+            "Git log limit {limit} exceeds the maximum of {MAX_GIT_LOG_LIMIT} entries"
+        ));
+    }
+    Ok(limit)
+}
+
+fn stale_git_log_cursor(cursor: &str) -> String {
+    format!("Git log cursor {cursor} is no longer in the history; reload the log")
+}
 
-    let branch_name = repo
+/// Resolves a `get_git_log` cursor, rejecting commits that are gone or no longer reachable
+/// from HEAD (e.g. after a force-push), since paging from them would show stale history.
+fn resolve_git_log_cursor(repo: &Repository, cursor: &str) -> Result<git2::Oid, String> {
+    let oid = git2::Oid::from_str(cursor.trim())
+        .map_err(|_| format!("Invalid git log cursor: {cursor}"))?;
+    repo.find_commit(oid).map_err(|_| stale_git_log_cursor(cursor))?;
+    let head = repo
         .head()
         .ok()
-        .and_then(|head| head.shorthand().map(|s| s.to_string()))
-        .unwrap_or_else(|| "unknown".to_string());
+        .and_then(|head| head.target())
+        .ok_or_else(|| "Failed to resolve HEAD".to_string())?;
+    let reachable = head == oid
+        || repo
+            .graph_descendant_of(head, oid)
+            .map_err(|e| e.to_string())?;
+    if !reachable {
+        return Err(stale_git_log_cursor(cursor));
+    }
+    Ok(oid)
+}
+
+/// Optional `get_git_log` filters; a commit must match all that are set.
+#[derive(Default)]
+struct GitLogFilter {
+    /// Pathspec the commit's diff against its first parent must touch.
+    path: Option<String>,
+    /// Lowercased substring of the author name or email.
+    author: Option<String>,
+}
+
+impl GitLogFilter {
+    fn new(path: Option<String>, author: Option<String>) -> Self {
+        let non_empty = |value: Option<String>| {
+            value
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        Self {
+            path: non_empty(path),
+            author: non_empty(author).map(|author| author.to_lowercase()),
+        }
+    }
+
+    fn matches(&self, repo: &Repository, commit: &git2::Commit) -> Result<bool, String> {
+        if let Some(author) = &self.author {
+            let signature = commit.author();
+            let matches = [signature.name(), signature.email()]
+                .into_iter()
+                .flatten()
+                .any(|value| value.to_lowercase().contains(author));
+            if !matches {
+                return Ok(false);
+            }
+        }
+        if let Some(path) = &self.path {
+            let parent_tree = match commit.parent(0) {
+                Ok(parent) => Some(parent.tree().map_err(|e| e.to_string())?),
+                Err(_) => None,
+            };
+            let tree = commit.tree().map_err(|e| e.to_string())?;
+            let mut options = DiffOptions::new();
+            options.pathspec(path);
+            let diff = repo
+                .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut options))
+                .map_err(|e| e.to_string())?;
+            if diff.deltas().len() == 0 {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Returns up to `max_items` commits after `cursor` (or from HEAD) with a single revwalk
+/// started at the cursor, so a page costs the same however deep it is; `next_cursor` is set
+/// when more commits follow. Counting the whole history for `total` is
+/// opt-in because it walks every commit.
+fn git_log_for_repo(
+    repo: &Repository,
+    max_items: usize,
+    cursor: Option<&str>,
+    count_total: bool,
+    filter: &GitLogFilter,
+) -> Result<GitLogResponse, String> {
+    if head_is_unborn(repo) {
+        return Ok(GitLogResponse {
+            total: count_total.then_some(0),
+            next_cursor: None,
+            entries: Vec::new(),
+            ahead: 0,
+            behind: 0,
+            ahead_entries: Vec::new(),
+            behind_entries: Vec::new(),
+            upstream: None,
+        });
+    }
+
+    let total = if count_total {
+        let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
+        revwalk.push_head().map_err(|e| e.to_string())?;
+        let mut total = 0usize;
+        for oid_result in revwalk {
+            let oid = oid_result.map_err(|e| e.to_string())?;
+            if filter.path.is_none() && filter.author.is_none() {
+                total += 1;
+                continue;
+            }
+            let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+            if filter.matches(repo, &commit)? {
+                total += 1;
+            }
+        }
+        Some(total)
+    } else {
+        None
+    };
+
+    let cursor_oid = cursor
+        .map(|cursor| resolve_git_log_cursor(repo, cursor))
+        .transpose()?;
+    // A page resumes from the cursor, the last entry of the previous page, and skips it.
+    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
+    match cursor_oid {
+        Some(oid) => revwalk.push(oid).map_err(|e| e.to_string())?,
+        None => revwalk.push_head().map_err(|e| e.to_string())?,
+    }
+    revwalk
+        .set_sorting(Sort::TIME)
+        .map_err(|e| e.to_string())?;
+
+    let mut entries = Vec::new();
+    let mut has_more = false;
+    for oid_result in revwalk.skip(usize::from(cursor_oid.is_some())) {
+        let oid = oid_result.map_err(|e| e.to_string())?;
+        let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+        if !filter.matches(repo, &commit)? {
+            continue;
+        }
+        if entries.len() == max_items {
+            has_more = true;
+            break;
+        }
+        entries.push(commit_to_entry(commit));
+    }
+    let next_cursor = entries
+        .last()
+        .filter(|_| has_more)
+        .map(|entry| entry.sha.clone());
+
+    let mut ahead = 0usize;
+    let mut behind = 0usize;
+    let mut ahead_entries = Vec::new();
+    let mut behind_entries = Vec::new();
+    let mut upstream = None;
+
+    if let Some(tracking) = head_upstream(repo) {
+        upstream = tracking.name;
+        if let Some((head_oid, upstream_oid)) = tracking.oids {
+            let (ahead_count, behind_count) = repo
+                .graph_ahead_behind(head_oid, upstream_oid)
+                .map_err(|e| e.to_string())?;
+            ahead = ahead_count;
+            behind = behind_count;
+
+            let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
+            revwalk.push(head_oid).map_err(|e| e.to_string())?;
+            revwalk.hide(upstream_oid).map_err(|e| e.to_string())?;
+            revwalk
+                .set_sorting(Sort::TIME)
+                .map_err(|e| e.to_string())?;
+            for oid_result in revwalk.take(max_items) {
+                let oid = oid_result.map_err(|e| e.to_string())?;
+                let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+                ahead_entries.push(commit_to_entry(commit));
+            }
+
+            let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
+            revwalk.push(upstream_oid).map_err(|e| e.to_string())?;
+            revwalk.hide(head_oid).map_err(|e| e.to_string())?;
+            revwalk
+                .set_sorting(Sort::TIME)
+                .map_err(|e| e.to_string())?;
+            for oid_result in revwalk.take(max_items) {
+                let oid = oid_result.map_err(|e| e.to_string())?;
+                let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+                behind_entries.push(commit_to_entry(commit));
+            }
+        }
+    }
+
+    Ok(GitLogResponse {
+        total,
+        next_cursor,
+        entries,
+        ahead,
+        behind,
+        ahead_entries,
+        behind_entries,
+        upstream,
+    })
+}
+
+fn rename_paths(delta: git2::DiffDelta) -> Option<(String, String)> {
+    let old_path = normalize_git_path(delta.old_file().path()?.to_string_lossy().as_ref());
+    let new_path = normalize_git_path(delta.new_file().path()?.to_string_lossy().as_ref());
+    if old_path == new_path {
+        return None;
+    }
+    Some((old_path, new_path))
+}
+
+struct GitStatusEntry {
+    file: GitFileStatus,
+    staged: Option<GitFileStatus>,
+    unstaged: Option<GitFileStatus>,
+}
 
+/// Walks the repo status, computing diff stats per entry and handing each to `on_entry`
+/// as soon as it's ready.
+fn visit_git_status<F>(repo: &Repository, mut on_entry: F) -> Result<(), String>
+where
+    F: FnMut(GitStatusEntry) -> Result<(), String>,
+{
     let mut status_options = StatusOptions::new();
     status_options
         .include_untracked(true)
@@ -443,14 +682,9 @@ pub(crate) async fn get_git_status(
         .statuses(Some(&mut status_options))
         .map_err(|e| e.to_string())?;
 
-    let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+    let head_tree = head_tree(repo);
     let index = repo.index().ok();
 
-    let mut files = Vec::new();
-    let mut staged_files = Vec::new();
-    let mut unstaged_files = Vec::new();
-    let mut total_additions = 0i64;
-    let mut total_deletions = 0i64;
     for entry in statuses.iter() {
         let path = entry.path().unwrap_or("");
         if path.is_empty() {
@@ -465,6 +699,16 @@ pub(crate) async fn get_git_status(
         }
         let status = entry.status();
         let normalized_path = normalize_git_path(path);
+        let index_rename = if status.contains(Status::INDEX_RENAMED) {
+            entry.head_to_index().and_then(rename_paths)
+        } else {
+            None
+        };
+        let workdir_rename = if status.contains(Status::WT_RENAMED) {
+            entry.index_to_workdir().and_then(rename_paths)
+        } else {
+            None
+        };
         let include_index = status.intersects(
             Status::INDEX_NEW
                 | Status::INDEX_MODIFIED
@@ -481,175 +725,211 @@ pub(crate) async fn get_git_status(
         );
         let mut combined_additions = 0i64;
         let mut combined_deletions = 0i64;
+        let mut staged = None;
+        let mut unstaged = None;
 
         if include_index {
-            let (additions, deletions) =
-                diff_stats_for_path(&repo, head_tree.as_ref(), path, true, false)
-                    .unwrap_or((0, 0));
-            if let Some(status_str) = status_for_index(status) {
-                staged_files.push(GitFileStatus {
-                    path: normalized_path.clone(),
-                    status: status_str.to_string(),
-                    additions,
-                    deletions,
-                });
-            }
+            let (stats_path, rename_from) = match index_rename.as_ref() {
+                Some((old_path, new_path)) => (new_path.as_str(), Some(old_path.as_str())),
+                None => (path, None),
+            };
+            let (additions, deletions) = diff_stats_for_path(
+                repo,
+                head_tree.as_ref(),
+                stats_path,
+                rename_from,
+                true,
+                false,
+            )
+            .unwrap_or((0, 0));
+            staged = status_for_index(status).map(|status_str| GitFileStatus {
+                path: normalize_git_path(stats_path),
+                status: status_str.to_string(),
+                additions,
+                deletions,
+                rename_from: rename_from.map(str::to_string),
+            });
             combined_additions += additions;
             combined_deletions += deletions;
-            total_additions += additions;
-            total_deletions += deletions;
         }
 
         if include_workdir {
-            let (additions, deletions) =
-                diff_stats_for_path(&repo, head_tree.as_ref(), path, false, true)
-                    .unwrap_or((0, 0));
-            if let Some(status_str) = status_for_workdir(status) {
-                unstaged_files.push(GitFileStatus {
-                    path: normalized_path.clone(),
-                    status: status_str.to_string(),
-                    additions,
-                    deletions,
-                });
-            }
+            let (stats_path, rename_from) = match workdir_rename.as_ref() {
+                Some((old_path, new_path)) => (new_path.as_str(), Some(old_path.as_str())),
+                None => (
+                    index_rename
+                        .as_ref()
+                        .map(|(_, new_path)| new_path.as_str())
+                        .unwrap_or(path),
+                    None,
+                ),
+            };
+            let (additions, deletions) = diff_stats_for_path(
+                repo,
+                head_tree.as_ref(),
+                stats_path,
+                rename_from,
+                false,
+                true,
+            )
+            .unwrap_or((0, 0));
+            unstaged = status_for_workdir(status).map(|status_str| GitFileStatus {
+                path: normalize_git_path(stats_path),
+                status: status_str.to_string(),
+                additions,
+                deletions,
+                rename_from: rename_from.map(str::to_string),
+            });
             combined_additions += additions;
             combined_deletions += deletions;
-            total_additions += additions;
-            total_deletions += deletions;
         }
 
         if include_index || include_workdir {
             let status_str = status_for_workdir(status)
                 .or_else(|| status_for_index(status))
                 .unwrap_or("--");
-            files.push(GitFileStatus {
-                path: normalized_path,
-                status: status_str.to_string(),
-                additions: combined_additions,
-                deletions: combined_deletions,
-            });
+            let display_path = workdir_rename
+                .as_ref()
+                .or(index_rename.as_ref())
+                .map(|(_, new_path)| new_path.clone())
+                .unwrap_or(normalized_path);
+            let rename_from = index_rename
+                .as_ref()
+                .or(workdir_rename.as_ref())
+                .map(|(old_path, _)| old_path.clone());
+            on_entry(GitStatusEntry {
+                file: GitFileStatus {
+                    path: display_path,
+                    status: status_str.to_string(),
+                    additions: combined_additions,
+                    deletions: combined_deletions,
+                    rename_from,
+                },
+                staged,
+                unstaged,
+            })?;
         }
     }
-
-    Ok(json!({
-        "branchName": branch_name,
-        "files": files,
-        "stagedFiles": staged_files,
-        "unstagedFiles": unstaged_files,
-        "totalAdditions": total_additions,
-        "totalDeletions": total_deletions,
-    }))
+    Ok(())
 }
 
-#[tauri::command]
-pub(crate) async fn stage_git_file(
-    workspace_id: String,
-    path: String,
-    state: State<'_, AppState>,
-) -> Result<(), String> {
-    let entry = {
-        let workspaces = state.workspaces.lock().await;
-        workspaces
-            .get(&workspace_id)
-            .cloned()
-            .ok_or("workspace not found")?
-    };
+/// The upstream tracking branch of the checked-out branch.
+struct HeadUpstream {
+    /// Short name such as `origin/main`.
+    name: Option<String>,
+    /// HEAD and upstream commits; unset when either ref does not point at a commit.
+    oids: Option<(git2::Oid, git2::Oid)>,
+}
 
-    let repo_root = resolve_git_root(&entry)?;
-    // If libgit2 reports a rename, we want a single UI action to stage both the
-    // old + new paths so the change actually moves to the staged section.
-    for path in action_paths_for_file(&repo_root, &path) {
-        run_git_command(&repo_root, &["add", "-A", "--", &path]).await?;
+/// `None` when HEAD is detached, unborn or its branch has no upstream configured.
+fn head_upstream(repo: &Repository) -> Option<HeadUpstream> {
+    let head = repo.head().ok()?;
+    if !head.is_branch() {
+        return None;
     }
-    Ok(())
+    let branch = repo.find_branch(head.shorthand()?, BranchType::Local).ok()?;
+    let upstream_branch = branch.upstream().ok()?;
+    let upstream_ref = upstream_branch.get();
+    let name = upstream_ref
+        .shorthand()
+        .map(|name| name.to_string())
+        .or_else(|| upstream_ref.name().map(|name| name.to_string()));
+    let oids = head.target().zip(upstream_ref.target());
+    Some(HeadUpstream { name, oids })
 }
 
-#[tauri::command]
-pub(crate) async fn stage_git_all(
-    workspace_id: String,
-    state: State<'_, AppState>,
-) -> Result<(), String> {
-    let entry = {
-        let workspaces = state.workspaces.lock().await;
-        workspaces
-            .get(&workspace_id)
-            .cloned()
-            .ok_or("workspace not found")?
-    };
-
-    let repo_root = resolve_git_root(&entry)?;
-    run_git_command(&repo_root, &["add", "-A"]).await
+/// Commits HEAD is ahead of and behind its upstream; `None` when HEAD tracks no upstream or
+/// the counts cannot be computed, so status never fails over a missing upstream commit.
+fn upstream_ahead_behind(repo: &Repository) -> Option<(usize, usize)> {
+    let (head_oid, upstream_oid) = head_upstream(repo)?.oids?;
+    repo.graph_ahead_behind(head_oid, upstream_oid).ok()
 }
 
-#[tauri::command]
-pub(crate) async fn unstage_git_file(
-    workspace_id: String,
-    path: String,
-    state: State<'_, AppState>,
-) -> Result<(), String> {
-    let entry = {
-        let workspaces = state.workspaces.lock().await;
-        workspaces
-            .get(&workspace_id)
-            .cloned()
-            .ok_or("workspace not found")?
-    };
+fn git_status_for_repo(repo: &Repository) -> Result<serde_json::Value, String> {
+    let branch_name = current_branch_name(repo).unwrap_or_else(|| "unknown".to_string());
+    let mut files = Vec::new();
+    let mut staged_files = Vec::new();
+    let mut unstaged_files = Vec::new();
+    let mut total_additions = 0i64;
+    let mut total_deletions = 0i64;
+    visit_git_status(repo, |entry| {
+        total_additions += entry.file.additions;
+        total_deletions += entry.file.deletions;
+        files.push(entry.file);
+        staged_files.extend(entry.staged);
+        unstaged_files.extend(entry.unstaged);
+        Ok(())
+    })?;
+    let ahead_behind = upstream_ahead_behind(repo);
 
-    let repo_root = resolve_git_root(&entry)?;
-    for path in action_paths_for_file(&repo_root, &path) {
-        run_git_command(&repo_root, &["restore", "--staged", "--", &path]).await?;
-    }
-    Ok(())
+    Ok(json!({
+        "branchName": branch_name,
+        "ahead": ahead_behind.map(|(ahead, _)| ahead),
+        "behind": ahead_behind.map(|(_, behind)| behind),
+        "files": files,
+        "stagedFiles": staged_files,
+        "unstagedFiles": unstaged_files,
+        "totalAdditions": total_additions,
+        "totalDeletions": total_deletions,
+    }))
 }
 
-#[tauri::command]
-pub(crate) async fn revert_git_file(
-    workspace_id: String,
-    path: String,
-    state: State<'_, AppState>,
-) -> Result<(), String> {
-    let entry = {
-        let workspaces = state.workspaces.lock().await;
-        workspaces
-            .get(&workspace_id)
-            .cloned()
-            .ok_or("workspace not found")?
-    };
-
-    let repo_root = resolve_git_root(&entry)?;
-    for path in action_paths_for_file(&repo_root, &path) {
-        if run_git_command(
-            &repo_root,
-            &["restore", "--staged", "--worktree", "--", &path],
-        )
-        .await
-        .is_ok()
-        {
-            continue;
+fn stream_git_status_for_repo<F>(
+    repo: &Repository,
+    chunk_size: usize,
+    mut send: F,
+) -> Result<(), String>
+where
+    F: FnMut(GitStatusChunk) -> Result<(), String>,
+{
+    let mut chunk = GitStatusChunk::default();
+    let mut total_additions = 0i64;
+    let mut total_deletions = 0i64;
+    visit_git_status(repo, |entry| {
+        total_additions += entry.file.additions;
+        total_deletions += entry.file.deletions;
+        chunk.files.push(entry.file);
+        chunk.staged_files.extend(entry.staged);
+        chunk.unstaged_files.extend(entry.unstaged);
+        if chunk.files.len() >= chunk_size {
+            let mut ready = std::mem::take(&mut chunk);
+            ready.total_additions = total_additions;
+            ready.total_deletions = total_deletions;
+            send(ready)?;
         }
-        run_git_command(&repo_root, &["clean", "-f", "--", &path]).await?;
-    }
-    Ok(())
+        Ok(())
+    })?;
+
+    chunk.total_additions = total_additions;
+    chunk.total_deletions = total_deletions;
+    chunk.done = true;
+    chunk.branch_name =
+        Some(current_branch_name(repo).unwrap_or_else(|| "unknown".to_string()));
+    send(chunk)
 }
 
-#[tauri::command]
-pub(crate) async fn revert_git_all(
-    workspace_id: String,
-    state: State<'_, AppState>,
-) -> Result<(), String> {
+/// Clones the entry for a command that changes the working tree, index or refs; monitor-only
+/// workspaces are watched, not edited.
+async fn writable_workspace_entry(
+    state: &AppState,
+    workspace_id: &str,
+) -> Result<WorkspaceEntry, String> {
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
-        .get(&workspace_id)
+        .get(workspace_id)
+        .cloned()
         .ok_or("workspace not found")?;
-    let repo_root = resolve_git_root(entry)?;
-    run_git_command(&repo_root, &["restore", "--staged", "--worktree", "--", "."]).await?;
-    run_git_command(&repo_root, &["clean", "-f", "-d"]).await
+    if entry.monitor_only {
+        return Err(MONITOR_ONLY_ERROR.to_string());
+    }
+    Ok(entry)
 }
 
+/// Streams status in batches so large change sets render progressively.
 #[tauri::command]
-pub(crate) async fn commit_git(
+pub(crate) async fn stream_git_status(
     workspace_id: String,
-    message: String,
+    channel: Channel<GitStatusChunk>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     let workspaces = state.workspaces.lock().await;
@@ -657,218 +937,1932 @@ pub(crate) async fn commit_git(
         .get(&workspace_id)
         .ok_or("workspace not found")?
         .clone();
+    drop(workspaces);
 
     let repo_root = resolve_git_root(&entry)?;
-    run_git_command(&repo_root, &["commit", "-m", &message]).await
+    tokio::task::spawn_blocking(move || {
+        let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+        stream_git_status_for_repo(&repo, GIT_STATUS_CHUNK_SIZE, |chunk| {
+            channel.send(chunk).map_err(|e| e.to_string())
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
 }
 
-#[tauri::command]
-pub(crate) async fn push_git(
-    workspace_id: String,
-    state: State<'_, AppState>,
-) -> Result<(), String> {
-    let workspaces = state.workspaces.lock().await;
-    let entry = workspaces
-        .get(&workspace_id)
-        .ok_or("workspace not found")?
-        .clone();
-
-    let repo_root = resolve_git_root(&entry)?;
-    push_with_upstream(&repo_root).await
+/// Drops cached repositories that no remaining workspace resolves to, e.g. after a removal.
+pub(crate) async fn prune_repository_cache(state: &AppState) {
+    let roots: HashSet<PathBuf> = state
+        .workspaces
+        .lock()
+        .await
+        .values()
+        .filter_map(|entry| resolve_git_root(entry).ok())
+        .collect();
+    let repositories = Arc::clone(&state.repositories);
+    let _ = tokio::task::spawn_blocking(move || {
+        repositories.retain(|path| roots.contains(path));
+    })
+    .await;
 }
 
 #[tauri::command]
-pub(crate) async fn pull_git(
+pub(crate) async fn get_git_status(
     workspace_id: String,
     state: State<'_, AppState>,
-) -> Result<(), String> {
+) -> Result<serde_json::Value, GitRepoError> {
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
-        .ok_or("workspace not found")?
+        .ok_or_else(|| GitRepoError::from("workspace not found".to_string()))?
         .clone();
+    drop(workspaces);
 
     let repo_root = resolve_git_root(&entry)?;
-    run_git_command(&repo_root, &["pull"]).await
+    let repositories = Arc::clone(&state.repositories);
+    tokio::task::spawn_blocking(move || {
+        repositories.with_repository(&repo_root, git_status_for_repo)
+    })
+    .await
+    .map_err(|e| GitRepoError::from(e.to_string()))?
 }
 
-#[tauri::command]
-pub(crate) async fn sync_git(
-    workspace_id: String,
-    state: State<'_, AppState>,
-) -> Result<(), String> {
-    let workspaces = state.workspaces.lock().await;
-    let entry = workspaces
-        .get(&workspace_id)
-        .ok_or("workspace not found")?
-        .clone();
-
-    let repo_root = resolve_git_root(&entry)?;
-    // Pull first, then push (like VSCode sync)
-    run_git_command(&repo_root, &["pull"]).await?;
-    push_with_upstream(&repo_root).await
+/// Resolves a workspace-relative path that may not exist yet. Rejects absolute paths, `..`,
+/// and existing parents that resolve (via symlinks) outside the workspace.
+fn resolve_workspace_path(root: &Path, relative: &str) -> Result<PathBuf, String> {
+    let relative = Path::new(relative.trim());
+    let is_plain = relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    if !is_plain || !relative.components().any(|c| matches!(c, Component::Normal(_))) {
+        return Err("Invalid file path".to_string());
+    }
+    let canonical_root = root
+        .canonicalize()
+        .map_err(|err| format!("Failed to resolve workspace root: {err}"))?;
+    let candidate = canonical_root.join(relative);
+    let mut ancestor = candidate.parent();
+    while let Some(dir) = ancestor {
+        if dir.exists() {
+            let canonical = dir
+                .canonicalize()
+                .map_err(|err| format!("Failed to resolve path: {err}"))?;
+            if !canonical.starts_with(&canonical_root) {
+                return Err("Invalid file path".to_string());
+            }
+            break;
+        }
+        ancestor = dir.parent();
+    }
+    Ok(candidate)
+}
+
+/// Workspaces outside a repository still get file operations, just without index updates.
+fn open_optional_repository(repo_root: &Path) -> Result<Option<Repository>, String> {
+    match open_repository(repo_root) {
+        Ok(repo) => Ok(Some(repo)),
+        Err(GitRepoError::NotAGitRepo { .. }) => Ok(None),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+fn repo_relative_path(repo: &Repository, path: &Path) -> Option<String> {
+    let workdir = repo.workdir()?.canonicalize().ok()?;
+    let relative = path.strip_prefix(&workdir).ok()?;
+    Some(normalize_git_path(relative.to_string_lossy().as_ref()))
+}
+
+fn clean_path_status(path: String) -> GitPathStatus {
+    GitPathStatus {
+        path,
+        index_status: None,
+        worktree_status: None,
+        rename_from: None,
+    }
+}
+
+fn path_status_for_repo(
+    repo: &Repository,
+    path: &str,
+    related: &[&str],
+) -> Result<GitPathStatus, String> {
+    let mut result = clean_path_status(path.to_string());
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .renames_head_to_index(true)
+        .disable_pathspec_match(true)
+        .pathspec(path);
+    for other in related {
+        options.pathspec(other);
+    }
+    let statuses = repo.statuses(Some(&mut options)).map_err(|e| e.to_string())?;
+    for entry in statuses.iter() {
+        let entry_path = entry
+            .head_to_index()
+            .and_then(|delta| delta.new_file().path().map(Path::to_path_buf))
+            .map(|value| normalize_git_path(value.to_string_lossy().as_ref()))
+            .or_else(|| entry.path().map(normalize_git_path));
+        if entry_path.as_deref() != Some(path) {
+            continue;
+        }
+        let status = entry.status();
+        result.index_status = status_for_index(status).map(str::to_string);
+        result.worktree_status = status_for_workdir(status).map(str::to_string);
+        if status.contains(Status::INDEX_RENAMED) {
+            result.rename_from = entry
+                .head_to_index()
+                .and_then(rename_paths)
+                .map(|(old_path, _)| old_path);
+        }
+    }
+    Ok(result)
+}
+
+/// Renames a file like `git mv`: the staged entry moves to the new path, so the change shows
+/// up as a staged rename. Untracked sources are moved on disk only.
+fn move_workspace_file_inner(
+    workspace_root: &Path,
+    repo: Option<&Repository>,
+    from: &str,
+    to: &str,
+    force: bool,
+) -> Result<GitPathStatus, String> {
+    let from_path = resolve_workspace_path(workspace_root, from)?;
+    let to_path = resolve_workspace_path(workspace_root, to)?;
+    if from_path == to_path {
+        return Err("Source and destination are the same".to_string());
+    }
+    let metadata = fs::symlink_metadata(&from_path)
+        .map_err(|err| format!("Failed to read {from}: {err}"))?;
+    if metadata.is_dir() {
+        return Err("Path is not a file".to_string());
+    }
+    if let Ok(existing) = fs::symlink_metadata(&to_path) {
+        if existing.is_dir() {
+            return Err(format!("Destination is a directory: {to}"));
+        }
+        if !force {
+            return Err(format!("Destination already exists: {to}"));
+        }
+    }
+    if let Some(parent) = to_path.parent() {
+        fs::create_dir_all(parent).map_err(|err| format!("Failed to create directory: {err}"))?;
+    }
+    fs::rename(&from_path, &to_path).map_err(|err| format!("Failed to move file: {err}"))?;
+
+    let Some(repo) = repo else {
+        return Ok(clean_path_status(normalize_git_path(to)));
+    };
+    let (Some(old_rel), Some(new_rel)) = (
+        repo_relative_path(repo, &from_path),
+        repo_relative_path(repo, &to_path),
+    ) else {
+        return Err("File is outside the repository".to_string());
+    };
+    let mut index = repo.index().map_err(|e| e.to_string())?;
+    if let Some(mut entry) = index.get_path(Path::new(&old_rel), 0) {
+        index
+            .remove_path(Path::new(&old_rel))
+            .map_err(|e| e.to_string())?;
+        entry.path = new_rel.clone().into_bytes();
+        index.add(&entry).map_err(|e| e.to_string())?;
+        index.write().map_err(|e| e.to_string())?;
+    }
+    path_status_for_repo(repo, &new_rel, &[&old_rel])
+}
+
+/// Writes a new file through a temp sibling and rename, so readers never see partial content.
+fn create_workspace_file_inner(
+    workspace_root: &Path,
+    repo: Option<&Repository>,
+    path: &str,
+    content: &str,
+    overwrite: bool,
+) -> Result<GitPathStatus, String> {
+    let target = resolve_workspace_path(workspace_root, path)?;
+    if let Ok(existing) = fs::symlink_metadata(&target) {
+        if existing.is_dir() {
+            return Err(format!("Path is a directory: {path}"));
+        }
+        if !overwrite {
+            return Err(format!("File already exists: {path}"));
+        }
+    }
+    let parent = target
+        .parent()
+        .ok_or_else(|| "Invalid file path".to_string())?;
+    fs::create_dir_all(parent).map_err(|err| format!("Failed to create directory: {err}"))?;
+    let file_name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let temp_path = parent.join(format!(".{file_name}.{}.tmp", uuid::Uuid::new_v4()));
+    fs::write(&temp_path, content).map_err(|err| format!("Failed to write file: {err}"))?;
+    if let Err(err) = fs::rename(&temp_path, &target) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(format!("Failed to write file: {err}"));
+    }
+
+    let Some(repo) = repo else {
+        return Ok(clean_path_status(normalize_git_path(path)));
+    };
+    let relative = repo_relative_path(repo, &target)
+        .ok_or_else(|| "File is outside the repository".to_string())?;
+    path_status_for_repo(repo, &relative, &[])
+}
+
+/// True when deleting `target` would remove git metadata: a `.git` path component (matched
+/// case-insensitively, since macOS and Windows file systems are), the repository's git
+/// directory or a directory containing it.
+fn touches_git_metadata(target: &Path, relative: &str, repo: Option<&Repository>) -> bool {
+    let has_git_component = Path::new(relative.trim())
+        .components()
+        .any(|component| component.as_os_str().eq_ignore_ascii_case(".git"));
+    if has_git_component {
+        return true;
+    }
+    let Some(repo) = repo else {
+        return false;
+    };
+    let (Ok(git_dir), Some(parent), Some(name)) = (
+        repo.path().canonicalize(),
+        target.parent().and_then(|parent| parent.canonicalize().ok()),
+        target.file_name(),
+    ) else {
+        return false;
+    };
+    let target = parent.join(name);
+    git_dir.starts_with(&target) || target.starts_with(&git_dir)
+}
+
+/// Deletes a file (or, with `recursive`, a directory) and optionally stages the deletion.
+/// A removed directory reports a single "D" when it held tracked files.
+fn delete_workspace_file_inner(
+    workspace_root: &Path,
+    repo: Option<&Repository>,
+    path: &str,
+    stage: bool,
+    recursive: bool,
+) -> Result<GitPathStatus, String> {
+    let target = resolve_workspace_path(workspace_root, path)?;
+    if touches_git_metadata(&target, path, repo) {
+        return Err("Refusing to delete git metadata".to_string());
+    }
+    let metadata =
+        fs::symlink_metadata(&target).map_err(|err| format!("Failed to read {path}: {err}"))?;
+    let is_dir = metadata.is_dir();
+    if is_dir && !recursive {
+        return Err(format!("Path is a directory: {path}"));
+    }
+    let relative = match repo {
+        Some(repo) => Some(
+            repo_relative_path(repo, &target)
+                .ok_or_else(|| "File is outside the repository".to_string())?,
+        ),
+        None => None,
+    };
+    if is_dir {
+        fs::remove_dir_all(&target).map_err(|err| format!("Failed to delete directory: {err}"))?;
+    } else {
+        fs::remove_file(&target).map_err(|err| format!("Failed to delete file: {err}"))?;
+    }
+
+    let (Some(repo), Some(relative)) = (repo, relative) else {
+        return Ok(clean_path_status(normalize_git_path(path)));
+    };
+    let mut index = repo.index().map_err(|e| e.to_string())?;
+    let prefix = format!("{relative}/");
+    let had_index_entries = index
+        .iter()
+        .any(|entry| entry.path.starts_with(prefix.as_bytes()));
+    if stage {
+        let removed = if is_dir {
+            index.remove_dir(Path::new(&relative), 0)
+        } else {
+            index.remove_path(Path::new(&relative))
+        };
+        removed.map_err(|e| e.to_string())?;
+        index.write().map_err(|e| e.to_string())?;
+    }
+    if !is_dir {
+        return path_status_for_repo(repo, &relative, &[]);
+    }
+    let in_head = head_tree(repo).is_some_and(|tree| tree.get_path(Path::new(&relative)).is_ok());
+    let mut status = clean_path_status(relative);
+    if stage && in_head {
+        status.index_status = Some("D".to_string());
+    } else if !stage && had_index_entries {
+        status.worktree_status = Some("D".to_string());
+    }
+    Ok(status)
+}
+
+#[tauri::command]
+pub(crate) async fn delete_workspace_file(
+    workspace_id: String,
+    path: String,
+    stage: Option<bool>,
+    recursive: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<GitPathStatus, String> {
+    let entry = writable_workspace_entry(&state, &workspace_id).await?;
+
+    let workspace_root = PathBuf::from(&entry.path);
+    let repo_root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || {
+        let repo = open_optional_repository(&repo_root)?;
+        delete_workspace_file_inner(
+            &workspace_root,
+            repo.as_ref(),
+            &path,
+            stage.unwrap_or(false),
+            recursive.unwrap_or(false),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub(crate) async fn create_workspace_file(
+    workspace_id: String,
+    path: String,
+    content: String,
+    overwrite: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<GitPathStatus, String> {
+    let entry = writable_workspace_entry(&state, &workspace_id).await?;
+
+    let workspace_root = PathBuf::from(&entry.path);
+    let repo_root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || {
+        let repo = open_optional_repository(&repo_root)?;
+        create_workspace_file_inner(
+            &workspace_root,
+            repo.as_ref(),
+            &path,
+            &content,
+            overwrite.unwrap_or(false),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub(crate) async fn move_workspace_file(
+    workspace_id: String,
+    from: String,
+    to: String,
+    force: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<GitPathStatus, String> {
+    let entry = writable_workspace_entry(&state, &workspace_id).await?;
+
+    let workspace_root = PathBuf::from(&entry.path);
+    let repo_root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || {
+        let repo = open_optional_repository(&repo_root)?;
+        move_workspace_file_inner(
+            &workspace_root,
+            repo.as_ref(),
+            &from,
+            &to,
+            force.unwrap_or(false),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn index_path(repo: &Repository, path: &str) -> Result<String, String> {
+    if repo.is_bare() {
+        return Err("Cannot stage files in a bare repository".to_string());
+    }
+    let path = normalize_git_path(path.trim());
+    let path = path.trim_start_matches("./").to_string();
+    if path.is_empty() {
+        return Err("Missing file path".to_string());
+    }
+    Ok(path)
+}
+
+/// Stages one path through libgit2: files still on disk are added, missing ones are staged
+/// as deletions.
+fn stage_path_for_repo(repo: &Repository, path: &str) -> Result<GitPathStatus, String> {
+    let path = index_path(repo, path)?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| "Cannot stage files in a bare repository".to_string())?;
+    let mut index = repo.index().map_err(|e| e.to_string())?;
+    if fs::symlink_metadata(workdir.join(&path)).is_ok() {
+        index
+            .add_path(Path::new(&path))
+            .map_err(|e| format!("Failed to stage {path}: {e}"))?;
+    } else {
+        index
+            .remove_path(Path::new(&path))
+            .map_err(|e| format!("Failed to stage deletion of {path}: {e}"))?;
+    }
+    index.write().map_err(|e| e.to_string())?;
+    path_status_for_repo(repo, &path, &[])
+}
+
+/// Resets the path's index entry to HEAD, or drops it when HEAD does not have the file.
+fn unstage_path_for_repo(repo: &Repository, path: &str) -> Result<GitPathStatus, String> {
+    let path = index_path(repo, path)?;
+    let head = if head_is_unborn(repo) {
+        None
+    } else {
+        let commit = repo
+            .head()
+            .and_then(|head| head.peel(git2::ObjectType::Commit))
+            .map_err(|e| format!("Failed to resolve HEAD: {e}"))?;
+        Some(commit)
+    };
+    repo.reset_default(head.as_ref(), [path.as_str()])
+        .map_err(|e| format!("Failed to unstage {path}: {e}"))?;
+    path_status_for_repo(repo, &path, &[])
+}
+
+/// Applies `update` to the file and, when libgit2 reports it as half of a rename, to the
+/// other side too so one UI action moves the whole change. Returns the file's new status.
+fn update_file_in_index(
+    repo_root: &Path,
+    path: &str,
+    update: fn(&Repository, &str) -> Result<GitPathStatus, String>,
+) -> Result<GitPathStatus, String> {
+    let repo = open_repository(repo_root).map_err(|e| e.to_string())?;
+    let target = index_path(&repo, path)?;
+    for action_path in action_paths_for_file(repo_root, &target) {
+        update(&repo, &action_path)?;
+    }
+    path_status_for_repo(&repo, &target, &[])
+}
+
+#[tauri::command]
+pub(crate) async fn stage_git_file(
+    workspace_id: String,
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<GitPathStatus, String> {
+    let entry = writable_workspace_entry(&state, &workspace_id).await?;
+
+    let repo_root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || {
+        update_file_in_index(&repo_root, &path, stage_path_for_repo)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub(crate) async fn stage_git_all(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let entry = writable_workspace_entry(&state, &workspace_id).await?;
+
+    let repo_root = resolve_git_root(&entry)?;
+    run_git_command(&repo_root, &["add", "-A"]).await
+}
+
+#[tauri::command]
+pub(crate) async fn unstage_git_file(
+    workspace_id: String,
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<GitPathStatus, String> {
+    let entry = writable_workspace_entry(&state, &workspace_id).await?;
+
+    let repo_root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || {
+        update_file_in_index(&repo_root, &path, unstage_path_for_repo)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub(crate) async fn revert_git_file(
+    workspace_id: String,
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let entry = writable_workspace_entry(&state, &workspace_id).await?;
+
+    let repo_root = resolve_git_root(&entry)?;
+    for path in action_paths_for_file(&repo_root, &path) {
+        if run_git_command(
+            &repo_root,
+            &["restore", "--staged", "--worktree", "--", &path],
+        )
+        .await
+        .is_ok()
+        {
+            continue;
+        }
+        run_git_command(&repo_root, &["clean", "-f", "--", &path]).await?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) async fn revert_git_all(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let entry = writable_workspace_entry(&state, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    run_git_command(&repo_root, &["restore", "--staged", "--worktree", "--", "."]).await?;
+    run_git_command(&repo_root, &["clean", "-f", "-d"]).await
+}
+
+fn commit_command_args(message: &str, settings: &WorkspaceSettings) -> Vec<String> {
+    let mut args = Vec::new();
+    if settings.git_sign_commits {
+        let signing_key = settings
+            .git_signing_key
+            .as_deref()
+            .map(str::trim)
+            .filter(|key| !key.is_empty());
+        if let Some(key) = signing_key {
+            args.push("-c".to_string());
+            args.push(format!("user.signingKey={key}"));
+        }
+        args.push("commit".to_string());
+        args.push("-S".to_string());
+    } else {
+        args.push("commit".to_string());
+    }
+    args.push("-m".to_string());
+    args.push(message.to_string());
+    args
+}
+
+/// Output from gpg, or from ssh-keygen for `gpg.format = ssh`, when it cannot sign.
+const SIGNER_FAILURE_MARKERS: [&str; 8] = [
+    "gpg failed to sign the data",
+    "cannot run gpg",
+    "no secret key",
+    "ssh-keygen",
+    "couldn't load public key",
+    "couldn't find key in agent",
+    "no private key found",
+    "load key",
+];
+
+/// git reports signer failures as a failure to write the commit object; surface
+/// them as a key problem so it's clear nothing was committed unsigned. Other commit
+/// failures, such as a hook rejecting the commit, pass through unchanged.
+fn signing_error_message(detail: &str) -> Option<String> {
+    let lower = detail.to_ascii_lowercase();
+    SIGNER_FAILURE_MARKERS
+        .iter()
+        .any(|marker| lower.contains(marker))
+        .then(|| format!("signing key not available: {detail}"))
+}
+
+/// Runs `git commit` with the workspace's signing settings so `commit.gpgsign`, the
+/// configured signing key and hooks all apply, returning the new HEAD commit.
+async fn commit_with_git_cli(
+    entry: &WorkspaceEntry,
+    repo_root: &Path,
+    message: &str,
+) -> Result<GitLogEntry, String> {
+    let args = commit_command_args(message, &entry.settings);
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    run_git_command(repo_root, &args).await.map_err(|detail| {
+        if entry.settings.git_sign_commits {
+            signing_error_message(&detail).unwrap_or(detail)
+        } else {
+            detail
+        }
+    })?;
+    let repo_root = repo_root.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+        repo.head()
+            .and_then(|head| head.peel_to_commit())
+            .map(commit_to_entry)
+            .map_err(|e| format!("Failed to resolve HEAD after commit: {e}"))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// What to stage before committing.
+enum CommitStaging {
+    /// Commit the index as it is.
+    Index,
+    /// Stage these paths first.
+    Paths(Vec<String>),
+    /// Stage every change to tracked files first, like `git add -u`.
+    Tracked,
+}
+
+/// Stages, validates and commits through `git commit`, then pushes when the workspace has
+/// `git_push_after_commit` set. A failed push still reports the commit that was made.
+async fn commit_and_push(
+    entry: &WorkspaceEntry,
+    repo_root: &Path,
+    message: &str,
+    staging: CommitStaging,
+) -> Result<GitLogEntry, GitCommitError> {
+    let commit_failed = |message: String| GitCommitError::CommitFailed { message };
+    let message = message.trim().to_string();
+    let prepare_root = repo_root.to_path_buf();
+    let prepare_message = message.clone();
+    tokio::task::spawn_blocking(move || {
+        let repo = open_repository(&prepare_root).map_err(|e| e.to_string())?;
+        prepare_commit_for_repo(&repo, &prepare_message, &staging)
+    })
+    .await
+    .map_err(|e| commit_failed(e.to_string()))?
+    .map_err(commit_failed)?;
+    let commit = commit_with_git_cli(entry, repo_root, &message)
+        .await
+        .map_err(commit_failed)?;
+
+    if !entry.settings.git_push_after_commit {
+        return Ok(commit);
+    }
+    let remote_name = entry.settings.git_push_remote_name.trim().to_string();
+    let remote_name = if remote_name.is_empty() {
+        "origin".to_string()
+    } else {
+        remote_name
+    };
+    let push_root = repo_root.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let repo = open_repository(&push_root).map_err(|e| commit_failed(e.to_string()))?;
+        git_push(&repo, &remote_name).map_err(|e| GitCommitError::CommitOkPushFailed {
+            sha: commit.sha.clone(),
+            message: e.message().to_string(),
+        })?;
+        Ok(commit)
+    })
+    .await
+    .map_err(|e| commit_failed(e.to_string()))?
+}
+
+/// Commits `paths`, or every change to tracked files when `paths` is `None`.
+#[tauri::command]
+pub(crate) async fn commit_git(
+    workspace_id: String,
+    message: String,
+    paths: Option<Vec<String>>,
+    state: State<'_, AppState>,
+) -> Result<GitLogEntry, GitCommitError> {
+    let commit_failed = |message: String| GitCommitError::CommitFailed { message };
+    let entry = writable_workspace_entry(&state, &workspace_id)
+        .await
+        .map_err(commit_failed)?;
+
+    let repo_root = resolve_git_root(&entry).map_err(commit_failed)?;
+    let staging = match paths {
+        Some(paths) => CommitStaging::Paths(paths),
+        None => CommitStaging::Tracked,
+    };
+    commit_and_push(&entry, &repo_root, &message, staging).await
+}
+
+fn repo_signature(repo: &Repository) -> Result<git2::Signature<'static>, String> {
+    repo.signature().map_err(|e| {
+        if e.code() == git2::ErrorCode::NotFound {
+            "Git author identity is not configured. Set it with \
+             `git config user.name \"Your Name\"` and \
+             `git config user.email \"you@example.com\"`."
+                .to_string()
+        } else {
+            e.to_string()
+        }
+    })
+}
+
+const MAX_COMMIT_SUBJECT_CHARS: usize = 72;
+
+/// Stages per `staging`, then rejects commits git would refuse or that would come out
+/// empty, with messages the UI can show as-is; the commit itself is left to `git commit`.
+fn prepare_commit_for_repo(
+    repo: &Repository,
+    message: &str,
+    staging: &CommitStaging,
+) -> Result<(), String> {
+    let subject = message.trim().lines().next().unwrap_or("");
+    if subject.trim().is_empty() {
+        return Err("Commit message cannot be empty".to_string());
+    }
+    if subject.chars().count() > MAX_COMMIT_SUBJECT_CHARS {
+        return Err(format!(
+            "Commit summary must be at most {MAX_COMMIT_SUBJECT_CHARS} characters"
+        ));
+    }
+    // Checked before staging, since staging a conflicted path would mark it resolved.
+    if repo.index().map_err(|e| e.to_string())?.has_conflicts() {
+        return Err("Resolve merge conflicts before committing".to_string());
+    }
+    match staging {
+        CommitStaging::Index => {}
+        CommitStaging::Paths(paths) => {
+            for path in paths {
+                stage_path_for_repo(repo, path)?;
+            }
+        }
+        CommitStaging::Tracked => {
+            let mut index = repo.index().map_err(|e| e.to_string())?;
+            index
+                .update_all(["*"], None)
+                .map_err(|e| format!("Failed to stage tracked changes: {e}"))?;
+            index.write().map_err(|e| e.to_string())?;
+        }
+    }
+    let index = repo.index().map_err(|e| e.to_string())?;
+    let unchanged = if head_is_unborn(repo) {
+        index.is_empty()
+    } else {
+        let head_tree = repo
+            .head()
+            .and_then(|head| head.peel_to_tree())
+            .map_err(|e| format!("Failed to resolve HEAD: {e}"))?;
+        let diff = repo
+            .diff_tree_to_index(Some(&head_tree), Some(&index), None)
+            .map_err(|e| e.to_string())?;
+        diff.deltas().len() == 0
+    };
+    if unchanged {
+        return Err("Nothing staged to commit".to_string());
+    }
+    repo_signature(repo).map(|_| ())
+}
+
+/// Commits the index as staged, returning the new commit's sha.
+#[tauri::command]
+pub(crate) async fn create_git_commit(
+    workspace_id: String,
+    message: String,
+    state: State<'_, AppState>,
+) -> Result<String, GitCommitError> {
+    let commit_failed = |message: String| GitCommitError::CommitFailed { message };
+    let entry = writable_workspace_entry(&state, &workspace_id)
+        .await
+        .map_err(commit_failed)?;
+
+    let repo_root = resolve_git_root(&entry).map_err(commit_failed)?;
+    let commit = commit_and_push(&entry, &repo_root, &message, CommitStaging::Index).await?;
+    Ok(commit.sha)
+}
+
+#[tauri::command]
+pub(crate) async fn push_git(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let entry = writable_workspace_entry(&state, &workspace_id).await?;
+
+    let repo_root = resolve_git_root(&entry)?;
+    push_with_upstream(&repo_root).await
+}
+
+#[tauri::command]
+pub(crate) async fn pull_git(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let entry = writable_workspace_entry(&state, &workspace_id).await?;
+
+    let repo_root = resolve_git_root(&entry)?;
+    run_git_command(&repo_root, &["pull"]).await
+}
+
+#[tauri::command]
+pub(crate) async fn sync_git(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let entry = writable_workspace_entry(&state, &workspace_id).await?;
+
+    let repo_root = resolve_git_root(&entry)?;
+    // Pull first, then push (like VSCode sync)
+    run_git_command(&repo_root, &["pull"]).await?;
+    push_with_upstream(&repo_root).await
+}
+
+#[tauri::command]
+pub(crate) async fn list_git_roots(
+    workspace_id: String,
+    depth: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+
+    let root = PathBuf::from(&entry.path);
+    let depth = depth.unwrap_or(2).clamp(1, 6);
+    Ok(scan_git_roots(&root, depth, 200))
+}
+
+/// Helper function to get the combined diff for a workspace (used by commit message generation)
+pub(crate) async fn get_workspace_diff(
+    workspace_id: &str,
+    state: &State<'_, AppState>,
+) -> Result<String, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    collect_workspace_diff(&repo_root)
+}
+
+/// The stored blob for one side of a delta. Tree and index sides always have one; a
+/// working-tree side usually doesn't and is read from disk instead.
+fn delta_blob<'r>(repo: &'r Repository, file: &git2::DiffFile) -> Option<git2::Blob<'r>> {
+    let id = file.id();
+    if id.is_zero() {
+        return None;
+    }
+    repo.find_blob(id).ok()
+}
+
+/// Per-file diffs for `diff`, whichever trees, index or working tree it compared. Image
+/// sides carry base64 data instead of a patch; `repo_root` is where working-tree sides
+/// without a stored blob are read from.
+fn file_diffs_for_diff(repo: &Repository, repo_root: &Path, diff: &git2::Diff) -> Vec<GitFileDiff> {
+    let mut results = Vec::new();
+    for (index, delta) in diff.deltas().enumerate() {
+        let old_path = delta.old_file().path();
+        let new_path = delta.new_file().path();
+        let display_path = new_path.or(old_path);
+        let Some(display_path) = display_path else {
+            continue;
+        };
+        let old_path_str = old_path.map(|path| path.to_string_lossy());
+        let new_path_str = new_path.map(|path| path.to_string_lossy());
+        let display_path_str = display_path.to_string_lossy();
+        let normalized_path = normalize_git_path(&display_path_str);
+        let old_image_mime = old_path_str.as_deref().and_then(image_mime_type);
+        let new_image_mime = new_path_str.as_deref().and_then(image_mime_type);
+        let is_image = old_image_mime.is_some() || new_image_mime.is_some();
+
+        if is_image {
+            let is_deleted = delta.status() == git2::Delta::Deleted;
+            let is_added = delta.status() == git2::Delta::Added;
+
+            // Each side comes from whatever the diff compared: HEAD or a branch tree, the
+            // index, or the working tree.
+            let old_image_data = if !is_added && old_image_mime.is_some() {
+                delta_blob(repo, &delta.old_file()).and_then(blob_to_base64)
+            } else {
+                None
+            };
+
+            let new_image_data = if !is_deleted && new_image_mime.is_some() {
+                match delta_blob(repo, &delta.new_file()) {
+                    Some(blob) => blob_to_base64(blob),
+                    None => new_path.and_then(|path| read_image_base64(&repo_root.join(path))),
+                }
+            } else {
+                None
+            };
+
+            results.push(GitFileDiff {
+                path: normalized_path,
+                diff: String::new(),
+                is_binary: true,
+                is_image: true,
+                old_image_data,
+                new_image_data,
+                old_image_mime: old_image_mime.map(str::to_string),
+                new_image_mime: new_image_mime.map(str::to_string),
+                mode: None,
+            });
+            continue;
+        }
+
+        let patch = match git2::Patch::from_diff(diff, index) {
+            Ok(patch) => patch,
+            Err(_) => continue,
+        };
+        // libgit2 only settles the binary flag once the patch has loaded the content.
+        if delta.flags().is_binary() {
+            results.push(GitFileDiff {
+                path: normalized_path,
+                diff: String::new(),
+                is_binary: true,
+                is_image: false,
+                old_image_data: None,
+                new_image_data: None,
+                old_image_mime: None,
+                new_image_mime: None,
+                mode: None,
+            });
+            continue;
+        }
+        let Some(mut patch) = patch else {
+            continue;
+        };
+        let content = match diff_patch_to_string(&mut patch) {
+            Ok(content) => content,
+            Err(_) => continue,
+        };
+        if content.trim().is_empty() {
+            continue;
+        }
+        results.push(GitFileDiff {
+            path: normalized_path,
+            diff: content,
+            is_binary: false,
+            is_image: false,
+            old_image_data: None,
+            new_image_data: None,
+            old_image_mime: None,
+            new_image_mime: None,
+            mode: None,
+        });
+    }
+
+    results
+}
+
+/// Unified-diff context used when `get_git_diffs` is not given `context_lines`; git's default.
+const DEFAULT_DIFF_CONTEXT_LINES: u32 = 3;
+
+fn working_diffs_for_repo(
+    repo: &Repository,
+    repo_root: &Path,
+    mode: GitDiffMode,
+    context_lines: u32,
+) -> Result<Vec<GitFileDiff>, String> {
+    let head_tree = head_tree(repo);
+    let mut options = DiffOptions::new();
+    options
+        .context_lines(context_lines)
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true);
+    let diff = match mode {
+        GitDiffMode::Staged => {
+            repo.diff_tree_to_index(head_tree.as_ref(), None, Some(&mut options))
+        }
+        GitDiffMode::Unstaged => repo.diff_index_to_workdir(None, Some(&mut options)),
+        GitDiffMode::Combined => {
+            repo.diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut options))
+        }
+    }
+    .map_err(|e| e.to_string())?;
+    let mut diffs = file_diffs_for_diff(repo, repo_root, &diff);
+    for diff in &mut diffs {
+        diff.mode = Some(mode);
+    }
+    Ok(diffs)
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_diffs(
+    workspace_id: String,
+    mode: Option<GitDiffMode>,
+    context_lines: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitFileDiff>, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let context_lines = context_lines.unwrap_or(DEFAULT_DIFF_CONTEXT_LINES);
+    tokio::task::spawn_blocking(move || {
+        let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+        working_diffs_for_repo(&repo, &repo_root, mode.unwrap_or_default(), context_lines)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn diff_hunk_page_for_repo(
+    repo: &Repository,
+    path: &str,
+    cursor: usize,
+    limit: usize,
+) -> Result<GitDiffHunkPage, String> {
+    let mut options = DiffOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true)
+        .disable_pathspec_match(true)
+        .pathspec(path);
+    let tree = head_tree(repo);
+    let diff = repo
+        .diff_tree_to_workdir_with_index(tree.as_ref(), Some(&mut options))
+        .map_err(|e| e.to_string())?;
+    let patch = match diff.deltas().len() {
+        0 => None,
+        _ => git2::Patch::from_diff(&diff, 0).map_err(|e| e.to_string())?,
+    };
+    let Some(patch) = patch else {
+        return Ok(GitDiffHunkPage {
+            path: path.to_string(),
+            hunks: Vec::new(),
+            total_hunks: 0,
+            next_hunk_cursor: None,
+        });
+    };
+    let (hunks, next_hunk_cursor) =
+        diff_patch_hunks(&patch, cursor, limit).map_err(|e| e.to_string())?;
+    Ok(GitDiffHunkPage {
+        path: path.to_string(),
+        hunks,
+        total_hunks: patch.num_hunks(),
+        next_hunk_cursor,
+    })
+}
+
+/// One file's HEAD-to-working-tree diff, with staged and unstaged changes combined.
+fn file_diff_for_repo(
+    repo: &Repository,
+    repo_root: &Path,
+    path: &str,
+) -> Result<GitFileDiff, String> {
+    let path = normalize_git_path(path.trim());
+    let path = path.trim_start_matches("./").to_string();
+    if path.is_empty() {
+        return Err("Missing file path".to_string());
+    }
+    let head_tree = head_tree(repo);
+    let mut options = DiffOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true)
+        .disable_pathspec_match(true)
+        .pathspec(&path);
+    let diff = repo
+        .diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut options))
+        .map_err(|e| e.to_string())?;
+    let mut file_diff = file_diffs_for_diff(repo, repo_root, &diff)
+        .into_iter()
+        .find(|diff| diff.path == path)
+        .ok_or_else(|| "file not in working tree changes".to_string())?;
+    file_diff.mode = Some(GitDiffMode::Combined);
+    Ok(file_diff)
+}
+
+/// Working tree (with index) against the tip of a local or remote-tracking branch.
+fn branch_diffs_for_repo(
+    repo: &Repository,
+    repo_root: &Path,
+    branch: &str,
+) -> Result<Vec<GitFileDiff>, String> {
+    let branch = branch.trim();
+    let reference = repo
+        .find_branch(branch, BranchType::Local)
+        .or_else(|_| repo.find_branch(branch, BranchType::Remote))
+        .map_err(|_| format!("Branch not found: {branch}"))?;
+    let tree = reference.get().peel_to_tree().map_err(|e| e.to_string())?;
+
+    let mut options = DiffOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true);
+    let diff = repo
+        .diff_tree_to_workdir_with_index(Some(&tree), Some(&mut options))
+        .map_err(|e| e.to_string())?;
+    Ok(file_diffs_for_diff(repo, repo_root, &diff))
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_file_diff(
+    workspace_id: String,
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<GitFileDiff, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || {
+        let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+        file_diff_for_repo(&repo, &repo_root, &path)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub(crate) async fn diff_against_branch(
+    workspace_id: String,
+    branch: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitFileDiff>, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || {
+        let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+        branch_diffs_for_repo(&repo, &repo_root, &branch)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Stable patch-id of everything that differs from HEAD, untracked files included, so identical
+/// edits yield the same id across reruns. `None` means there are no changes.
+fn working_patch_id_for_repo(repo: &Repository) -> Result<Option<String>, String> {
+    let head_tree = head_tree(repo);
+    let mut options = DiffOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true);
+    let diff = repo
+        .diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut options))
+        .map_err(|e| e.to_string())?;
+    if diff.deltas().len() == 0 {
+        return Ok(None);
+    }
+    let patch_id = diff.patchid(None).map_err(|e| e.to_string())?;
+    Ok(Some(patch_id.to_string()))
+}
+
+#[tauri::command]
+pub(crate) async fn get_working_patch_id(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || {
+        let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+        working_patch_id_for_repo(&repo)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Pages through one file's working-tree diff hunk by hunk, for files too large to render
+/// from a single `get_git_diffs` patch string.
+#[tauri::command]
+pub(crate) async fn get_git_diff_for_file(
+    workspace_id: String,
+    path: String,
+    hunk_cursor: Option<usize>,
+    hunk_limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<GitDiffHunkPage, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let limit = hunk_limit
+        .unwrap_or(DEFAULT_HUNK_PAGE_SIZE)
+        .clamp(1, MAX_HUNK_PAGE_SIZE);
+    let path = normalize_git_path(&path);
+    let repo_root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || {
+        let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+        diff_hunk_page_for_repo(&repo, &path, hunk_cursor.unwrap_or(0), limit)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// HEAD-to-index deltas only; skips the patch and line stats that `get_git_status` computes.
+fn staged_files_for_repo(repo: &Repository) -> Result<Vec<GitStagedFile>, String> {
+    let head_tree = head_tree(repo);
+    let index = repo.index().map_err(|e| e.to_string())?;
+    let mut diff = repo
+        .diff_tree_to_index(head_tree.as_ref(), Some(&index), None)
+        .map_err(|e| e.to_string())?;
+    let mut find_options = git2::DiffFindOptions::new();
+    find_options.renames(true);
+    diff.find_similar(Some(&mut find_options)).map_err(|e| e.to_string())?;
+
+    let mut files = Vec::new();
+    for delta in diff.deltas() {
+        let Some(path) = delta.new_file().path().or_else(|| delta.old_file().path()) else {
+            continue;
+        };
+        let status = status_for_delta(delta.status());
+        let rename_from = if status == "R" {
+            rename_paths(delta).map(|(old_path, _)| old_path)
+        } else {
+            None
+        };
+        files.push(GitStagedFile {
+            path: normalize_git_path(path.to_string_lossy().as_ref()),
+            status: status.to_string(),
+            rename_from,
+        });
+    }
+    Ok(files)
+}
+
+#[tauri::command]
+pub(crate) async fn list_staged_files(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitStagedFile>, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || {
+        let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+        staged_files_for_repo(&repo)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn revision_tree<'repo>(repo: &'repo Repository, rev: &str) -> Result<git2::Tree<'repo>, String> {
+    repo.revparse_single(rev.trim())
+        .and_then(|object| object.peel_to_tree())
+        .map_err(|err| format!("Unknown revision {rev}: {}", err.message()))
+}
+
+/// Tree-to-tree deltas between `base` and `head`, with no patches generated.
+fn changed_files_between_for_repo(
+    repo: &Repository,
+    base: &str,
+    head: &str,
+) -> Result<Vec<GitChangedFile>, String> {
+    let base_tree = revision_tree(repo, base)?;
+    let head_tree = revision_tree(repo, head)?;
+    let mut diff = repo
+        .diff_tree_to_tree(Some(&base_tree), Some(&head_tree), None)
+        .map_err(|e| e.to_string())?;
+    let mut find_options = git2::DiffFindOptions::new();
+    find_options.renames(true);
+    diff.find_similar(Some(&mut find_options)).map_err(|e| e.to_string())?;
+
+    let mut files = Vec::new();
+    for delta in diff.deltas() {
+        let Some(path) = delta.new_file().path().or_else(|| delta.old_file().path()) else {
+            continue;
+        };
+        files.push(GitChangedFile {
+            path: normalize_git_path(path.to_string_lossy().as_ref()),
+            status: status_for_delta(delta.status()).to_string(),
+        });
+    }
+    Ok(files)
 }
 
 #[tauri::command]
-pub(crate) async fn list_git_roots(
+pub(crate) async fn changed_files_between(
     workspace_id: String,
-    depth: Option<usize>,
+    base: String,
+    head: String,
     state: State<'_, AppState>,
-) -> Result<Vec<String>, String> {
+) -> Result<Vec<GitChangedFile>, String> {
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
         .ok_or("workspace not found")?
         .clone();
+    drop(workspaces);
 
-    let root = PathBuf::from(&entry.path);
-    let depth = depth.unwrap_or(2).clamp(1, 6);
-    Ok(scan_git_roots(&root, depth, 200))
+    let repo_root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || {
+        let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+        changed_files_between_for_repo(&repo, &base, &head)
+    })
+    .await
+    .map_err(|e| e.to_string())?
 }
 
-/// Helper function to get the combined diff for a workspace (used by commit message generation)
-pub(crate) async fn get_workspace_diff(
-    workspace_id: &str,
-    state: &State<'_, AppState>,
-) -> Result<String, String> {
+#[tauri::command]
+pub(crate) async fn get_git_log(
+    workspace_id: String,
+    limit: Option<usize>,
+    cursor: Option<String>,
+    count_total: Option<bool>,
+    path: Option<String>,
+    author: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<GitLogResponse, String> {
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
-        .get(workspace_id)
+        .get(&workspace_id)
         .ok_or("workspace not found")?
         .clone();
     drop(workspaces);
 
+    let max_items = resolve_git_log_limit(limit)?;
     let repo_root = resolve_git_root(&entry)?;
-    collect_workspace_diff(&repo_root)
+    tokio::task::spawn_blocking(move || {
+        let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+        git_log_for_repo(
+            &repo,
+            max_items,
+            cursor.as_deref(),
+            count_total.unwrap_or(false),
+            &GitLogFilter::new(path, author),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn head_info_for_repo(repo: &Repository) -> Result<Option<GitLogEntry>, String> {
+    if head_is_unborn(repo) {
+        return Ok(None);
+    }
+    let commit = repo
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .map_err(|e| e.to_string())?;
+    Ok(Some(commit_to_entry(commit)))
 }
 
 #[tauri::command]
-pub(crate) async fn get_git_diffs(
+pub(crate) async fn get_head_info(
     workspace_id: String,
     state: State<'_, AppState>,
-) -> Result<Vec<GitFileDiff>, String> {
+) -> Result<Option<GitLogEntry>, String> {
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
         .ok_or("workspace not found")?
         .clone();
+    drop(workspaces);
 
     let repo_root = resolve_git_root(&entry)?;
-    tokio::task::spawn_blocking(move || {
-        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
-        let head_tree = repo
-            .head()
+    let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+    head_info_for_repo(&repo)
+}
+
+fn signature_info(signature: git2::Signature) -> GitSignatureInfo {
+    GitSignatureInfo {
+        name: signature.name().unwrap_or("").to_string(),
+        email: signature.email().unwrap_or("").to_string(),
+        timestamp: signature.when().seconds(),
+    }
+}
+
+fn commit_details_for_repo(repo: &Repository, sha: &str) -> Result<GitCommitDetails, String> {
+    let oid = git2::Oid::from_str(sha).map_err(|e| e.to_string())?;
+    let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+    let commit_tree = commit.tree().map_err(|e| e.to_string())?;
+    let parent_tree = commit
+        .parent(0)
+        .ok()
+        .and_then(|parent| parent.tree().ok());
+    let diff = repo
+        .diff_tree_to_tree(parent_tree.as_ref(), Some(&commit_tree), None)
+        .map_err(|e| e.to_string())?;
+    let stats = diff.stats().map_err(|e| e.to_string())?;
+
+    Ok(GitCommitDetails {
+        sha: commit.id().to_string(),
+        summary: commit.summary().unwrap_or("").to_string(),
+        message: commit.message().unwrap_or("").to_string(),
+        author: signature_info(commit.author()),
+        committer: signature_info(commit.committer()),
+        parents: commit.parent_ids().map(|id| id.to_string()).collect(),
+        files_changed: stats.files_changed(),
+        insertions: stats.insertions(),
+        deletions: stats.deletions(),
+    })
+}
+
+fn commit_file_stats_for_repo(
+    repo: &Repository,
+    sha: &str,
+) -> Result<Vec<GitCommitFileStat>, String> {
+    let oid = git2::Oid::from_str(sha).map_err(|e| e.to_string())?;
+    let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+    let commit_tree = commit.tree().map_err(|e| e.to_string())?;
+    let parent_tree = commit
+        .parent(0)
+        .ok()
+        .and_then(|parent| parent.tree().ok());
+    let diff = repo
+        .diff_tree_to_tree(parent_tree.as_ref(), Some(&commit_tree), None)
+        .map_err(|e| e.to_string())?;
+
+    let mut results = Vec::new();
+    for (index, delta) in diff.deltas().enumerate() {
+        let Some(path) = delta.new_file().path().or_else(|| delta.old_file().path()) else {
+            continue;
+        };
+        let (additions, deletions) = match git2::Patch::from_diff(&diff, index) {
+            Ok(Some(patch)) => patch
+                .line_stats()
+                .map(|(_, additions, deletions)| (additions as i64, deletions as i64))
+                .unwrap_or((0, 0)),
+            _ => (0, 0),
+        };
+        results.push(GitCommitFileStat {
+            path: normalize_git_path(&path.to_string_lossy()),
+            status: status_for_delta(delta.status()).to_string(),
+            additions,
+            deletions,
+        });
+    }
+    Ok(results)
+}
+
+/// Counts lines by streaming the file in chunks so large tracked files are never held in
+/// memory; returns `None` for binaries (a NUL byte within the first `BINARY_SNIFF_BYTES`).
+fn count_text_lines(mut reader: impl Read) -> Option<usize> {
+    let mut buffer = [0u8; BINARY_SNIFF_BYTES];
+    let mut sniffed = 0;
+    let mut newlines = 0;
+    let mut last_byte = None;
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(_) => return None,
+        };
+        let chunk = &buffer[..read];
+        if sniffed < BINARY_SNIFF_BYTES {
+            let sniff_len = (BINARY_SNIFF_BYTES - sniffed).min(read);
+            if chunk[..sniff_len].contains(&0) {
+                return None;
+            }
+            sniffed += sniff_len;
+        }
+        newlines += chunk.iter().filter(|byte| **byte == b'\n').count();
+        last_byte = chunk.last().copied();
+    }
+    let unterminated = last_byte.is_some_and(|byte| byte != b'\n');
+    Some(newlines + usize::from(unterminated))
+}
+
+fn head_oid(repo: &Repository) -> Option<String> {
+    repo.head()
+        .ok()
+        .and_then(|head| head.target())
+        .map(|oid| oid.to_string())
+}
+
+/// Counts tracked files and their lines in the working tree; binaries and files missing
+/// from disk still count as tracked but add no lines.
+fn project_summary_for_repo(repo: &Repository) -> Result<ProjectSummary, String> {
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| "repository has no working tree".to_string())?;
+    let index = repo.index().map_err(|e| e.to_string())?;
+    let mut tracked_files = 0;
+    let mut total_lines = 0;
+    let mut languages: HashMap<String, (usize, usize)> = HashMap::new();
+    for entry in index.iter() {
+        tracked_files += 1;
+        let path = String::from_utf8_lossy(&entry.path).to_string();
+        let Some(lines) = fs::File::open(workdir.join(&path))
             .ok()
-            .and_then(|head| head.peel_to_tree().ok());
-
-        let mut options = DiffOptions::new();
-        options
-            .include_untracked(true)
-            .recurse_untracked_dirs(true)
-            .show_untracked_content(true);
-
-        let diff = match head_tree.as_ref() {
-            Some(tree) => repo
-                .diff_tree_to_workdir_with_index(Some(tree), Some(&mut options))
-                .map_err(|e| e.to_string())?,
-            None => repo
-                .diff_tree_to_workdir_with_index(None, Some(&mut options))
-                .map_err(|e| e.to_string())?,
+            .and_then(count_text_lines)
+        else {
+            continue;
         };
+        total_lines += lines;
+        if let Some(extension) = Path::new(&path).extension().and_then(|ext| ext.to_str()) {
+            let stat = languages.entry(extension.to_ascii_lowercase()).or_default();
+            stat.0 += 1;
+            stat.1 += lines;
+        }
+    }
+    let mut languages: Vec<_> = languages
+        .into_iter()
+        .map(|(extension, (files, lines))| ProjectLanguageStat {
+            extension,
+            files,
+            lines,
+        })
+        .collect();
+    languages.sort_by(|a, b| b.lines.cmp(&a.lines).then_with(|| a.extension.cmp(&b.extension)));
+    languages.truncate(PROJECT_SUMMARY_LANGUAGES);
+
+    let latest_commit_timestamp = repo
+        .head()
+        .ok()
+        .and_then(|head| head.peel_to_commit().ok())
+        .map(|commit| commit.time().seconds());
+    Ok(ProjectSummary {
+        tracked_files,
+        total_lines,
+        languages,
+        latest_commit_timestamp,
+    })
+}
 
-        let mut results = Vec::new();
-        for (index, delta) in diff.deltas().enumerate() {
-            let old_path = delta.old_file().path();
-            let new_path = delta.new_file().path();
-            let display_path = new_path.or(old_path);
-            let Some(display_path) = display_path else {
-                continue;
-            };
-            let old_path_str = old_path.map(|path| path.to_string_lossy());
-            let new_path_str = new_path.map(|path| path.to_string_lossy());
-            let display_path_str = display_path.to_string_lossy();
-            let normalized_path = normalize_git_path(&display_path_str);
-            let old_image_mime = old_path_str.as_deref().and_then(image_mime_type);
-            let new_image_mime = new_path_str.as_deref().and_then(image_mime_type);
-            let is_image = old_image_mime.is_some() || new_image_mime.is_some();
-
-            if is_image {
-                let is_deleted = delta.status() == git2::Delta::Deleted;
-                let is_added = delta.status() == git2::Delta::Added;
-
-                let old_image_data = if !is_added && old_image_mime.is_some() {
-                    head_tree
-                        .as_ref()
-                        .and_then(|tree| old_path.and_then(|path| tree.get_path(path).ok()))
-                        .and_then(|entry| repo.find_blob(entry.id()).ok())
-                        .and_then(blob_to_base64)
-                } else {
-                    None
-                };
-
-                let new_image_data = if !is_deleted && new_image_mime.is_some() {
-                    match new_path {
-                        Some(path) => {
-                            let full_path = repo_root.join(path);
-                            read_image_base64(&full_path)
-                        }
-                        None => None,
+#[derive(Default)]
+struct ConventionTally {
+    sampled_files: usize,
+    lf_files: usize,
+    crlf_files: usize,
+    mixed_files: usize,
+    tab_lines: usize,
+    space_lines: usize,
+    space_multiple_of_four: usize,
+    space_multiple_of_two: usize,
+}
+
+impl ConventionTally {
+    fn add_file(&mut self, data: &[u8]) {
+        if data[..data.len().min(BINARY_SNIFF_BYTES)].contains(&0) {
+            return;
+        }
+        self.sampled_files += 1;
+        let crlf = data.windows(2).filter(|pair| pair == b"\r\n").count();
+        let lf = data.iter().filter(|byte| **byte == b'\n').count() - crlf;
+        match (lf, crlf) {
+            (0, 0) => {}
+            (_, 0) => self.lf_files += 1,
+            (0, _) => self.crlf_files += 1,
+            _ => self.mixed_files += 1,
+        }
+        for line in data.split(|byte| *byte == b'\n') {
+            match line.first() {
+                Some(b'\t') => self.tab_lines += 1,
+                Some(b' ') => {
+                    let width = line.iter().take_while(|byte| **byte == b' ').count();
+                    // Skip blank lines and block-comment continuations (` * `).
+                    if width == line.len() || line.get(width) == Some(&b'*') {
+                        continue;
                     }
-                } else {
-                    None
-                };
-
-                results.push(GitFileDiff {
-                    path: normalized_path,
-                    diff: String::new(),
-                    is_binary: true,
-                    is_image: true,
-                    old_image_data,
-                    new_image_data,
-                    old_image_mime: old_image_mime.map(str::to_string),
-                    new_image_mime: new_image_mime.map(str::to_string),
-                });
-                continue;
+                    self.space_lines += 1;
+                    if width % 4 == 0 {
+                        self.space_multiple_of_four += 1;
+                    }
+                    if width % 2 == 0 {
+                        self.space_multiple_of_two += 1;
+                    }
+                }
+                _ => {}
             }
+        }
+    }
 
-            let patch = match git2::Patch::from_diff(&diff, index) {
-                Ok(patch) => patch,
-                Err(_) => continue,
-            };
-            let Some(mut patch) = patch else {
-                continue;
-            };
-            let content = match diff_patch_to_string(&mut patch) {
-                Ok(content) => content,
-                Err(_) => continue,
-            };
-            if content.trim().is_empty() {
-                continue;
-            }
-            results.push(GitFileDiff {
-                path: normalized_path,
-                diff: content,
-                is_binary: false,
-                is_image: false,
-                old_image_data: None,
-                new_image_data: None,
-                old_image_mime: None,
-                new_image_mime: None,
-            });
+    fn line_ending(&self) -> Option<String> {
+        if self.lf_files == 0 && self.crlf_files == 0 {
+            return None;
+        }
+        let ending = if self.crlf_files > self.lf_files { "crlf" } else { "lf" };
+        Some(ending.to_string())
+    }
+
+    fn indentation(&self) -> (Option<String>, Option<u32>) {
+        if self.tab_lines == 0 && self.space_lines == 0 {
+            return (None, None);
+        }
+        if self.tab_lines > self.space_lines {
+            return (Some("tabs".to_string()), None);
+        }
+        // Tolerate a few odd alignments before giving up on a width.
+        let dominant = |count: usize| count * 10 >= self.space_lines * 9;
+        let width = if dominant(self.space_multiple_of_four) {
+            Some(4)
+        } else if dominant(self.space_multiple_of_two) {
+            Some(2)
+        } else {
+            None
+        };
+        (Some("spaces".to_string()), width)
+    }
+}
+
+fn parse_editorconfig_defaults(content: &str) -> EditorConfigDefaults {
+    let mut defaults = EditorConfigDefaults::default();
+    let mut in_default_section = false;
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if line.starts_with('[') {
+            in_default_section = line == "[*]";
+            continue;
+        }
+        if !in_default_section {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = Some(value.trim().to_ascii_lowercase());
+        match key.trim().to_ascii_lowercase().as_str() {
+            "end_of_line" => defaults.end_of_line = value,
+            "indent_style" => defaults.indent_style = value,
+            "indent_size" => defaults.indent_size = value,
+            _ => {}
+        }
+    }
+    defaults
+}
+
+/// Samples tracked files for their dominant line endings and indentation.
+fn repo_conventions_for_repo(repo: &Repository) -> Result<RepoConventions, String> {
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| "repository has no working tree".to_string())?;
+    let index = repo.index().map_err(|e| e.to_string())?;
+    let mut tally = ConventionTally::default();
+    for entry in index.iter() {
+        if tally.sampled_files >= CONVENTION_SAMPLE_FILES {
+            break;
+        }
+        let path = String::from_utf8_lossy(&entry.path).to_string();
+        let Ok(file) = fs::File::open(workdir.join(&path)) else {
+            continue;
+        };
+        let mut data = Vec::new();
+        if file
+            .take(CONVENTION_SAMPLE_BYTES)
+            .read_to_end(&mut data)
+            .is_err()
+        {
+            continue;
+        }
+        tally.add_file(&data);
+    }
+
+    let core_autocrlf = repo
+        .config()
+        .ok()
+        .and_then(|config| config.get_string("core.autocrlf").ok());
+    let editorconfig = fs::read_to_string(workdir.join(".editorconfig"))
+        .ok()
+        .map(|content| parse_editorconfig_defaults(&content));
+    let (indent_style, indent_width) = tally.indentation();
+    Ok(RepoConventions {
+        sampled_files: tally.sampled_files,
+        lf_files: tally.lf_files,
+        crlf_files: tally.crlf_files,
+        mixed_files: tally.mixed_files,
+        line_ending: tally.line_ending(),
+        indent_style,
+        indent_width,
+        core_autocrlf,
+        editorconfig,
+    })
+}
+
+#[tauri::command]
+pub(crate) async fn detect_repo_conventions(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<RepoConventions, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || {
+        let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+        repo_conventions_for_repo(&repo)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Size overview for a workspace, cached until HEAD moves.
+#[tauri::command]
+pub(crate) async fn get_project_summary(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<ProjectSummary, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let head_root = repo_root.clone();
+    let head = tokio::task::spawn_blocking(move || {
+        open_repository(&head_root)
+            .map(|repo| head_oid(&repo))
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    if let Some((cached_head, summary)) = state.project_summaries.lock().await.get(&workspace_id) {
+        if *cached_head == head {
+            return Ok(summary.clone());
         }
+    }
+
+    let summary = tokio::task::spawn_blocking(move || {
+        let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+        project_summary_for_repo(&repo)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    state
+        .project_summaries
+        .lock()
+        .await
+        .insert(workspace_id, (head, summary.clone()));
+    Ok(summary)
+}
+
+#[tauri::command]
+pub(crate) async fn get_commit_file_stats(
+    workspace_id: String,
+    sha: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitCommitFileStat>, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+    commit_file_stats_for_repo(&repo, &sha)
+}
+
+#[tauri::command]
+pub(crate) async fn get_commit_details(
+    workspace_id: String,
+    sha: String,
+    state: State<'_, AppState>,
+) -> Result<GitCommitDetails, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+    commit_details_for_repo(&repo, &sha)
+}
+
+/// Reads the reflog of `ref_name` (default `HEAD`), which may be a short name such as `main`.
+/// A ref that does not exist is an error rather than an empty reflog.
+fn reflog_for_repo(
+    repo: &Repository,
+    ref_name: Option<&str>,
+    limit: usize,
+) -> Result<Vec<GitReflogEntry>, String> {
+    let ref_name = ref_name.map(str::trim).filter(|name| !name.is_empty()).unwrap_or("HEAD");
+    let reference = repo
+        .resolve_reference_from_short_name(ref_name)
+        .map_err(|err| format!("Unknown ref {ref_name}: {}", err.message()))?;
+    let full_name = reference.name().unwrap_or(ref_name);
+    let reflog = repo
+        .reflog(full_name)
+        .map_err(|err| format!("Failed to read reflog for {ref_name}: {}", err.message()))?;
+    Ok(reflog
+        .iter()
+        .take(limit)
+        .map(|entry| GitReflogEntry {
+            old_sha: entry.id_old().to_string(),
+            new_sha: entry.id_new().to_string(),
+            committer: signature_info(entry.committer()),
+            message: entry.message().unwrap_or("").to_string(),
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub(crate) async fn get_reflog(
+    workspace_id: String,
+    ref_name: Option<String>,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitReflogEntry>, String> {
+    let limit = resolve_git_log_limit(limit)?;
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+    reflog_for_repo(&repo, ref_name.as_deref(), limit)
+}
+
+fn read_git_file(repo: &Repository, name: &str) -> Option<String> {
+    fs::read_to_string(repo.path().join(name))
+        .ok()
+        .map(|content| content.trim().to_string())
+        .filter(|content| !content.is_empty())
+}
+
+fn short_sha(sha: &str) -> &str {
+    sha.get(..7).unwrap_or(sha)
+}
 
-        Ok(results)
+fn rebase_detail(repo: &Repository) -> Option<String> {
+    let (dir, step_file, total_file) = if repo.path().join("rebase-merge").is_dir() {
+        ("rebase-merge", "msgnum", "end")
+    } else {
+        ("rebase-apply", "next", "last")
+    };
+    let branch = read_git_file(repo, &format!("{dir}/head-name"))
+        .map(|name| name.trim_start_matches("refs/heads/").to_string());
+    let step = read_git_file(repo, &format!("{dir}/{step_file}"));
+    let total = read_git_file(repo, &format!("{dir}/{total_file}"));
+    let progress = step.zip(total).map(|(step, total)| format!("step {step}/{total}"));
+    match (branch, progress) {
+        (Some(branch), Some(progress)) => Some(format!("Rebasing {branch} ({progress})")),
+        (Some(branch), None) => Some(format!("Rebasing {branch}")),
+        (None, Some(progress)) => Some(format!("Rebasing ({progress})")),
+        (None, None) => None,
+    }
+}
+
+fn operation_state_for_repo(repo: &Repository) -> Result<GitOperationState, String> {
+    use git2::RepositoryState;
+
+    let state = repo.state();
+    let name = match state {
+        RepositoryState::Clean => "clean",
+        RepositoryState::Merge => "merge",
+        RepositoryState::Revert => "revert",
+        RepositoryState::RevertSequence => "revertSequence",
+        RepositoryState::CherryPick => "cherryPick",
+        RepositoryState::CherryPickSequence => "cherryPickSequence",
+        RepositoryState::Bisect => "bisect",
+        RepositoryState::Rebase => "rebase",
+        RepositoryState::RebaseInteractive => "rebaseInteractive",
+        RepositoryState::RebaseMerge => "rebaseMerge",
+        RepositoryState::ApplyMailbox => "applyMailbox",
+        RepositoryState::ApplyMailboxOrRebase => "applyMailboxOrRebase",
+    };
+    let detail = match state {
+        RepositoryState::Clean => None,
+        RepositoryState::Merge => read_git_file(repo, "MERGE_HEAD").map(|heads| {
+            let heads: Vec<&str> = heads.lines().map(short_sha).collect();
+            format!("Merging {}", heads.join(", "))
+        }),
+        RepositoryState::CherryPick | RepositoryState::CherryPickSequence => {
+            read_git_file(repo, "CHERRY_PICK_HEAD")
+                .map(|sha| format!("Cherry-picking {}", short_sha(&sha)))
+        }
+        RepositoryState::Revert | RepositoryState::RevertSequence => {
+            read_git_file(repo, "REVERT_HEAD").map(|sha| format!("Reverting {}", short_sha(&sha)))
+        }
+        RepositoryState::Bisect => Some("Bisecting".to_string()),
+        RepositoryState::Rebase
+        | RepositoryState::RebaseInteractive
+        | RepositoryState::RebaseMerge
+        | RepositoryState::ApplyMailboxOrRebase => rebase_detail(repo),
+        RepositoryState::ApplyMailbox => Some("Applying patches".to_string()),
+    };
+    Ok(GitOperationState {
+        state: name.to_string(),
+        in_progress: state != RepositoryState::Clean,
+        detail,
+        conflicted_paths: conflicted_paths(repo)?,
     })
-    .await
-    .map_err(|e| e.to_string())?
 }
 
 #[tauri::command]
-pub(crate) async fn get_git_log(
+pub(crate) async fn get_repo_operation_state(
     workspace_id: String,
-    limit: Option<usize>,
     state: State<'_, AppState>,
-) -> Result<GitLogResponse, String> {
+) -> Result<GitOperationState, String> {
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
@@ -877,99 +2871,141 @@ pub(crate) async fn get_git_log(
     drop(workspaces);
 
     let repo_root = resolve_git_root(&entry)?;
-    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
-    let max_items = limit.unwrap_or(40);
-    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
-    revwalk.push_head().map_err(|e| e.to_string())?;
-    revwalk
-        .set_sorting(Sort::TIME)
-        .map_err(|e| e.to_string())?;
-
-    let mut total = 0usize;
-    for oid_result in revwalk {
-        oid_result.map_err(|e| e.to_string())?;
-        total += 1;
-    }
+    let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+    operation_state_for_repo(&repo)
+}
 
-    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
-    revwalk.push_head().map_err(|e| e.to_string())?;
-    revwalk
-        .set_sorting(Sort::TIME)
+/// The combined HEAD-to-working-tree diff as a unified patch, untracked files included.
+fn working_patch_for_repo(repo: &Repository) -> Result<String, String> {
+    let head_tree = head_tree(repo);
+    let mut options = DiffOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true);
+    let diff = repo
+        .diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut options))
         .map_err(|e| e.to_string())?;
+    let mut patch = String::new();
+    diff.print(DiffFormat::Patch, |_, _, line| {
+        if matches!(line.origin(), '+' | '-' | ' ') {
+            patch.push(line.origin());
+        }
+        patch.push_str(&String::from_utf8_lossy(line.content()));
+        true
+    })
+    .map_err(|e| e.to_string())?;
+    Ok(patch)
+}
 
-    let mut entries = Vec::new();
-    for oid_result in revwalk.take(max_items) {
-        let oid = oid_result.map_err(|e| e.to_string())?;
-        let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
-        entries.push(commit_to_entry(commit));
-    }
+fn review_bundle_for_repo(repo: &Repository) -> Result<GitReviewBundle, String> {
+    let mut files = Vec::new();
+    let mut total_additions = 0i64;
+    let mut total_deletions = 0i64;
+    visit_git_status(repo, |entry| {
+        total_additions += entry.file.additions;
+        total_deletions += entry.file.deletions;
+        files.push(entry.file);
+        Ok(())
+    })?;
+    let head_sha = repo
+        .head()
+        .ok()
+        .and_then(|head| head.target())
+        .map(|oid| oid.to_string());
+    Ok(GitReviewBundle {
+        branch_name: current_branch_name(repo).unwrap_or_else(|| "unknown".to_string()),
+        head_sha,
+        files,
+        total_additions,
+        total_deletions,
+        patch: working_patch_for_repo(repo)?,
+    })
+}
 
-    let mut ahead = 0usize;
-    let mut behind = 0usize;
-    let mut ahead_entries = Vec::new();
-    let mut behind_entries = Vec::new();
-    let mut upstream = None;
+/// Branch, status summary and full working-tree patch in one payload for out-of-band review.
+#[tauri::command]
+pub(crate) async fn export_review_bundle(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<GitReviewBundle, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
 
-    if let Ok(head) = repo.head() {
-        if head.is_branch() {
-            if let Some(branch_name) = head.shorthand() {
-                if let Ok(branch) = repo.find_branch(branch_name, BranchType::Local) {
-                    if let Ok(upstream_branch) = branch.upstream() {
-                        let upstream_ref = upstream_branch.get();
-                        upstream = upstream_ref
-                            .shorthand()
-                            .map(|name| name.to_string())
-                            .or_else(|| upstream_ref.name().map(|name| name.to_string()));
-                        if let (Some(head_oid), Some(upstream_oid)) =
-                            (head.target(), upstream_ref.target())
-                        {
-                            let (ahead_count, behind_count) = repo
-                                .graph_ahead_behind(head_oid, upstream_oid)
-                                .map_err(|e| e.to_string())?;
-                            ahead = ahead_count;
-                            behind = behind_count;
-
-                            let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
-                            revwalk.push(head_oid).map_err(|e| e.to_string())?;
-                            revwalk.hide(upstream_oid).map_err(|e| e.to_string())?;
-                            revwalk
-                                .set_sorting(Sort::TIME)
-                                .map_err(|e| e.to_string())?;
-                            for oid_result in revwalk.take(max_items) {
-                                let oid = oid_result.map_err(|e| e.to_string())?;
-                                let commit =
-                                    repo.find_commit(oid).map_err(|e| e.to_string())?;
-                                ahead_entries.push(commit_to_entry(commit));
-                            }
-
-                            let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
-                            revwalk.push(upstream_oid).map_err(|e| e.to_string())?;
-                            revwalk.hide(head_oid).map_err(|e| e.to_string())?;
-                            revwalk
-                                .set_sorting(Sort::TIME)
-                                .map_err(|e| e.to_string())?;
-                            for oid_result in revwalk.take(max_items) {
-                                let oid = oid_result.map_err(|e| e.to_string())?;
-                                let commit =
-                                    repo.find_commit(oid).map_err(|e| e.to_string())?;
-                                behind_entries.push(commit_to_entry(commit));
-                            }
-                        }
-                    }
-                }
-            }
+    let repo_root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || {
+        let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+        review_bundle_for_repo(&repo)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn reset_to_commit_for_repo(
+    repo: &Repository,
+    sha: &str,
+    mode: GitResetMode,
+    confirm: bool,
+) -> Result<GitResetResult, String> {
+    if mode == GitResetMode::Hard && !confirm {
+        return Err("Hard reset discards local changes; confirm it to continue".to_string());
+    }
+    let commit = repo
+        .revparse_single(sha.trim())
+        .and_then(|object| object.peel_to_commit())
+        .map_err(|err| format!("Unknown commit {sha}: {}", err.message()))?;
+    let target_tree = commit.tree().map_err(|e| e.to_string())?;
+    let affected = match mode {
+        GitResetMode::Soft => {
+            repo.diff_tree_to_tree(head_tree(repo).as_ref(), Some(&target_tree), None)
         }
+        GitResetMode::Mixed => repo.diff_tree_to_index(Some(&target_tree), None, None),
+        GitResetMode::Hard => repo.diff_tree_to_workdir_with_index(Some(&target_tree), None),
     }
+    .map_err(|e| e.to_string())?
+    .deltas()
+    .len();
+
+    let (reset_type, mut checkout) = match mode {
+        GitResetMode::Soft => (git2::ResetType::Soft, None),
+        GitResetMode::Mixed => (git2::ResetType::Mixed, None),
+        GitResetMode::Hard => {
+            let mut checkout = git2::build::CheckoutBuilder::new();
+            checkout.force();
+            (git2::ResetType::Hard, Some(checkout))
+        }
+    };
+    repo.reset(commit.as_object(), reset_type, checkout.as_mut())
+        .map_err(|err| format!("Failed to reset: {}", err.message()))?;
+    Ok(GitResetResult {
+        sha: commit.id().to_string(),
+        affected_files: affected,
+    })
+}
 
-    Ok(GitLogResponse {
-        total,
-        entries,
-        ahead,
-        behind,
-        ahead_entries,
-        behind_entries,
-        upstream,
+/// Moves HEAD to `sha`. A hard reset also overwrites the index and working tree, so it needs
+/// `confirm`; the previous HEAD stays recoverable through `get_reflog`.
+#[tauri::command]
+pub(crate) async fn reset_to_commit(
+    workspace_id: String,
+    sha: String,
+    mode: GitResetMode,
+    confirm: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<GitResetResult, String> {
+    let entry = writable_workspace_entry(&state, &workspace_id).await?;
+
+    let repo_root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || {
+        let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+        reset_to_commit_for_repo(&repo, &sha, mode, confirm.unwrap_or(false))
     })
+    .await
+    .map_err(|e| e.to_string())?
 }
 
 #[tauri::command]
@@ -985,7 +3021,7 @@ pub(crate) async fn get_git_commit_diff(
         .clone();
 
     let repo_root = resolve_git_root(&entry)?;
-    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
     let oid = git2::Oid::from_str(&sha).map_err(|e| e.to_string())?;
     let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
     let commit_tree = commit.tree().map_err(|e| e.to_string())?;
@@ -1056,6 +3092,20 @@ pub(crate) async fn get_git_commit_diff(
             Ok(patch) => patch,
             Err(_) => continue,
         };
+        if delta.flags().is_binary() {
+            results.push(GitCommitDiff {
+                path: normalized_path,
+                status: status_for_delta(delta.status()).to_string(),
+                diff: String::new(),
+                is_binary: true,
+                is_image: false,
+                old_image_data: None,
+                new_image_data: None,
+                old_image_mime: None,
+                new_image_mime: None,
+            });
+            continue;
+        }
         let Some(mut patch) = patch else {
             continue;
         };
@@ -1094,7 +3144,7 @@ pub(crate) async fn get_git_remote(
         .clone();
 
     let repo_root = resolve_git_root(&entry)?;
-    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
     let remotes = repo.remotes().map_err(|e| e.to_string())?;
     let name = if remotes.iter().any(|remote| remote == Some("origin")) {
         "origin".to_string()
@@ -1113,6 +3163,246 @@ pub(crate) async fn get_git_remote(
     Ok(remote.url().map(|url| url.to_string()))
 }
 
+fn list_remotes_for_repo(repo: &Repository) -> Result<Vec<GitRemoteInfo>, String> {
+    let names = repo.remotes().map_err(|e| e.to_string())?;
+    let mut remotes = Vec::new();
+    for name in names.iter().flatten() {
+        let remote = repo.find_remote(name).map_err(|e| e.to_string())?;
+        remotes.push(GitRemoteInfo {
+            name: name.to_string(),
+            url: remote.url().map(|url| url.to_string()),
+        });
+    }
+    Ok(remotes)
+}
+
+fn validate_remote_name(name: &str) -> Result<(), String> {
+    if git2::Remote::is_valid_name(name) {
+        Ok(())
+    } else {
+        Err(format!("Invalid remote name: {name}"))
+    }
+}
+
+fn set_remote_for_repo(
+    repo: &Repository,
+    name: &str,
+    url: &str,
+) -> Result<Vec<GitRemoteInfo>, String> {
+    let name = name.trim();
+    let url = url.trim();
+    validate_remote_name(name)?;
+    if url.is_empty() {
+        return Err("Remote URL is required.".to_string());
+    }
+    if repo.find_remote(name).is_ok() {
+        repo.remote_set_url(name, url).map_err(|e| e.to_string())?;
+    } else {
+        repo.remote(name, url).map_err(|e| e.to_string())?;
+    }
+    list_remotes_for_repo(repo)
+}
+
+fn remove_remote_for_repo(repo: &Repository, name: &str) -> Result<Vec<GitRemoteInfo>, String> {
+    let name = name.trim();
+    validate_remote_name(name)?;
+    repo.remote_delete(name).map_err(|e| e.to_string())?;
+    list_remotes_for_repo(repo)
+}
+
+#[tauri::command]
+pub(crate) async fn set_git_remote(
+    workspace_id: String,
+    name: String,
+    url: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitRemoteInfo>, String> {
+    let entry = writable_workspace_entry(&state, &workspace_id).await?;
+
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+    set_remote_for_repo(&repo, &name, &url)
+}
+
+#[tauri::command]
+pub(crate) async fn remove_git_remote(
+    workspace_id: String,
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitRemoteInfo>, String> {
+    let entry = writable_workspace_entry(&state, &workspace_id).await?;
+
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+    remove_remote_for_repo(&repo, &name)
+}
+
+fn list_stashes_for_repo(repo: &mut Repository) -> Result<Vec<GitStashEntry>, String> {
+    let mut stashes = Vec::new();
+    repo.stash_foreach(|index, message, oid| {
+        stashes.push(GitStashEntry {
+            index,
+            message: message.to_string(),
+            sha: oid.to_string(),
+        });
+        true
+    })
+    .map_err(|e| e.to_string())?;
+    Ok(stashes)
+}
+
+fn conflicted_paths(repo: &Repository) -> Result<Vec<String>, String> {
+    let index = repo.index().map_err(|e| e.to_string())?;
+    index_conflict_paths(&index)
+}
+
+fn index_conflict_paths(index: &git2::Index) -> Result<Vec<String>, String> {
+    if !index.has_conflicts() {
+        return Ok(Vec::new());
+    }
+    let mut paths = Vec::new();
+    for conflict in index.conflicts().map_err(|e| e.to_string())? {
+        let conflict = conflict.map_err(|e| e.to_string())?;
+        let entry = conflict.our.or(conflict.their).or(conflict.ancestor);
+        if let Some(entry) = entry {
+            paths.push(normalize_git_path(&String::from_utf8_lossy(&entry.path)));
+        }
+    }
+    paths.sort();
+    paths.dedup();
+    Ok(paths)
+}
+
+/// Merges the stash into HEAD in memory so conflicts are found before the working tree is
+/// touched; libgit2 would otherwise write conflict entries and leave a half-applied stash.
+fn stash_conflicts_with_head(repo: &Repository, sha: &str) -> Result<Vec<String>, String> {
+    let Ok(head) = repo.head().and_then(|head| head.peel_to_tree()) else {
+        return Ok(Vec::new());
+    };
+    let oid = git2::Oid::from_str(sha).map_err(|e| e.to_string())?;
+    let stash = repo.find_commit(oid).map_err(|e| e.to_string())?;
+    let base = stash
+        .parent(0)
+        .and_then(|parent| parent.tree())
+        .map_err(|e| e.to_string())?;
+    let stashed = stash.tree().map_err(|e| e.to_string())?;
+    let merged = repo
+        .merge_trees(&base, &head, &stashed, None)
+        .map_err(|e| e.to_string())?;
+    index_conflict_paths(&merged)
+}
+
+/// Applies `stash@{index}` and keeps it in the stash list, unlike a pop.
+fn stash_apply_for_repo(repo: &mut Repository, index: usize) -> Result<(), String> {
+    let stashes = list_stashes_for_repo(repo)?;
+    let Some(stash) = stashes.get(index) else {
+        return Err(format!("Stash {index} not found"));
+    };
+    let conflicts = stash_conflicts_with_head(repo, &stash.sha)?;
+    if !conflicts.is_empty() {
+        return Err(format!("Stash conflicts with HEAD in: {}", conflicts.join(", ")));
+    }
+    let result = repo.stash_apply(index, None);
+    let conflicts = conflicted_paths(repo)?;
+    if !conflicts.is_empty() {
+        return Err(format!("Stash applied with conflicts in: {}", conflicts.join(", ")));
+    }
+    result.map_err(|err| match err.code() {
+        git2::ErrorCode::Conflict | git2::ErrorCode::MergeConflict => {
+            format!("Stash conflicts with local changes: {}", err.message())
+        }
+        _ => err.to_string(),
+    })
+}
+
+/// Stashes tracked changes like `git stash push`, returning the stash commit's sha.
+fn stash_push_for_repo(repo: &mut Repository, message: Option<&str>) -> Result<String, String> {
+    let signature = repo_signature(repo)?;
+    let message = message.map(str::trim).filter(|message| !message.is_empty());
+    let oid = repo
+        .stash_save2(&signature, message, None)
+        .map_err(|err| match err.code() {
+            git2::ErrorCode::NotFound => "No local changes to stash".to_string(),
+            _ => format!("Failed to stash changes: {}", err.message()),
+        })?;
+    Ok(oid.to_string())
+}
+
+/// Applies `stash@{index}` and drops it only once it applied cleanly, as `git stash pop` does.
+fn stash_pop_for_repo(repo: &mut Repository, index: usize) -> Result<(), String> {
+    stash_apply_for_repo(repo, index)?;
+    repo.stash_drop(index)
+        .map_err(|err| format!("Stash applied but could not be dropped: {}", err.message()))
+}
+
+#[tauri::command]
+pub(crate) async fn git_stash_push(
+    workspace_id: String,
+    message: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let entry = writable_workspace_entry(&state, &workspace_id).await?;
+
+    let repo_root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || {
+        let mut repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+        stash_push_for_repo(&mut repo, message.as_deref())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub(crate) async fn git_stash_pop(
+    workspace_id: String,
+    index: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let entry = writable_workspace_entry(&state, &workspace_id).await?;
+
+    let repo_root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || {
+        let mut repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+        stash_pop_for_repo(&mut repo, index.unwrap_or(0))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub(crate) async fn stash_apply(
+    workspace_id: String,
+    index: usize,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let entry = writable_workspace_entry(&state, &workspace_id).await?;
+
+    let repo_root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || {
+        let mut repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+        stash_apply_for_repo(&mut repo, index)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub(crate) async fn list_stashes(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitStashEntry>, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let mut repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+    list_stashes_for_repo(&mut repo)
+}
+
 #[tauri::command]
 pub(crate) async fn get_github_issues(
     workspace_id: String,
@@ -1352,38 +3642,78 @@ pub(crate) async fn get_github_pull_request_comments(
     Ok(comments)
 }
 
+/// Local and remote-tracking branches: HEAD first, then locals, then remotes, by name.
+fn branches_for_repo(repo: &Repository) -> Result<Vec<GitBranchInfo>, String> {
+    let mut branches = Vec::new();
+    let refs = repo.branches(None).map_err(|e| e.to_string())?;
+    for branch_result in refs {
+        let (branch, branch_type) = branch_result.map_err(|e| e.to_string())?;
+        let name = branch.name().ok().flatten().unwrap_or("").to_string();
+        if name.is_empty() {
+            continue;
+        }
+        let is_remote = matches!(branch_type, BranchType::Remote);
+        // `origin/HEAD` only points at another remote branch.
+        if is_remote && branch.get().symbolic_target().is_some() {
+            continue;
+        }
+        let upstream = if is_remote {
+            None
+        } else {
+            branch
+                .upstream()
+                .ok()
+                .and_then(|upstream| upstream.name().ok().flatten().map(str::to_string))
+        };
+        let commit = branch.get().peel_to_commit().ok();
+        branches.push(GitBranchInfo {
+            name,
+            is_remote,
+            is_head: branch.is_head(),
+            upstream,
+            last_commit_sha: commit.as_ref().map(|commit| commit.id().to_string()),
+            last_commit: commit.map(|commit| commit.time().seconds()).unwrap_or(0),
+        });
+    }
+    branches.sort_by(|a, b| {
+        b.is_head
+            .cmp(&a.is_head)
+            .then(a.is_remote.cmp(&b.is_remote))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(branches)
+}
+
 #[tauri::command]
 pub(crate) async fn list_git_branches(
     workspace_id: String,
     state: State<'_, AppState>,
-) -> Result<serde_json::Value, String> {
+) -> Result<Vec<GitBranchInfo>, String> {
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
         .ok_or("workspace not found")?
         .clone();
+    drop(workspaces);
+
     let repo_root = resolve_git_root(&entry)?;
-    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
-    let mut branches = Vec::new();
-    let refs = repo
-        .branches(Some(BranchType::Local))
-        .map_err(|e| e.to_string())?;
-    for branch_result in refs {
-        let (branch, _) = branch_result.map_err(|e| e.to_string())?;
-        let name = branch.name().ok().flatten().unwrap_or("").to_string();
-        if name.is_empty() {
-            continue;
-        }
-        let last_commit = branch
-            .get()
-            .target()
-            .and_then(|oid| repo.find_commit(oid).ok())
-            .map(|commit| commit.time().seconds())
-            .unwrap_or(0);
-        branches.push(BranchInfo { name, last_commit });
+    tokio::task::spawn_blocking(move || {
+        let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+        branches_for_repo(&repo)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn checkout_error_message(name: &str, error: git2::Error) -> String {
+    match error.code() {
+        git2::ErrorCode::NotFound => format!("Branch not found: {name}"),
+        git2::ErrorCode::Conflict => format!(
+            "Cannot check out {name}: uncommitted changes would be overwritten. \
+             Commit or stash them first."
+        ),
+        _ => error.to_string(),
     }
-    branches.sort_by(|a, b| b.last_commit.cmp(&a.last_commit));
-    Ok(json!({ "branches": branches }))
 }
 
 #[tauri::command]
@@ -1392,29 +3722,27 @@ pub(crate) async fn checkout_git_branch(
     name: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
-    let workspaces = state.workspaces.lock().await;
-    let entry = workspaces
-        .get(&workspace_id)
-        .ok_or("workspace not found")?
-        .clone();
+    let entry = writable_workspace_entry(&state, &workspace_id).await?;
     let repo_root = resolve_git_root(&entry)?;
-    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
-    checkout_branch(&repo, &name).map_err(|e| e.to_string())
+    let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+    checkout_branch(&repo, &name).map_err(|e| checkout_error_message(&name, e))
 }
 
 #[tauri::command]
 pub(crate) async fn create_git_branch(
     workspace_id: String,
     name: String,
-    state: State<'_, AppState>,
-) -> Result<(), String> {
-    let workspaces = state.workspaces.lock().await;
-    let entry = workspaces
-        .get(&workspace_id)
-        .ok_or("workspace not found")?
-        .clone();
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let entry = writable_workspace_entry(&state, &workspace_id).await?;
     let repo_root = resolve_git_root(&entry)?;
-    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    let repo = open_repository(&repo_root).map_err(|e| e.to_string())?;
+    if head_is_unborn(&repo) {
+        // Nothing to branch from yet; point HEAD at the new name for the first commit.
+        return repo
+            .set_head(&format!("refs/heads/{name}"))
+            .map_err(|e| e.to_string());
+    }
     let head = repo.head().map_err(|e| e.to_string())?;
     let target = head.peel_to_commit().map_err(|e| e.to_string())?;
     repo.branch(&name, &target, false)
@@ -1425,6 +3753,7 @@ pub(crate) async fn create_git_branch(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::WorkspaceKind;
     use std::fs;
 
     fn create_temp_repo() -> (PathBuf, Repository) {
@@ -1437,6 +3766,41 @@ mod tests {
         (root, repo)
     }
 
+    /// Writes `content` to `path`, stages it and commits the index on top of HEAD, so fixtures
+    /// don't depend on a git binary. Anything staged beforehand goes into the same commit.
+    fn commit_file(
+        repo: &Repository,
+        path: &str,
+        content: impl AsRef<[u8]>,
+        message: &str,
+    ) -> git2::Oid {
+        let file = repo.workdir().expect("workdir").join(path);
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent).expect("create dir");
+        }
+        fs::write(&file, content).expect("write file");
+        let mut index = repo.index().expect("repo index");
+        index.add_path(Path::new(path)).expect("add path");
+        index.write().expect("write index");
+        let tree = repo
+            .find_tree(index.write_tree().expect("write tree"))
+            .expect("find tree");
+        // Only the repo's own config, so a developer's global identity can't leak in.
+        let config = repo
+            .config()
+            .and_then(|config| config.open_level(git2::ConfigLevel::Local))
+            .expect("repo config");
+        let name = config.get_string("user.name").unwrap_or_else(|_| "Test".to_string());
+        let email = config
+            .get_string("user.email")
+            .unwrap_or_else(|_| "test@example.com".to_string());
+        let sig = git2::Signature::now(&name, &email).expect("signature");
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .expect("commit")
+    }
+
     #[test]
     fn collect_workspace_diff_prefers_staged_changes() {
         let (root, repo) = create_temp_repo();
@@ -1463,20 +3827,1002 @@ mod tests {
     }
 
     #[test]
-    fn action_paths_for_file_expands_renames() {
+    fn prepare_commit_stages_requested_paths() {
         let (root, repo) = create_temp_repo();
-        fs::write(root.join("a.txt"), "hello\n").expect("write file");
+        let mut config = repo.config().expect("repo config");
+        config.set_str("user.name", "Test").expect("set name");
+        config.set_str("user.email", "test@example.com").expect("set email");
+        fs::write(root.join("a.txt"), "one\n").expect("write file");
+        fs::write(root.join("b.txt"), "one\n").expect("write file");
+
+        let paths = CommitStaging::Paths(vec!["a.txt".to_string()]);
+        let long = "x".repeat(73);
+        assert!(prepare_commit_for_repo(&repo, &long, &paths).is_err());
+        assert!(prepare_commit_for_repo(&repo, "  \n", &paths).is_err());
+        assert!(repo.index().expect("index").is_empty());
+
+        prepare_commit_for_repo(&repo, "add a", &paths).expect("prepare a");
+        let index = repo.index().expect("index");
+        assert!(index.get_path(Path::new("a.txt"), 0).is_some());
+        assert!(index.get_path(Path::new("b.txt"), 0).is_none());
+    }
 
-        let mut index = repo.index().expect("repo index");
-        index
-            .add_path(Path::new("a.txt"))
-            .expect("add path");
-        let tree_id = index.write_tree().expect("write tree");
+    #[test]
+    fn prepare_commit_stages_tracked_changes_without_paths() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, "a.txt", "one\n", "init");
+        fs::write(root.join("a.txt"), "two\n").expect("write file");
+        fs::write(root.join("new.txt"), "new\n").expect("write file");
+
+        prepare_commit_for_repo(&repo, "update a", &CommitStaging::Tracked).expect("prepare");
+        let statuses = repo.statuses(None).expect("statuses");
+        let status_of = |path: &str| {
+            statuses
+                .iter()
+                .find(|entry| entry.path() == Some(path))
+                .map(|entry| entry.status())
+                .expect("status entry")
+        };
+        assert!(status_of("a.txt").contains(Status::INDEX_MODIFIED));
+        assert!(status_of("new.txt").contains(Status::WT_NEW));
+    }
+
+    #[test]
+    fn commit_and_push_reports_the_commit_when_the_push_fails() {
+        let (root, repo) = create_temp_repo();
+        let mut config = repo.config().expect("repo config");
+        config.set_str("user.name", "Test").expect("set name");
+        config.set_str("user.email", "test@example.com").expect("set email");
+        config.set_bool("commit.gpgsign", false).expect("disable signing");
+        commit_file(&repo, "a.txt", "one\n", "init");
+        repo.remote("origin", &root.join("missing-remote").to_string_lossy())
+            .expect("add remote");
+        fs::write(root.join("a.txt"), "two\n").expect("write file");
+
+        let mut entry = WorkspaceEntry {
+            id: "w1".to_string(),
+            name: "w1".to_string(),
+            path: root.to_string_lossy().to_string(),
+            codex_bin: None,
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+            last_error: None,
+            monitor_only: false,
+            description: None,
+        };
+        entry.settings.git_push_after_commit = true;
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+
+        let error = runtime
+            .block_on(commit_and_push(&entry, &root, "  update a\n", CommitStaging::Tracked))
+            .expect_err("push should fail");
+        let head = repo.head().expect("head").peel_to_commit().expect("head commit");
+        match error {
+            GitCommitError::CommitOkPushFailed { sha, .. } => {
+                assert_eq!(sha, head.id().to_string());
+            }
+            GitCommitError::CommitFailed { message } => panic!("commit failed: {message}"),
+        }
+        assert_eq!(head.summary(), Some("update a"));
+    }
+
+    #[test]
+    fn branches_list_head_then_locals_then_remotes() {
+        let (_root, repo) = create_temp_repo();
+        let oid = commit_file(&repo, "a.txt", "one\n", "init");
+        let commit = repo.find_commit(oid).expect("find commit");
+        let head_name = current_branch_name(&repo).expect("branch name");
+        repo.branch("aaa", &commit, false).expect("create branch");
+        repo.branch("zzz", &commit, false).expect("create branch");
+        repo.remote("origin", "https://example.com/repo.git")
+            .expect("add remote");
+        repo.reference("refs/remotes/origin/aaa", oid, true, "test remote")
+            .expect("create remote ref");
+        repo.reference_symbolic(
+            "refs/remotes/origin/HEAD",
+            "refs/remotes/origin/aaa",
+            true,
+            "test remote head",
+        )
+        .expect("create remote head");
+        repo.find_branch("aaa", BranchType::Local)
+            .expect("local branch")
+            .set_upstream(Some("origin/aaa"))
+            .expect("set upstream");
+
+        let branches = branches_for_repo(&repo).expect("list branches");
+        let names: Vec<_> = branches.iter().map(|branch| branch.name.as_str()).collect();
+        assert_eq!(names, vec![head_name.as_str(), "aaa", "zzz", "origin/aaa"]);
+        assert!(branches[0].is_head);
+        assert!(branches[1..].iter().all(|branch| !branch.is_head));
+        assert_eq!(branches[1].upstream.as_deref(), Some("origin/aaa"));
+        assert_eq!(branches[2].upstream, None);
+        assert!(branches[3].is_remote);
+        assert_eq!(branches[3].last_commit_sha, Some(oid.to_string()));
+    }
+
+    #[test]
+    fn checkout_branch_refuses_to_overwrite_local_changes() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, "a.txt", "one\n", "init");
+        let main = current_branch_name(&repo).expect("current branch");
+
+        let head = repo.head().expect("head").peel_to_commit().expect("head commit");
+        repo.branch("feature", &head, false).expect("create branch");
+        checkout_branch(&repo, "feature").expect("checkout feature");
+        commit_file(&repo, "a.txt", "two\n", "feature change");
+
+        checkout_branch(&repo, &main).expect("checkout main");
+        assert_eq!(fs::read_to_string(root.join("a.txt")).expect("read"), "one\n");
+        let branches = branches_for_repo(&repo).expect("list branches");
+        assert_eq!(branches.len(), 2);
+        assert!(branches[0].is_head);
+        assert_eq!(branches[0].name, main);
+
+        fs::write(root.join("a.txt"), "local\n").expect("write file");
+        let error = checkout_branch(&repo, "feature").expect_err("dirty checkout");
+        assert!(checkout_error_message("feature", error).contains("uncommitted changes"));
+        assert_eq!(current_branch_name(&repo).as_deref(), Some(main.as_str()));
+        assert_eq!(fs::read_to_string(root.join("a.txt")).expect("read"), "local\n");
+
+        let error = checkout_branch(&repo, "missing").expect_err("missing branch");
+        assert_eq!(checkout_error_message("missing", error), "Branch not found: missing");
+    }
+
+    #[test]
+    fn commitless_repo_reports_unborn_branch_and_empty_log() {
+        let (root, repo) = create_temp_repo();
+        repo.set_head("refs/heads/trunk").expect("set unborn head");
+        fs::write(root.join("new.txt"), "first\n").expect("write file");
+
+        assert!(head_is_unborn(&repo));
+        assert_eq!(current_branch_name(&repo).as_deref(), Some("trunk"));
+
+        let log =
+            git_log_for_repo(&repo, 40, None, false, &GitLogFilter::default()).expect("git log");
+        assert_eq!(log.total, Some(0));
+        assert!(log.entries.is_empty());
+        assert!(log.upstream.is_none());
+
+        let diff = collect_workspace_diff(&root).expect("collect diff");
+        assert!(diff.contains("new.txt"));
+    }
+
+    #[test]
+    fn git_log_pages_with_cursor_and_rejects_stale_cursors() {
+        let (_root, repo) = create_temp_repo();
+        let filter = GitLogFilter::default();
+        let unborn = git_log_for_repo(&repo, 2, None, false, &filter).expect("unborn log");
+        assert_eq!(unborn.total, None);
+        let unborn = git_log_for_repo(&repo, 2, None, true, &filter).expect("unborn log");
+        assert_eq!(unborn.total, Some(0));
+
+        let mut shas = Vec::new();
+        for content in ["one\n", "two\n", "three\n"] {
+            shas.push(commit_file(&repo, "a.txt", content, content.trim()).to_string());
+        }
+
+        let first = git_log_for_repo(&repo, 2, None, false, &filter).expect("first page");
+        assert_eq!(first.total, None);
+        let page: Vec<&str> = first.entries.iter().map(|entry| entry.sha.as_str()).collect();
+        assert_eq!(page, [shas[2].as_str(), shas[1].as_str()]);
+        assert_eq!(first.next_cursor.as_deref(), Some(shas[1].as_str()));
+
+        let cursor = first.next_cursor.as_deref();
+        let second = git_log_for_repo(&repo, 2, cursor, true, &filter).expect("second page");
+        assert_eq!(second.total, Some(3));
+        assert_eq!(second.entries.len(), 1);
+        assert_eq!(second.entries[0].sha, shas[0]);
+        assert!(second.next_cursor.is_none());
+
+        // Rewrite history so the old tip is no longer reachable from HEAD.
+        let base = repo
+            .find_object(git2::Oid::from_str(&shas[0]).expect("oid"), None)
+            .expect("base commit");
+        repo.reset(&base, git2::ResetType::Hard, None).expect("reset");
+        let error = git_log_for_repo(&repo, 2, Some(&shas[2]), false, &filter)
+            .expect_err("stale cursor");
+        assert!(error.contains("no longer in the history"), "{error}");
+        assert!(git_log_for_repo(&repo, 2, Some("not-a-sha"), false, &filter).is_err());
+    }
+
+    #[test]
+    fn git_log_pages_from_a_merge_cursor_walk_both_parents() {
+        let (_root, repo) = create_temp_repo();
+        let tree_id = repo.treebuilder(None).and_then(|builder| builder.write()).expect("tree");
         let tree = repo.find_tree(tree_id).expect("find tree");
-        let sig =
-            git2::Signature::now("Test", "test@example.com").expect("signature");
-        repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
-            .expect("commit");
+        let commit_at = |seconds: i64, message: &str, parents: &[&git2::Commit]| {
+            let sig = git2::Signature::new("Test", "test@example.com", &git2::Time::new(seconds, 0))
+                .expect("signature");
+            let oid = repo
+                .commit(None, &sig, &sig, message, &tree, parents)
+                .expect("commit");
+            repo.find_commit(oid).expect("find commit")
+        };
+        let root_commit = commit_at(100, "root", &[]);
+        let a = commit_at(300, "a", &[&root_commit]);
+        let b = commit_at(200, "b", &[&root_commit]);
+        let merge = commit_at(400, "merge", &[&a, &b]);
+        repo.reference("refs/heads/main", merge.id(), true, "test")
+            .expect("branch");
+        repo.set_head("refs/heads/main").expect("set head");
+
+        let filter = GitLogFilter::default();
+        let first = git_log_for_repo(&repo, 1, None, false, &filter).expect("first page");
+        let page: Vec<String> = first.entries.iter().map(|entry| entry.sha.clone()).collect();
+        assert_eq!(page, [merge.id().to_string()]);
+
+        let second = git_log_for_repo(&repo, 3, first.next_cursor.as_deref(), false, &filter)
+            .expect("second page");
+        let page: Vec<String> = second.entries.iter().map(|entry| entry.sha.clone()).collect();
+        let expected = [a.id(), b.id(), root_commit.id()].map(|oid| oid.to_string());
+        assert_eq!(page, expected);
+        assert!(second.next_cursor.is_none());
+    }
+
+    #[test]
+    fn git_log_filters_by_path_and_author() {
+        let (_root, repo) = create_temp_repo();
+        let commit_as = |name: &str, path: &str, content: &str| {
+            let mut config = repo.config().expect("repo config");
+            config.set_str("user.name", name).expect("set name");
+            config.set_str("user.email", "dev@example.com").expect("set email");
+            commit_file(&repo, path, content, path).to_string()
+        };
+        let root_commit = commit_as("Alice", "src/a.txt", "one\n");
+        commit_as("Bob", "docs/readme.md", "docs\n");
+        let latest = commit_as("Bob", "src/b.txt", "two\n");
+
+        let shas = |filter: GitLogFilter| -> Vec<String> {
+            let log = git_log_for_repo(&repo, 10, None, true, &filter).expect("git log");
+            assert_eq!(log.total, Some(log.entries.len()));
+            log.entries.into_iter().map(|entry| entry.sha).collect()
+        };
+        let by_path = GitLogFilter::new(Some("src".to_string()), None);
+        assert_eq!(shas(by_path), [latest.clone(), root_commit.clone()]);
+        let by_author = GitLogFilter::new(None, Some("ALI".to_string()));
+        assert_eq!(shas(by_author), [root_commit]);
+        let both = GitLogFilter::new(Some("src".to_string()), Some("bob".to_string()));
+        assert_eq!(shas(both), [latest]);
+
+        let by_author = GitLogFilter::new(None, Some("bob".to_string()));
+        let paged = git_log_for_repo(&repo, 1, None, false, &by_author).expect("paged");
+        assert!(paged.next_cursor.is_some());
+    }
+
+    #[test]
+    fn git_log_limit_defaults_and_rejects_oversized_requests() {
+        assert_eq!(resolve_git_log_limit(None), Ok(DEFAULT_GIT_LOG_LIMIT));
+        assert_eq!(resolve_git_log_limit(Some(MAX_GIT_LOG_LIMIT)), Ok(MAX_GIT_LOG_LIMIT));
+        assert!(resolve_git_log_limit(Some(MAX_GIT_LOG_LIMIT + 1)).is_err());
+    }
+
+    #[test]
+    fn commit_details_include_parents_and_stats() {
+        let (_root, repo) = create_temp_repo();
+        let first = commit_file(&repo, "a.txt", "one\n", "init");
+        let second = commit_file(&repo, "a.txt", "one\ntwo\n", "add two\n\nbody");
+
+        let details = commit_details_for_repo(&repo, &second.to_string()).expect("details");
+        assert_eq!(details.summary, "add two");
+        assert_eq!(details.message, "add two\n\nbody");
+        assert_eq!(details.author.email, "test@example.com");
+        assert_eq!(details.parents, vec![first.to_string()]);
+        assert_eq!(details.files_changed, 1);
+        assert_eq!((details.insertions, details.deletions), (1, 0));
+    }
+
+    #[test]
+    fn commit_file_stats_report_per_file_counts() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, "b.txt", "gone\n", "add b");
+        commit_file(&repo, "a.txt", "one\ntwo\n", "add a");
+
+        fs::remove_file(root.join("b.txt")).expect("delete file");
+        stage_path_for_repo(&repo, "b.txt").expect("stage deletion");
+        let second = commit_file(&repo, "a.txt", "one\nthree\nfour\n", "edit");
+
+        let stats = commit_file_stats_for_repo(&repo, &second.to_string()).expect("stats");
+        let summary: Vec<_> = stats
+            .iter()
+            .map(|stat| (stat.path.as_str(), stat.status.as_str(), stat.additions, stat.deletions))
+            .collect();
+        assert_eq!(summary, vec![("a.txt", "M", 2, 1), ("b.txt", "D", 0, 1)]);
+    }
+
+    #[test]
+    fn project_summary_counts_tracked_text_files() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, "main.rs", "fn main() {\n}\n", "add main");
+        commit_file(&repo, "lib.rs", "pub fn lib() {}", "add lib");
+        commit_file(&repo, "README.md", "# Readme\n", "add readme");
+        let head = commit_file(&repo, "logo.png", [0x89, b'P', b'N', b'G', 0, 1], "add logo");
+        fs::write(root.join("untracked.rs"), "ignored\n").expect("write file");
+
+        let summary = project_summary_for_repo(&repo).expect("summary");
+        assert_eq!(summary.tracked_files, 4);
+        assert_eq!(summary.total_lines, 4);
+        let languages: Vec<_> = summary
+            .languages
+            .iter()
+            .map(|stat| (stat.extension.as_str(), stat.files, stat.lines))
+            .collect();
+        assert_eq!(languages, vec![("rs", 2, 3), ("md", 1, 1)]);
+        let head_time = repo.find_commit(head).expect("head commit").time().seconds();
+        assert_eq!(summary.latest_commit_timestamp, Some(head_time));
+    }
+
+    #[test]
+    fn count_text_lines_streams_past_the_sniff_window() {
+        let text: String = (0..3000).map(|line| format!("line {line}\n")).collect();
+        assert_eq!(count_text_lines(text.as_bytes()), Some(3000));
+        assert_eq!(count_text_lines(&b"one\ntwo"[..]), Some(2));
+        assert_eq!(count_text_lines(&b""[..]), Some(0));
+
+        let mut late_nul = vec![b'a'; BINARY_SNIFF_BYTES];
+        late_nul.extend_from_slice(b"\0\n");
+        assert_eq!(count_text_lines(late_nul.as_slice()), Some(1));
+        assert_eq!(count_text_lines(&b"bin\0ary\n"[..]), None);
+    }
+
+    #[test]
+    fn diff_hunk_pages_walk_every_hunk() {
+        let (root, repo) = create_temp_repo();
+        let original: String = (0..100).map(|line| format!("line {line}\n")).collect();
+        commit_file(&repo, "big.txt", &original, "init");
+
+        let edited = original
+            .replace("line 10\n", "changed 10\n")
+            .replace("line 50\n", "changed 50\n")
+            .replace("line 90\n", "changed 90\n");
+        fs::write(root.join("big.txt"), edited).expect("edit file");
+
+        let first = diff_hunk_page_for_repo(&repo, "big.txt", 0, 2).expect("first page");
+        assert_eq!(first.total_hunks, 3);
+        assert_eq!(first.hunks.len(), 2);
+        assert!(first.hunks[0].starts_with("@@"));
+        assert!(first.hunks[0].contains("-line 10\n+changed 10\n"));
+        assert_eq!(first.next_hunk_cursor, Some(2));
+
+        let second = diff_hunk_page_for_repo(&repo, "big.txt", 2, 2).expect("second page");
+        assert_eq!(second.hunks.len(), 1);
+        assert!(second.hunks[0].contains("+changed 90\n"));
+        assert_eq!(second.next_hunk_cursor, None);
+
+        let clean = diff_hunk_page_for_repo(&repo, "missing.txt", 0, 2).expect("no diff");
+        assert_eq!(clean.total_hunks, 0);
+    }
+
+    #[test]
+    fn repo_conventions_report_dominant_style() {
+        let (root, repo) = create_temp_repo();
+        fs::write(root.join("a.rs"), "fn a() {\n    one();\n        two();\n}\n")
+            .expect("write");
+        fs::write(root.join("b.rs"), "fn b() {\n    three();\n}\n").expect("write");
+        fs::write(root.join("c.txt"), "windows\r\nfile\r\n").expect("write");
+        fs::write(
+            root.join(".editorconfig"),
+            "root = true\n\n[*]\nend_of_line = LF\nindent_style = space\n\n[*.md]\nindent_size = 2\n",
+        )
+        .expect("write");
+        let mut index = repo.index().expect("repo index");
+        for path in ["a.rs", "b.rs", "c.txt", ".editorconfig"] {
+            index.add_path(Path::new(path)).expect("add path");
+        }
+        index.write().expect("write index");
+
+        let conventions = repo_conventions_for_repo(&repo).expect("conventions");
+        assert_eq!(conventions.sampled_files, 4);
+        assert_eq!(conventions.crlf_files, 1);
+        assert_eq!(conventions.line_ending.as_deref(), Some("lf"));
+        assert_eq!(conventions.indent_style.as_deref(), Some("spaces"));
+        assert_eq!(conventions.indent_width, Some(4));
+        let editorconfig = conventions.editorconfig.expect("editorconfig");
+        assert_eq!(editorconfig.end_of_line.as_deref(), Some("lf"));
+        assert_eq!(editorconfig.indent_style.as_deref(), Some("space"));
+        assert_eq!(editorconfig.indent_size, None);
+    }
+
+    #[test]
+    fn staged_files_list_paths_and_change_types() {
+        let (root, repo) = create_temp_repo();
+        fs::write(root.join("untracked.txt"), "ignored\n").expect("write file");
+        fs::write(root.join("new.txt"), "new\n").expect("write file");
+        let mut index = repo.index().expect("repo index");
+        index.add_path(Path::new("new.txt")).expect("add path");
+        index.write().expect("write index");
+        let staged = staged_files_for_repo(&repo).expect("unborn staged files");
+        assert_eq!(staged.len(), 1);
+        assert_eq!((staged[0].path.as_str(), staged[0].status.as_str()), ("new.txt", "A"));
+
+        fs::write(root.join("gone.txt"), "gone\n").expect("write file");
+        index.add_path(Path::new("gone.txt")).expect("add path");
+        index.write().expect("write index");
+        commit_file(&repo, "old.txt", "one\ntwo\nthree\nfour\n", "init");
+
+        fs::rename(root.join("old.txt"), root.join("moved.txt")).expect("rename file");
+        fs::write(root.join("new.txt"), "changed\n").expect("write file");
+        index.remove_path(Path::new("old.txt")).expect("remove old path");
+        index.add_path(Path::new("moved.txt")).expect("add new path");
+        index.add_path(Path::new("new.txt")).expect("add path");
+        index.remove_path(Path::new("gone.txt")).expect("remove path");
+        index.write().expect("write index");
+
+        let mut staged = staged_files_for_repo(&repo).expect("staged files");
+        staged.sort_by(|a, b| a.path.cmp(&b.path));
+        let summary: Vec<(&str, &str, Option<&str>)> = staged
+            .iter()
+            .map(|file| (file.path.as_str(), file.status.as_str(), file.rename_from.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("gone.txt", "D", None),
+                ("moved.txt", "R", Some("old.txt")),
+                ("new.txt", "M", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn list_stashes_reports_newest_first() {
+        let (root, mut repo) = create_temp_repo();
+        assert!(list_stashes_for_repo(&mut repo).expect("no stashes").is_empty());
+
+        commit_file(&repo, "a.txt", "one\n", "init");
+        let sig = git2::Signature::now("Test", "test@example.com").expect("signature");
+
+        fs::write(root.join("a.txt"), "two\n").expect("write file");
+        repo.stash_save(&sig, "first", None).expect("stash first");
+        fs::write(root.join("a.txt"), "three\n").expect("write file");
+        let second = repo.stash_save(&sig, "second", None).expect("stash second");
+
+        let stashes = list_stashes_for_repo(&mut repo).expect("stashes");
+        assert_eq!(stashes.len(), 2);
+        assert_eq!(stashes[0].index, 0);
+        assert!(stashes[0].message.contains("second"));
+        assert_eq!(stashes[0].sha, second.to_string());
+        assert!(stashes[1].message.contains("first"));
+    }
+
+    #[test]
+    fn stash_push_and_pop_round_trip_changes() {
+        let (root, mut repo) = create_temp_repo();
+        let mut config = repo.config().expect("repo config");
+        config.set_str("user.name", "Test").expect("set name");
+        config.set_str("user.email", "test@example.com").expect("set email");
+        commit_file(&repo, "a.txt", "one\n", "init");
+
+        let error = stash_push_for_repo(&mut repo, None).expect_err("clean tree");
+        assert_eq!(error, "No local changes to stash");
+
+        fs::write(root.join("a.txt"), "two\n").expect("write file");
+        let sha = stash_push_for_repo(&mut repo, Some("wip")).expect("stash push");
+        assert_eq!(fs::read_to_string(root.join("a.txt")).expect("read"), "one\n");
+        let stashes = list_stashes_for_repo(&mut repo).expect("stashes");
+        assert_eq!(stashes.len(), 1);
+        assert_eq!(stashes[0].sha, sha);
+        assert!(stashes[0].message.contains("wip"));
+
+        assert!(stash_pop_for_repo(&mut repo, 1).is_err());
+        stash_pop_for_repo(&mut repo, 0).expect("stash pop");
+        assert_eq!(fs::read_to_string(root.join("a.txt")).expect("read"), "two\n");
+        assert!(list_stashes_for_repo(&mut repo).expect("stashes").is_empty());
+    }
+
+    #[test]
+    fn working_diffs_split_staged_and_unstaged_changes() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, "a.txt", "one\n", "init");
+
+        fs::write(root.join("a.txt"), "two\n").expect("write file");
+        stage_path_for_repo(&repo, "a.txt").expect("stage file");
+        fs::write(root.join("a.txt"), "three\n").expect("write file");
+
+        let staged = working_diffs_for_repo(&repo, &root, GitDiffMode::Staged, 3).expect("staged");
+        assert_eq!(staged.len(), 1);
+        assert_eq!(staged[0].mode, Some(GitDiffMode::Staged));
+        assert!(staged[0].diff.contains("-one"));
+        assert!(staged[0].diff.contains("+two"));
+
+        let unstaged =
+            working_diffs_for_repo(&repo, &root, GitDiffMode::Unstaged, 3).expect("unstaged");
+        assert_eq!(unstaged.len(), 1);
+        assert_eq!(unstaged[0].mode, Some(GitDiffMode::Unstaged));
+        assert!(unstaged[0].diff.contains("-two"));
+        assert!(unstaged[0].diff.contains("+three"));
+
+        let combined =
+            working_diffs_for_repo(&repo, &root, GitDiffMode::Combined, 3).expect("combined");
+        assert_eq!(combined.len(), 1);
+        assert!(combined[0].diff.contains("-one"));
+        assert!(combined[0].diff.contains("+three"));
+        assert!(!combined[0].diff.contains("two"));
+    }
+
+    #[test]
+    fn working_diffs_honor_context_lines() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, "a.txt", "1\n2\n3\n4\n5\n6\n7\n", "init");
+        fs::write(root.join("a.txt"), "1\n2\n3\nfour\n5\n6\n7\n").expect("write file");
+
+        let default_diffs = working_diffs_for_repo(
+            &repo,
+            &root,
+            GitDiffMode::Combined,
+            DEFAULT_DIFF_CONTEXT_LINES,
+        )
+        .expect("default context");
+        assert!(default_diffs[0].diff.contains("\n 1\n"));
+        assert!(default_diffs[0].diff.contains("\n 7\n"));
+
+        let tight =
+            working_diffs_for_repo(&repo, &root, GitDiffMode::Combined, 0).expect("no context");
+        assert!(tight[0].diff.contains("-4\n+four\n"));
+        assert!(!tight[0].diff.contains("\n 3\n"));
+        assert!(!tight[0].diff.contains("\n 5\n"));
+    }
+
+    #[test]
+    fn working_diffs_read_image_sides_from_the_compared_trees() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, "a.png", [1u8], "init");
+        fs::write(root.join("a.png"), [2u8]).expect("write image");
+        stage_path_for_repo(&repo, "a.png").expect("stage image");
+        fs::write(root.join("a.png"), [3u8]).expect("write image");
+
+        let sides = |mode| {
+            let diffs = working_diffs_for_repo(&repo, &root, mode, DEFAULT_DIFF_CONTEXT_LINES)
+                .expect("diffs");
+            assert!(diffs[0].is_image);
+            (diffs[0].old_image_data.clone(), diffs[0].new_image_data.clone())
+        };
+        let encoded = |byte: u8| Some(STANDARD.encode([byte]));
+        assert_eq!(sides(GitDiffMode::Staged), (encoded(1), encoded(2)));
+        assert_eq!(sides(GitDiffMode::Unstaged), (encoded(2), encoded(3)));
+        assert_eq!(sides(GitDiffMode::Combined), (encoded(1), encoded(3)));
+    }
+
+    #[test]
+    fn working_diffs_mark_binary_files_without_patch_text() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, "data.bin", [0u8, 1, 2, 3], "add data");
+        commit_file(&repo, "a.txt", "one\n", "add text");
+
+        fs::write(root.join("data.bin"), [0u8, 4, 5, 6]).expect("write file");
+        fs::write(root.join("a.txt"), "two\n").expect("write file");
+
+        let diffs =
+            working_diffs_for_repo(&repo, &root, GitDiffMode::Combined, 3).expect("combined");
+        let binary = diffs
+            .iter()
+            .find(|diff| diff.path == "data.bin")
+            .expect("binary diff");
+        assert!(binary.is_binary);
+        assert!(!binary.is_image);
+        assert!(binary.diff.is_empty());
+        let text = diffs.iter().find(|diff| diff.path == "a.txt").expect("text diff");
+        assert!(!text.is_binary);
+        assert!(text.diff.contains("+two"));
+    }
+
+    #[test]
+    fn git_file_diff_covers_modified_new_and_deleted_files() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, "src/lib.rs", "one\n", "add lib");
+        commit_file(&repo, "gone.txt", "gone\n", "add gone");
+        commit_file(&repo, "same.txt", "same\n", "add same");
+
+        fs::write(root.join("src/lib.rs"), "two\n").expect("write file");
+        fs::write(root.join("new.txt"), "fresh\n").expect("write file");
+        fs::remove_file(root.join("gone.txt")).expect("delete file");
+
+        let modified = file_diff_for_repo(&repo, &root, "src\\lib.rs").expect("modified diff");
+        assert_eq!(modified.path, "src/lib.rs");
+        assert!(modified.diff.contains("-one"));
+        assert!(modified.diff.contains("+two"));
+
+        let added = file_diff_for_repo(&repo, &root, "./new.txt").expect("new file diff");
+        assert_eq!(added.path, "new.txt");
+        assert!(added.diff.contains("+fresh"));
+        assert!(file_diff_for_repo(&repo, &root, " ").is_err());
+
+        let deleted = file_diff_for_repo(&repo, &root, "gone.txt").expect("deleted diff");
+        assert!(deleted.diff.contains("-gone"));
+
+        let error = file_diff_for_repo(&repo, &root, "same.txt").expect_err("unchanged file");
+        assert_eq!(error, "file not in working tree changes");
+    }
+
+    #[test]
+    fn prepare_commit_requires_staged_changes() {
+        let (root, repo) = create_temp_repo();
+        let index_only = CommitStaging::Index;
+        fs::write(root.join("a.txt"), "one\n").expect("write file");
+        assert_eq!(
+            prepare_commit_for_repo(&repo, "init", &index_only).expect_err("empty index"),
+            "Nothing staged to commit"
+        );
+
+        stage_path_for_repo(&repo, "a.txt").expect("stage file");
+        assert!(prepare_commit_for_repo(&repo, "  ", &index_only).is_err());
+        let mut config = repo.config().expect("repo config");
+        config.set_str("user.name", "Test").expect("set name");
+        config.set_str("user.email", "test@example.com").expect("set email");
+        prepare_commit_for_repo(&repo, "init", &index_only).expect("ready");
+
+        commit_file(&repo, "a.txt", "one\n", "init");
+        assert_eq!(
+            prepare_commit_for_repo(&repo, "again", &index_only).expect_err("nothing staged"),
+            "Nothing staged to commit"
+        );
+        fs::write(root.join("a.txt"), "two\n").expect("write file");
+        stage_path_for_repo(&repo, "a.txt").expect("stage file");
+        prepare_commit_for_repo(&repo, "second", &index_only).expect("ready");
+    }
+
+    #[test]
+    fn reflog_lists_head_moves_newest_first() {
+        let (_root, repo) = create_temp_repo();
+        let mut config = repo.config().expect("repo config");
+        config.set_str("user.name", "Test").expect("set name");
+        config.set_str("user.email", "test@example.com").expect("set email");
+        let first = commit_file(&repo, "a.txt", "one\n", "first").to_string();
+        let second = commit_file(&repo, "a.txt", "two\n", "second").to_string();
+
+        let entries = reflog_for_repo(&repo, Some(""), 10).expect("reflog");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].old_sha, first);
+        assert_eq!(entries[0].new_sha, second);
+        assert!(entries[0].message.contains("second"));
+        assert_eq!(entries[0].committer.name, "Test");
+        assert_eq!(entries[1].new_sha, first);
+        assert_eq!(reflog_for_repo(&repo, None, 1).expect("limited").len(), 1);
+
+        let branch = repo.head().expect("head").shorthand().expect("branch").to_string();
+        let branch_entries = reflog_for_repo(&repo, Some(&branch), 10).expect("branch reflog");
+        assert_eq!(branch_entries.len(), 2);
+        assert_eq!(branch_entries[0].new_sha, second);
+        assert!(reflog_for_repo(&repo, Some("no-such-branch"), 10).is_err());
+    }
+
+    #[test]
+    fn operation_state_reports_merge_conflicts() {
+        let (_root, repo) = create_temp_repo();
+        commit_file(&repo, "a.txt", "base\n", "base");
+        let clean = operation_state_for_repo(&repo).expect("clean state");
+        assert_eq!(clean.state, "clean");
+        assert!(!clean.in_progress);
+        assert!(clean.detail.is_none());
+
+        let head = repo.head().expect("head");
+        let main_ref = head.name().expect("head name").to_string();
+        let base = head.peel_to_commit().expect("base");
+        repo.branch("other", &base, false).expect("create branch");
+        commit_file(&repo, "a.txt", "ours\n", "ours");
+        repo.set_head("refs/heads/other").expect("switch branch");
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .expect("checkout other");
+        let theirs = commit_file(&repo, "a.txt", "theirs\n", "theirs");
+        repo.set_head(&main_ref).expect("switch back");
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .expect("checkout back");
+
+        let annotated = repo.find_annotated_commit(theirs).expect("annotated commit");
+        repo.merge(&[&annotated], None, None).expect("merge");
+        let merging = operation_state_for_repo(&repo).expect("merge state");
+        assert_eq!(merging.state, "merge");
+        assert!(merging.in_progress);
+        assert_eq!(merging.detail, Some(format!("Merging {}", &theirs.to_string()[..7])));
+        assert_eq!(merging.conflicted_paths, vec!["a.txt".to_string()]);
+    }
+
+    #[test]
+    fn reset_to_commit_applies_each_mode() {
+        let (root, repo) = create_temp_repo();
+        let base = commit_file(&repo, "a.txt", "one\n", "base").to_string();
+        fs::write(root.join("a.txt"), "two\n").expect("write file");
+        stage_path_for_repo(&repo, "a.txt").expect("stage file");
+        let tip = commit_file(&repo, "b.txt", "b\n", "tip").to_string();
+
+        let result =
+            reset_to_commit_for_repo(&repo, &base, GitResetMode::Soft, false).expect("soft");
+        assert_eq!(result.sha, base);
+        assert_eq!(result.affected_files, 2);
+        assert_eq!(staged_files_for_repo(&repo).expect("staged").len(), 2);
+
+        reset_to_commit_for_repo(&repo, &tip, GitResetMode::Soft, false).expect("back to tip");
+        let result =
+            reset_to_commit_for_repo(&repo, &base, GitResetMode::Mixed, false).expect("mixed");
+        assert_eq!(result.affected_files, 2);
+        assert!(staged_files_for_repo(&repo).expect("staged").is_empty());
+        assert_eq!(fs::read_to_string(root.join("a.txt")).expect("read"), "two\n");
+
+        let error = reset_to_commit_for_repo(&repo, &base, GitResetMode::Hard, false)
+            .expect_err("unconfirmed hard reset");
+        assert!(error.contains("confirm"));
+        let result =
+            reset_to_commit_for_repo(&repo, &base, GitResetMode::Hard, true).expect("hard");
+        assert_eq!(result.affected_files, 1);
+        assert_eq!(fs::read_to_string(root.join("a.txt")).expect("read"), "one\n");
+        assert!(reset_to_commit_for_repo(&repo, "missing", GitResetMode::Soft, false).is_err());
+    }
+
+    #[test]
+    fn changed_files_between_lists_tree_deltas() {
+        let (root, repo) = create_temp_repo();
+        fs::write(root.join("a.txt"), "one\n").expect("write file");
+        stage_path_for_repo(&repo, "a.txt").expect("stage file");
+        let base = commit_file(&repo, "b.txt", "gone\n", "base").to_string();
+
+        fs::write(root.join("a.txt"), "two\n").expect("write file");
+        fs::remove_file(root.join("b.txt")).expect("delete file");
+        for path in ["a.txt", "b.txt"] {
+            stage_path_for_repo(&repo, path).expect("stage path");
+        }
+        commit_file(&repo, "c.txt", "new\n", "head");
+        fs::write(root.join("d.txt"), "untracked\n").expect("write file");
+
+        let files = changed_files_between_for_repo(&repo, &base, "HEAD").expect("changed files");
+        let entries: Vec<(&str, &str)> = files
+            .iter()
+            .map(|file| (file.path.as_str(), file.status.as_str()))
+            .collect();
+        assert_eq!(entries, vec![("a.txt", "M"), ("b.txt", "D"), ("c.txt", "A")]);
+        assert!(changed_files_between_for_repo(&repo, "HEAD", "HEAD")
+            .expect("same revision")
+            .is_empty());
+        assert!(changed_files_between_for_repo(&repo, "missing", "HEAD").is_err());
+    }
+
+    #[test]
+    fn diff_against_branch_compares_working_tree_to_branch_tip() {
+        let (root, repo) = create_temp_repo();
+        let base = commit_file(&repo, "a.txt", "base\n", "base");
+        let base_commit = repo.find_commit(base).expect("base commit");
+        repo.branch("base-branch", &base_commit, false)
+            .expect("create branch");
+
+        commit_file(&repo, "a.txt", "committed\n", "second");
+        fs::write(root.join("b.txt"), "untracked\n").expect("write file");
+
+        let diffs = branch_diffs_for_repo(&repo, &root, "base-branch").expect("branch diff");
+        let paths: Vec<&str> = diffs.iter().map(|diff| diff.path.as_str()).collect();
+        assert_eq!(paths, vec!["a.txt", "b.txt"]);
+        assert!(diffs[0].diff.contains("-base"));
+        assert!(diffs[0].diff.contains("+committed"));
+        assert!(branch_diffs_for_repo(&repo, &root, "missing").is_err());
+    }
+
+    #[test]
+    fn working_patch_id_matches_identical_changes() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, "a.txt", "one\ntwo\n", "init");
+        assert_eq!(working_patch_id_for_repo(&repo).expect("clean"), None);
+
+        fs::write(root.join("a.txt"), "one\nchanged\n").expect("write file");
+        let first = working_patch_id_for_repo(&repo).expect("patch id").expect("changes");
+
+        fs::write(root.join("a.txt"), "one\ntwo\n").expect("write file");
+        assert_eq!(working_patch_id_for_repo(&repo).expect("reverted"), None);
+        fs::write(root.join("a.txt"), "one\nchanged\n").expect("write file");
+        let rerun = working_patch_id_for_repo(&repo).expect("patch id").expect("changes");
+        assert_eq!(first, rerun);
+
+        fs::write(root.join("a.txt"), "one\nother\n").expect("write file");
+        let different = working_patch_id_for_repo(&repo).expect("patch id").expect("changes");
+        assert_ne!(first, different);
+    }
+
+    #[test]
+    fn stage_and_unstage_single_paths() {
+        let (root, repo) = create_temp_repo();
+        fs::create_dir_all(root.join("dir")).expect("create dir");
+        fs::write(root.join("dir/new.txt"), "new\n").expect("write file");
+        let status = stage_path_for_repo(&repo, "dir\\new.txt").expect("stage new file");
+        assert_eq!(status.path, "dir/new.txt");
+        assert_eq!(status.index_status.as_deref(), Some("A"));
+        let status = unstage_path_for_repo(&repo, "dir/new.txt").expect("unstage on unborn head");
+        assert_eq!(status.index_status, None);
+        assert_eq!(status.worktree_status.as_deref(), Some("A"));
+
+        commit_file(&repo, "tracked.txt", "one\n", "init");
+
+        fs::write(root.join("tracked.txt"), "two\n").expect("write file");
+        let status = stage_path_for_repo(&repo, "tracked.txt").expect("stage modification");
+        assert_eq!(status.index_status.as_deref(), Some("M"));
+        let status = unstage_path_for_repo(&repo, "tracked.txt").expect("unstage modification");
+        assert_eq!(status.index_status, None);
+        assert_eq!(status.worktree_status.as_deref(), Some("M"));
+
+        fs::remove_file(root.join("tracked.txt")).expect("delete file");
+        let status = stage_path_for_repo(&repo, "tracked.txt").expect("stage deletion");
+        assert_eq!(status.index_status.as_deref(), Some("D"));
+        let status = unstage_path_for_repo(&repo, "tracked.txt").expect("unstage deletion");
+        assert_eq!(status.worktree_status.as_deref(), Some("D"));
+    }
+
+    #[test]
+    fn update_file_in_index_moves_both_sides_of_a_rename() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, "a.txt", "hello\n", "init");
+        fs::rename(root.join("a.txt"), root.join("b.txt")).expect("rename file");
+        let mut index = repo.index().expect("repo index");
+        index.remove_path(Path::new("a.txt")).expect("remove old path");
+        index.add_path(Path::new("b.txt")).expect("add new path");
+        index.write().expect("write index");
+
+        let status =
+            update_file_in_index(&root, "b.txt", unstage_path_for_repo).expect("unstage rename");
+        assert_eq!(status.index_status, None);
+        index.read(true).expect("reload index");
+        assert!(index.get_path(Path::new("a.txt"), 0).is_some());
+        assert!(index.get_path(Path::new("b.txt"), 0).is_none());
+
+        let status =
+            update_file_in_index(&root, "b.txt", stage_path_for_repo).expect("stage rename");
+        assert!(status.index_status.is_some());
+        index.read(true).expect("reload index");
+        assert!(index.get_path(Path::new("a.txt"), 0).is_none());
+        assert!(index.get_path(Path::new("b.txt"), 0).is_some());
+    }
+
+    #[test]
+    fn stash_apply_keeps_the_stash() {
+        let (root, mut repo) = create_temp_repo();
+        commit_file(&repo, "a.txt", "one\n", "init");
+        let sig = git2::Signature::now("Test", "test@example.com").expect("signature");
+
+        fs::write(root.join("a.txt"), "two\n").expect("write file");
+        repo.stash_save(&sig, "wip", None).expect("stash");
+        assert_eq!(fs::read_to_string(root.join("a.txt")).expect("read"), "one\n");
+        assert!(stash_apply_for_repo(&mut repo, 3).is_err());
+
+        stash_apply_for_repo(&mut repo, 0).expect("apply stash");
+        assert_eq!(fs::read_to_string(root.join("a.txt")).expect("read"), "two\n");
+        assert_eq!(list_stashes_for_repo(&mut repo).expect("stashes").len(), 1);
+
+        fs::write(root.join("a.txt"), "local\n").expect("write file");
+        assert!(stash_apply_for_repo(&mut repo, 0).is_err());
+        assert_eq!(list_stashes_for_repo(&mut repo).expect("stashes").len(), 1);
+    }
+
+    #[test]
+    fn stash_apply_refuses_stash_that_conflicts_with_head() {
+        let (root, mut repo) = create_temp_repo();
+        let mut config = repo.config().expect("repo config");
+        config.set_str("user.name", "Test").expect("set name");
+        config.set_str("user.email", "test@example.com").expect("set email");
+        commit_file(&repo, "a.txt", "one\n", "init");
+
+        fs::write(root.join("a.txt"), "stashed\n").expect("write file");
+        stash_push_for_repo(&mut repo, Some("wip")).expect("stash push");
+        commit_file(&repo, "a.txt", "committed\n", "diverge");
+
+        let error = stash_apply_for_repo(&mut repo, 0).expect_err("conflicting stash");
+        assert!(error.contains("a.txt"), "{error}");
+        assert_eq!(fs::read_to_string(root.join("a.txt")).expect("read"), "committed\n");
+        assert!(conflicted_paths(&repo).expect("conflicts").is_empty());
+        assert_eq!(list_stashes_for_repo(&mut repo).expect("stashes").len(), 1);
+    }
+
+    #[test]
+    fn delete_workspace_file_stages_deletion_on_request() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, "a.txt", "a\n", "add a");
+        commit_file(&repo, "b.txt", "b\n", "add b");
+        commit_file(&repo, "dir/c.txt", "c\n", "add c");
+
+        let status = delete_workspace_file_inner(&root, Some(&repo), "a.txt", true, false)
+            .expect("delete staged");
+        assert_eq!(status.index_status.as_deref(), Some("D"));
+        let status = delete_workspace_file_inner(&root, Some(&repo), "b.txt", false, false)
+            .expect("delete unstaged");
+        assert_eq!(status.index_status, None);
+        assert_eq!(status.worktree_status.as_deref(), Some("D"));
+
+        let error = delete_workspace_file_inner(&root, Some(&repo), "dir", true, false)
+            .expect_err("directory without recursive");
+        assert!(error.contains("directory"));
+        assert!(delete_workspace_file_inner(&root, Some(&repo), ".git", true, true).is_err());
+        assert!(delete_workspace_file_inner(&root, Some(&repo), ".GIT", true, true).is_err());
+        assert!(delete_workspace_file_inner(&root, Some(&repo), "dir/.Git", true, true).is_err());
+        let nested = Repository::init(root.join("app")).expect("init nested repo");
+        assert!(touches_git_metadata(&root.join("app"), "app", Some(&nested)));
+        assert!(!touches_git_metadata(&root.join("app/src"), "app/src", Some(&nested)));
+        assert!(delete_workspace_file_inner(&root, Some(&repo), "../x", true, true).is_err());
+        let status = delete_workspace_file_inner(&root, Some(&repo), "dir", true, true)
+            .expect("delete directory");
+        assert_eq!(status.index_status.as_deref(), Some("D"));
+        assert!(!root.join("dir").exists());
+    }
+
+    #[test]
+    fn create_workspace_file_refuses_to_overwrite() {
+        let (root, repo) = create_temp_repo();
+        let status =
+            create_workspace_file_inner(&root, Some(&repo), "src/new.rs", "fn main() {}\n", false)
+                .expect("create file");
+        assert_eq!(status.path, "src/new.rs");
+        assert_eq!(status.worktree_status.as_deref(), Some("A"));
+        assert_eq!(
+            fs::read_to_string(root.join("src/new.rs")).expect("read file"),
+            "fn main() {}\n"
+        );
+
+        let error = create_workspace_file_inner(&root, Some(&repo), "src/new.rs", "", false)
+            .expect_err("existing file");
+        assert!(error.contains("already exists"));
+        create_workspace_file_inner(&root, Some(&repo), "src/new.rs", "updated\n", true)
+            .expect("overwrite file");
+        assert_eq!(
+            fs::read_to_string(root.join("src/new.rs")).expect("read file"),
+            "updated\n"
+        );
+        assert!(create_workspace_file_inner(&root, None, "/etc/evil", "", true).is_err());
+        let leftovers = fs::read_dir(root.join("src"))
+            .expect("read dir")
+            .filter(|entry| {
+                entry
+                    .as_ref()
+                    .is_ok_and(|entry| entry.file_name().to_string_lossy().ends_with(".tmp"))
+            })
+            .count();
+        assert_eq!(leftovers, 0);
+    }
+
+    #[test]
+    fn move_workspace_file_stages_a_rename() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, "old.txt", "one\ntwo\nthree\nfour\n", "init");
+        fs::write(root.join("taken.txt"), "taken\n").expect("write file");
+
+        let error = move_workspace_file_inner(&root, Some(&repo), "old.txt", "taken.txt", false)
+            .expect_err("existing destination");
+        assert!(error.contains("already exists"));
+        let escape = move_workspace_file_inner(&root, Some(&repo), "old.txt", "../x.txt", false);
+        assert!(escape.is_err());
+
+        let status =
+            move_workspace_file_inner(&root, Some(&repo), "old.txt", "nested/new.txt", false)
+                .expect("move file");
+        assert!(!root.join("old.txt").exists());
+        assert!(root.join("nested/new.txt").exists());
+        assert_eq!(status.path, "nested/new.txt");
+        assert_eq!(status.index_status.as_deref(), Some("R"));
+        assert_eq!(status.rename_from.as_deref(), Some("old.txt"));
+        assert_eq!(status.worktree_status, None);
+
+        let status = move_workspace_file_inner(&root, None, "taken.txt", "other.txt", false)
+            .expect("move without repo");
+        assert_eq!(status.index_status, None);
+        assert!(root.join("other.txt").exists());
+    }
+
+    #[test]
+    fn head_info_is_none_until_first_commit() {
+        let (_root, repo) = create_temp_repo();
+        assert!(head_info_for_repo(&repo).expect("unborn head").is_none());
+
+        let oid = commit_file(&repo, "a.txt", "hello\n", "first commit");
+
+        let head = head_info_for_repo(&repo).expect("head").expect("head entry");
+        assert_eq!(head.sha, oid.to_string());
+        assert_eq!(head.summary, "first commit");
+        assert_eq!(head.author, "Test");
+        let commit = repo.find_commit(oid).expect("find commit");
+        assert_eq!(head.timestamp, commit.time().seconds());
+    }
+
+    #[test]
+    fn action_paths_for_file_expands_renames() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, "a.txt", "hello\n", "init");
 
         fs::rename(root.join("a.txt"), root.join("b.txt")).expect("rename file");
 
@@ -1493,4 +4839,193 @@ mod tests {
         let paths = action_paths_for_file(&root, "b.txt");
         assert_eq!(paths, vec!["a.txt".to_string(), "b.txt".to_string()]);
     }
+
+    #[test]
+    fn git_status_reports_ahead_behind_against_upstream() {
+        let (_root, repo) = create_temp_repo();
+        let base = commit_file(&repo, "a.txt", "one\n", "base");
+
+        let status = git_status_for_repo(&repo).expect("git status");
+        assert!(status["ahead"].is_null());
+        assert!(status["behind"].is_null());
+
+        repo.remote("origin", "https://example.com/repo.git")
+            .expect("add remote");
+        let branch_name = current_branch_name(&repo).expect("branch name");
+        repo.reference(
+            &format!("refs/remotes/origin/{branch_name}"),
+            base,
+            true,
+            "test upstream",
+        )
+        .expect("create remote ref");
+        let mut branch = repo
+            .find_branch(&branch_name, BranchType::Local)
+            .expect("local branch");
+        branch
+            .set_upstream(Some(&format!("origin/{branch_name}")))
+            .expect("set upstream");
+        commit_file(&repo, "a.txt", "two\n", "local 1");
+        commit_file(&repo, "a.txt", "three\n", "local 2");
+
+        let status = git_status_for_repo(&repo).expect("git status");
+        assert_eq!(status["ahead"], 2);
+        assert_eq!(status["behind"], 0);
+
+        repo.find_reference(&format!("refs/remotes/origin/{branch_name}"))
+            .expect("remote ref")
+            .delete()
+            .expect("delete remote ref");
+        let status = git_status_for_repo(&repo).expect("status without upstream ref");
+        assert!(status["ahead"].is_null());
+        assert!(status["behind"].is_null());
+    }
+
+    #[test]
+    fn git_status_reports_staged_rename_with_source_path() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, "old.txt", "one\ntwo\nthree\nfour\n", "init");
+
+        fs::rename(root.join("old.txt"), root.join("new.txt")).expect("rename file");
+        let mut index = repo.index().expect("repo index");
+        index.remove_path(Path::new("old.txt")).expect("remove old path");
+        index.add_path(Path::new("new.txt")).expect("add new path");
+        index.write().expect("write index");
+
+        let status = git_status_for_repo(&repo).expect("git status");
+        let staged: Vec<GitFileStatus> =
+            serde_json::from_value(status["stagedFiles"].clone()).expect("staged files");
+        assert_eq!(staged.len(), 1);
+        assert_eq!(staged[0].path, "new.txt");
+        assert_eq!(staged[0].status, "R");
+        assert_eq!(staged[0].rename_from.as_deref(), Some("old.txt"));
+        assert_eq!((staged[0].additions, staged[0].deletions), (0, 0));
+
+        let files: Vec<GitFileStatus> =
+            serde_json::from_value(status["files"].clone()).expect("files");
+        assert_eq!(files[0].path, "new.txt");
+        assert_eq!(files[0].rename_from.as_deref(), Some("old.txt"));
+    }
+
+    #[test]
+    fn git_status_reports_unstaged_rename_with_source_path() {
+        let (root, repo) = create_temp_repo();
+        commit_file(&repo, "old.txt", "one\ntwo\nthree\nfour\n", "init");
+
+        fs::rename(root.join("old.txt"), root.join("new.txt")).expect("rename file");
+
+        let status = git_status_for_repo(&repo).expect("git status");
+        let unstaged: Vec<GitFileStatus> =
+            serde_json::from_value(status["unstagedFiles"].clone()).expect("unstaged files");
+        assert_eq!(unstaged.len(), 1);
+        assert_eq!(unstaged[0].path, "new.txt");
+        assert_eq!(unstaged[0].status, "R");
+        assert_eq!(unstaged[0].rename_from.as_deref(), Some("old.txt"));
+
+        let staged = status["stagedFiles"].as_array().expect("staged files");
+        assert!(staged.is_empty());
+    }
+
+    #[test]
+    fn stream_git_status_batches_entries_with_running_totals() {
+        let (root, _repo) = create_temp_repo();
+        for index in 0..5 {
+            fs::write(root.join(format!("file-{index}.txt")), "line\n").expect("write file");
+        }
+        let repo = Repository::open(&root).expect("open repo");
+
+        let mut chunks = Vec::new();
+        stream_git_status_for_repo(&repo, 2, |chunk| {
+            chunks.push(chunk);
+            Ok(())
+        })
+        .expect("stream status");
+
+        let sizes: Vec<_> = chunks.iter().map(|chunk| chunk.files.len()).collect();
+        assert_eq!(sizes, vec![2, 2, 1]);
+        let totals: Vec<_> = chunks.iter().map(|chunk| chunk.total_additions).collect();
+        assert_eq!(totals, vec![2, 4, 5]);
+        assert!(chunks[..2].iter().all(|chunk| !chunk.done && chunk.branch_name.is_none()));
+        let last = chunks.last().expect("final chunk");
+        assert!(last.done);
+        assert!(last.branch_name.is_some());
+    }
+
+    #[test]
+    fn commit_args_request_signing_only_when_enabled() {
+        let mut settings = WorkspaceSettings::default();
+        assert_eq!(commit_command_args("msg", &settings), vec!["commit", "-m", "msg"]);
+
+        settings.git_sign_commits = true;
+        assert_eq!(
+            commit_command_args("msg", &settings),
+            vec!["commit", "-S", "-m", "msg"]
+        );
+
+        settings.git_signing_key = Some(" ABC123 ".to_string());
+        assert_eq!(
+            commit_command_args("msg", &settings),
+            vec!["-c", "user.signingKey=ABC123", "commit", "-S", "-m", "msg"]
+        );
+    }
+
+    #[test]
+    fn signing_failures_are_reported_as_missing_key() {
+        let message = signing_error_message(
+            "error: gpg failed to sign the data\nfatal: failed to write commit object",
+        )
+        .expect("signing error");
+        assert!(message.starts_with("signing key not available"));
+        let ssh = "error: Couldn't load public key /home/me/.ssh/id.pub: No such file\n\
+                   fatal: failed to write commit object";
+        assert!(signing_error_message(ssh).is_some());
+        assert!(signing_error_message("nothing to commit, working tree clean").is_none());
+        assert!(signing_error_message("pre-commit: please sign the CLA first").is_none());
+        assert!(signing_error_message("fatal: failed to write commit object").is_none());
+    }
+
+    #[test]
+    fn set_and_remove_git_remote_round_trip() {
+        let (_root, repo) = create_temp_repo();
+
+        let remotes = set_remote_for_repo(&repo, "origin", "https://example.com/a.git")
+            .expect("add remote");
+        assert_eq!(
+            remotes,
+            vec![GitRemoteInfo {
+                name: "origin".to_string(),
+                url: Some("https://example.com/a.git".to_string()),
+            }]
+        );
+
+        let remotes = set_remote_for_repo(&repo, "origin", "https://example.com/b.git")
+            .expect("update remote");
+        assert_eq!(remotes[0].url.as_deref(), Some("https://example.com/b.git"));
+
+        assert!(set_remote_for_repo(&repo, "bad name", "https://example.com").is_err());
+        assert!(set_remote_for_repo(&repo, "upstream", "  ").is_err());
+
+        let remotes = remove_remote_for_repo(&repo, "origin").expect("remove remote");
+        assert!(remotes.is_empty());
+    }
+
+    #[test]
+    fn review_bundle_combines_status_and_patch() {
+        let (root, repo) = create_temp_repo();
+        let head = commit_file(&repo, "a.txt", "one\n", "init");
+
+        fs::write(root.join("a.txt"), "one\ntwo\n").expect("modify file");
+        fs::write(root.join("b.txt"), "new\n").expect("write untracked");
+
+        let bundle = review_bundle_for_repo(&repo).expect("bundle");
+        assert_eq!(bundle.head_sha, Some(head.to_string()));
+        assert_ne!(bundle.branch_name, "unknown");
+        let mut paths: Vec<&str> = bundle.files.iter().map(|file| file.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["a.txt", "b.txt"]);
+        assert_eq!((bundle.total_additions, bundle.total_deletions), (2, 0));
+        assert!(bundle.patch.contains("diff --git a/a.txt b/a.txt"));
+        assert!(bundle.patch.contains(" one\n+two\n"));
+        assert!(bundle.patch.contains("+++ b/b.txt\n@@ -0,0 +1 @@\n+new\n"));
+    }
 }
@@ -0,0 +1,258 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::{Match, WalkBuilder};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde_json::json;
+use tauri::{AppHandle, Manager};
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::event_sink::TauriEventSink;
+use crate::state::AppState;
+use crate::types::WorkspaceEntry;
+
+const GIT_STATUS_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Emits `codex/gitChanged` once workspace files have been quiet for the debounce period.
+/// Dropping the handle stops the watcher; it also stops once the session is removed.
+pub(crate) struct GitStatusWatcher {
+    task: JoinHandle<()>,
+    /// Waits for the session's removal and aborts `task`.
+    stopper: JoinHandle<()>,
+}
+
+impl GitStatusWatcher {
+    pub(crate) fn is_running(&self) -> bool {
+        !self.task.is_finished()
+    }
+}
+
+impl Drop for GitStatusWatcher {
+    fn drop(&mut self) {
+        self.stopper.abort();
+        self.task.abort();
+    }
+}
+
+/// Lock files, objects and logs under `.git` churn on every git command without changing
+/// what the status shows; index, HEAD and ref updates still count as changes.
+fn is_ignored_git_path(root: &Path, path: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(root) else {
+        return false;
+    };
+    let Ok(inner) = relative.strip_prefix(".git") else {
+        return false;
+    };
+    inner.starts_with("objects")
+        || inner.starts_with("logs")
+        || inner.extension().is_some_and(|extension| extension == "lock")
+}
+
+/// The workspace's ignore rules: the global excludes file, `.git/info/exclude` and every
+/// `.gitignore` under the root. Changes to ignored files (build output, dependencies) can't
+/// change the git status, so they don't trigger a refresh.
+struct IgnoreRules {
+    /// Ordered from the shallowest root to the deepest, so deeper files take precedence.
+    matchers: Vec<Gitignore>,
+}
+
+impl IgnoreRules {
+    fn load(root: &Path) -> Self {
+        let (global, _) = GitignoreBuilder::new(root).build_global();
+        let mut matchers = vec![global];
+        let mut exclude = GitignoreBuilder::new(root);
+        exclude.add(root.join(".git").join("info").join("exclude"));
+        matchers.extend(exclude.build().ok());
+        let walker = WalkBuilder::new(root)
+            .hidden(false)
+            .follow_links(false)
+            .filter_entry(|entry| entry.file_name() != ".git")
+            .build();
+        for entry in walker.flatten() {
+            let path = entry.path();
+            if entry.file_name() != ".gitignore" || !path.is_file() {
+                continue;
+            }
+            let Some(dir) = path.parent() else {
+                continue;
+            };
+            let mut builder = GitignoreBuilder::new(dir);
+            builder.add(path);
+            matchers.extend(builder.build().ok());
+        }
+        matchers.sort_by_key(|matcher| matcher.path().components().count());
+        Self { matchers }
+    }
+
+    fn is_ignored(&self, path: &Path) -> bool {
+        let is_dir = path.is_dir();
+        for matcher in self.matchers.iter().rev() {
+            if !path.starts_with(matcher.path()) {
+                continue;
+            }
+            match matcher.matched_path_or_any_parents(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+}
+
+fn is_gitignore_file(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == ".gitignore")
+}
+
+fn watch_workspace(
+    root: &Path,
+) -> Result<(RecommendedWatcher, mpsc::UnboundedReceiver<()>), String> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let filter_root = root.to_path_buf();
+    // Loaded on the watcher's thread at the first event rather than while starting up, and
+    // dropped whenever a `.gitignore` changes so the next event sees the new rules.
+    let mut rules: Option<IgnoreRules> = None;
+    let mut watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+        let Ok(event) = result else {
+            return;
+        };
+        if event.paths.iter().any(|path| is_gitignore_file(path)) {
+            rules = None;
+        }
+        let rules = rules.get_or_insert_with(|| IgnoreRules::load(&filter_root));
+        let relevant = event.paths.iter().any(|path| {
+            !is_ignored_git_path(&filter_root, path)
+                && (path.starts_with(filter_root.join(".git")) || !rules.is_ignored(path))
+        });
+        if relevant {
+            let _ = tx.send(());
+        }
+    })
+    .map_err(|err| format!("Failed to start file watcher: {err}"))?;
+    watcher
+        .watch(root, RecursiveMode::Recursive)
+        .map_err(|err| format!("Failed to watch workspace: {err}"))?;
+    Ok((watcher, rx))
+}
+
+/// `removed` comes from `WorkspaceSession::subscribe_removed`; the watcher stops when the
+/// session is marked removed or dropped.
+pub(crate) fn start_git_status_watcher<E: EventSink>(
+    workspace_id: String,
+    root: PathBuf,
+    mut removed: watch::Receiver<bool>,
+    event_sink: E,
+) -> Result<GitStatusWatcher, String> {
+    // Event paths are reported canonicalized on some platforms (e.g. /private/var on macOS).
+    let root = root.canonicalize().unwrap_or(root);
+    let (watcher, mut rx) = watch_workspace(&root)?;
+    let task = tokio::spawn(async move {
+        // Owned by the task so aborting it also releases the OS watch.
+        let _watcher = watcher;
+        while let Some(()) = rx.recv().await {
+            while let Ok(Some(())) = timeout(GIT_STATUS_DEBOUNCE, rx.recv()).await {}
+            event_sink.emit_app_server_event(AppServerEvent::new(
+                workspace_id.clone(),
+                json!({
+                    "method": "codex/gitChanged",
+                    "params": { "workspaceId": workspace_id },
+                }),
+            ));
+        }
+    });
+    let watch_task = task.abort_handle();
+    let stopper = tokio::spawn(async move {
+        // Errors once the session is dropped, which stops the watcher too.
+        let _ = removed.wait_for(|removed| *removed).await;
+        watch_task.abort();
+    });
+    Ok(GitStatusWatcher { task, stopper })
+}
+
+/// Starts (or restarts) the workspace's watcher for `session` when `autoRefreshGitStatus` is
+/// on, and stops it otherwise.
+pub(crate) async fn sync_git_status_watcher(
+    app: &AppHandle,
+    entry: &WorkspaceEntry,
+    session: Option<&Arc<WorkspaceSession>>,
+) {
+    let state = app.state::<AppState>();
+    let mut watchers = state.watchers.lock().await;
+    watchers.retain(|_, watcher| watcher.is_running());
+    let Some(session) = session.filter(|_| entry.settings.auto_refresh_git_status) else {
+        watchers.remove(&entry.id);
+        return;
+    };
+    match start_git_status_watcher(
+        entry.id.clone(),
+        PathBuf::from(&entry.path),
+        session.subscribe_removed(),
+        TauriEventSink::new(app.clone()),
+    ) {
+        Ok(watcher) => {
+            watchers.insert(entry.id.clone(), watcher);
+        }
+        Err(error) => {
+            watchers.remove(&entry.id);
+            eprintln!("git status watcher for {}: {error}", entry.path);
+        }
+    }
+}
+
+/// Stops the watchers of workspaces that no longer exist, e.g. after a removal.
+pub(crate) async fn prune_git_status_watchers(state: &AppState) {
+    let workspaces = state.workspaces.lock().await;
+    state
+        .watchers
+        .lock()
+        .await
+        .retain(|id, watcher| watcher.is_running() && workspaces.contains_key(id));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_ignored_git_path, IgnoreRules};
+    use std::fs;
+    use std::path::Path;
+    use uuid::Uuid;
+
+    #[test]
+    fn git_lock_files_objects_and_logs_are_ignored() {
+        let root = Path::new("/repo");
+        assert!(is_ignored_git_path(root, Path::new("/repo/.git/index.lock")));
+        assert!(is_ignored_git_path(root, Path::new("/repo/.git/refs/heads/main.lock")));
+        assert!(is_ignored_git_path(root, Path::new("/repo/.git/objects/ab/cdef")));
+        assert!(is_ignored_git_path(root, Path::new("/repo/.git/logs/HEAD")));
+        assert!(!is_ignored_git_path(root, Path::new("/repo/.git/index")));
+        assert!(!is_ignored_git_path(root, Path::new("/repo/.git/HEAD")));
+        assert!(!is_ignored_git_path(root, Path::new("/repo/.git/refs/heads/main")));
+        assert!(!is_ignored_git_path(root, Path::new("/repo/.gitignore")));
+        assert!(!is_ignored_git_path(root, Path::new("/repo/src/main.rs")));
+        assert!(!is_ignored_git_path(root, Path::new("/repo/src/Cargo.lock")));
+        assert!(!is_ignored_git_path(root, Path::new("/elsewhere/.git/index.lock")));
+    }
+
+    #[test]
+    fn gitignored_paths_are_ignored() {
+        let root = std::env::temp_dir().join(format!("codex-monitor-watch-{}", Uuid::new_v4()));
+        fs::create_dir_all(root.join("sub")).expect("create dirs");
+        fs::write(root.join(".gitignore"), "target/\n*.log\n!keep.log\n").expect("gitignore");
+        fs::write(root.join("sub/.gitignore"), "generated.rs\n").expect("nested gitignore");
+
+        let rules = IgnoreRules::load(&root);
+        assert!(rules.is_ignored(&root.join("target/debug/app")));
+        assert!(rules.is_ignored(&root.join("debug.log")));
+        assert!(!rules.is_ignored(&root.join("keep.log")));
+        assert!(rules.is_ignored(&root.join("sub/generated.rs")));
+        assert!(!rules.is_ignored(&root.join("generated.rs")));
+        assert!(!rules.is_ignored(&root.join("src/main.rs")));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -1,8 +1,11 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
-use git2::{DiffOptions, Repository, Tree};
+use git2::{DiffFindOptions, DiffOptions, ErrorClass, ErrorCode, Repository, Tree};
 use ignore::WalkBuilder;
+use serde::Serialize;
 
 use crate::types::{GitLogEntry, WorkspaceEntry};
 use crate::utils::normalize_git_path;
@@ -24,6 +27,105 @@ pub(crate) fn image_mime_type(path: &str) -> Option<&'static str> {
     }
 }
 
+/// Why a repository could not be used. `get_git_status` returns it tagged by `kind`, like
+/// `GitCommitError`, so the UI can tell a missing repository from other failures; other
+/// commands flatten it to its message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub(crate) enum GitRepoError {
+    NotAGitRepo { path: PathBuf },
+    /// Permission, corruption or any other failure, with the underlying message.
+    Failed { message: String },
+}
+
+impl From<String> for GitRepoError {
+    fn from(message: String) -> Self {
+        Self::Failed { message }
+    }
+}
+
+impl fmt::Display for GitRepoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAGitRepo { path } => write!(f, "Not a git repository: {}", path.display()),
+            Self::Failed { message } => f.write_str(message),
+        }
+    }
+}
+
+/// Opens `path`, separating "no repository here" from permission or corruption failures.
+pub(crate) fn open_repository(path: &Path) -> Result<Repository, GitRepoError> {
+    Repository::open(path).map_err(|err| {
+        if err.code() == ErrorCode::NotFound && err.class() == ErrorClass::Repository {
+            GitRepoError::NotAGitRepo {
+                path: path.to_path_buf(),
+            }
+        } else {
+            GitRepoError::Failed {
+                message: err.to_string(),
+            }
+        }
+    })
+}
+
+/// Repositories opened by path and reused across commands, so polling `get_git_status`
+/// doesn't re-discover `.git` every few seconds. `Repository` is `Send` but not `Sync`, so
+/// each handle sits behind its own lock and commands on one repo run one at a time. libgit2
+/// re-reads the index, refs and config when they change on disk; a handle whose command
+/// fails is dropped so the next call reopens it.
+#[derive(Default)]
+pub(crate) struct RepositoryCache {
+    repos: Mutex<HashMap<PathBuf, Arc<Mutex<Repository>>>>,
+}
+
+impl RepositoryCache {
+    fn handle(&self, path: &Path) -> Result<Arc<Mutex<Repository>>, GitRepoError> {
+        let mut repos = self
+            .repos
+            .lock()
+            .map_err(|_| GitRepoError::from("repository cache poisoned".to_string()))?;
+        if let Some(handle) = repos.get(path) {
+            return Ok(Arc::clone(handle));
+        }
+        let repo = open_repository(path)?;
+        let handle = Arc::new(Mutex::new(repo));
+        repos.insert(path.to_path_buf(), Arc::clone(&handle));
+        Ok(handle)
+    }
+
+    fn evict(&self, path: &Path) {
+        if let Ok(mut repos) = self.repos.lock() {
+            repos.remove(path);
+        }
+    }
+
+    /// Drops every handle whose path fails `keep`, e.g. once its workspace is removed.
+    pub(crate) fn retain(&self, keep: impl Fn(&Path) -> bool) {
+        if let Ok(mut repos) = self.repos.lock() {
+            repos.retain(|path, _| keep(path));
+        }
+    }
+
+    pub(crate) fn with_repository<T>(
+        &self,
+        path: &Path,
+        run: impl FnOnce(&Repository) -> Result<T, String>,
+    ) -> Result<T, GitRepoError> {
+        let handle = self.handle(path)?;
+        let result = match handle.lock() {
+            Ok(repo) if repo.path().exists() => run(&repo).map_err(GitRepoError::from),
+            _ => Err(GitRepoError::from(format!(
+                "Repository at {} is no longer available",
+                path.display()
+            ))),
+        };
+        if result.is_err() {
+            self.evict(path);
+        }
+        result
+    }
+}
+
 pub(crate) fn commit_to_entry(commit: git2::Commit) -> GitLogEntry {
     let summary = commit.summary().unwrap_or("").to_string();
     let author = commit.author().name().unwrap_or("").to_string();
@@ -36,29 +138,111 @@ pub(crate) fn commit_to_entry(commit: git2::Commit) -> GitLogEntry {
     }
 }
 
+pub(crate) fn head_is_unborn(repo: &Repository) -> bool {
+    matches!(repo.head(), Err(err) if err.code() == git2::ErrorCode::UnbornBranch)
+}
+
+/// Returns `None` for an unborn HEAD so callers diff against the empty tree.
+pub(crate) fn head_tree(repo: &Repository) -> Option<Tree<'_>> {
+    repo.head().ok().and_then(|head| head.peel_to_tree().ok())
+}
+
+pub(crate) fn current_branch_name(repo: &Repository) -> Option<String> {
+    if let Ok(head) = repo.head() {
+        return head.shorthand().map(|name| name.to_string());
+    }
+    // An unborn HEAD still points at the branch the first commit will create.
+    let head_ref = repo.find_reference("HEAD").ok()?;
+    let target = head_ref.symbolic_target()?;
+    Some(target.strip_prefix("refs/heads/").unwrap_or(target).to_string())
+}
+
 pub(crate) fn checkout_branch(repo: &Repository, name: &str) -> Result<(), git2::Error> {
+    let branch = repo.find_branch(name, git2::BranchType::Local)?;
     let refname = format!("refs/heads/{name}");
-    repo.set_head(&refname)?;
+    let target = branch.get().peel_to_commit()?;
+    // Update the working tree before moving HEAD so a refused checkout leaves HEAD alone.
     let mut options = git2::build::CheckoutBuilder::new();
     options.safe();
-    repo.checkout_head(Some(&mut options))?;
+    repo.checkout_tree(target.as_object(), Some(&mut options))?;
+    repo.set_head(&refname)?;
     Ok(())
 }
 
+pub(crate) fn git_push(repo: &Repository, remote_name: &str) -> Result<(), git2::Error> {
+    let head = repo.head()?;
+    if !head.is_branch() {
+        return Err(git2::Error::from_str("HEAD is not on a branch"));
+    }
+    let refname = head
+        .name()
+        .ok_or_else(|| git2::Error::from_str("HEAD has an invalid name"))?
+        .to_string();
+    let mut remote = repo.find_remote(remote_name)?;
+    let config = repo.config()?;
+
+    let mut callbacks = git2::RemoteCallbacks::new();
+    let mut attempts = 0;
+    callbacks.credentials(move |url, username, allowed| {
+        // libgit2 keeps asking while credentials are rejected; bail instead of looping.
+        attempts += 1;
+        if attempts > 3 {
+            return Err(git2::Error::from_str("authentication failed"));
+        }
+        if allowed.contains(git2::CredentialType::SSH_KEY) {
+            if let Some(username) = username {
+                return git2::Cred::ssh_key_from_agent(username);
+            }
+        }
+        if allowed.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
+            return git2::Cred::credential_helper(&config, url, username);
+        }
+        git2::Cred::default()
+    });
+    let push_error = std::rc::Rc::new(std::cell::RefCell::new(None::<String>));
+    let push_error_sink = push_error.clone();
+    callbacks.push_update_reference(move |name, status| {
+        if let Some(status) = status {
+            *push_error_sink.borrow_mut() = Some(format!("{name}: {status}"));
+        }
+        Ok(())
+    });
+
+    let mut options = git2::PushOptions::new();
+    options.remote_callbacks(callbacks);
+    let refspec = format!("{refname}:{refname}");
+    remote.push(&[refspec.as_str()], Some(&mut options))?;
+    let rejected = push_error.borrow_mut().take();
+    match rejected {
+        Some(message) => Err(git2::Error::from_str(&message)),
+        None => Ok(()),
+    }
+}
+
 pub(crate) fn diff_stats_for_path(
     repo: &Repository,
     head_tree: Option<&Tree>,
     path: &str,
+    rename_from: Option<&str>,
     include_index: bool,
     include_workdir: bool,
 ) -> Result<(i64, i64), git2::Error> {
     let mut additions = 0i64;
     let mut deletions = 0i64;
+    // For renames, diff both sides and let rename detection pair them so the
+    // stats reflect edits instead of a full delete + add.
+    let rename_from = rename_from.filter(|old_path| *old_path != path);
 
     if include_index {
         let mut options = DiffOptions::new();
         options.pathspec(path).include_untracked(true);
-        let diff = repo.diff_tree_to_index(head_tree, None, Some(&mut options))?;
+        if let Some(old_path) = rename_from {
+            options.pathspec(old_path);
+        }
+        let mut diff = repo.diff_tree_to_index(head_tree, None, Some(&mut options))?;
+        if rename_from.is_some() {
+            diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+        }
         let stats = diff.stats()?;
         additions += stats.insertions() as i64;
         deletions += stats.deletions() as i64;
@@ -71,7 +255,15 @@ pub(crate) fn diff_stats_for_path(
             .include_untracked(true)
             .recurse_untracked_dirs(true)
             .show_untracked_content(true);
-        let diff = repo.diff_index_to_workdir(None, Some(&mut options))?;
+        if let Some(old_path) = rename_from {
+            options.pathspec(old_path);
+        }
+        let mut diff = repo.diff_index_to_workdir(None, Some(&mut options))?;
+        if rename_from.is_some() {
+            diff.find_similar(Some(
+                DiffFindOptions::new().renames(true).for_untracked(true),
+            ))?;
+        }
         let stats = diff.stats()?;
         additions += stats.insertions() as i64;
         deletions += stats.deletions() as i64;
@@ -88,9 +280,36 @@ pub(crate) fn diff_patch_to_string(patch: &mut git2::Patch) -> Result<String, gi
         .unwrap_or_else(|| String::from_utf8_lossy(&buf).to_string()))
 }
 
+/// Renders hunks `[cursor, cursor + limit)` of `patch`, returning the cursor of the next
+/// hunk when more remain.
+pub(crate) fn diff_patch_hunks(
+    patch: &git2::Patch,
+    cursor: usize,
+    limit: usize,
+) -> Result<(Vec<String>, Option<usize>), git2::Error> {
+    let total = patch.num_hunks();
+    let end = cursor.saturating_add(limit).min(total);
+    let mut hunks = Vec::new();
+    for hunk_index in cursor..end {
+        let (hunk, line_count) = patch.hunk(hunk_index)?;
+        let mut text = String::from_utf8_lossy(hunk.header()).to_string();
+        for line_index in 0..line_count {
+            let line = patch.line_in_hunk(hunk_index, line_index)?;
+            if matches!(line.origin(), '+' | '-' | ' ') {
+                text.push(line.origin());
+            }
+            text.push_str(&String::from_utf8_lossy(line.content()));
+        }
+        hunks.push(text);
+    }
+    Ok((hunks, (end < total).then_some(end)))
+}
+
 #[cfg(test)]
 mod tests {
-    use super::image_mime_type;
+    use std::sync::Arc;
+
+    use super::{image_mime_type, open_repository, GitRepoError, RepositoryCache};
 
     #[test]
     fn image_mime_type_detects_known_extensions() {
@@ -101,6 +320,58 @@ mod tests {
         assert_eq!(image_mime_type("readme.txt"), None);
     }
 
+    #[test]
+    fn open_repository_reports_non_repo_paths() {
+        let dir =
+            std::env::temp_dir().join(format!("codex-monitor-not-repo-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).expect("create dir");
+
+        let error = open_repository(&dir).err().expect("open should fail");
+        assert_eq!(error, GitRepoError::NotAGitRepo { path: dir.clone() });
+        assert!(error.to_string().starts_with("Not a git repository"));
+        let tagged = serde_json::to_value(&error).expect("serialize error");
+        assert_eq!(tagged["kind"], "not_a_git_repo");
+        assert_eq!(tagged["path"], dir.to_string_lossy().as_ref());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn repository_cache_reuses_handles_until_a_command_fails() {
+        let dir =
+            std::env::temp_dir().join(format!("codex-monitor-repo-cache-{}", uuid::Uuid::new_v4()));
+        git2::Repository::init(&dir).expect("init repo");
+        let cache = RepositoryCache::default();
+
+        let first = cache.handle(&dir).expect("open");
+        let second = cache.handle(&dir).expect("cached");
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(cache.with_repository(&dir, |repo| Ok(repo.is_empty())).expect("run"));
+
+        let error = cache.with_repository(&dir, |_| Err::<(), _>("boom".to_string()));
+        assert_eq!(error, Err(GitRepoError::from("boom".to_string())));
+        let reopened = cache.handle(&dir).expect("reopen");
+        assert!(!Arc::ptr_eq(&first, &reopened));
+
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(cache.with_repository(&dir, |_| Ok(())).is_err());
+    }
+
+    #[test]
+    fn repository_cache_retain_drops_unwanted_handles() {
+        let dir =
+            std::env::temp_dir().join(format!("codex-monitor-repo-cache-{}", uuid::Uuid::new_v4()));
+        git2::Repository::init(&dir).expect("init repo");
+        let cache = RepositoryCache::default();
+
+        let first = cache.handle(&dir).expect("open");
+        cache.retain(|path| path == dir.as_path());
+        assert!(Arc::ptr_eq(&first, &cache.handle(&dir).expect("kept")));
+        cache.retain(|_| false);
+        assert!(!Arc::ptr_eq(&first, &cache.handle(&dir).expect("reopened")));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
 
 pub(crate) fn parse_github_repo(remote_url: &str) -> Option<String> {
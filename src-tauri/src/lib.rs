@@ -1,15 +1,17 @@
-use tauri::Manager;
+use tauri::{Manager, RunEvent};
 #[cfg(target_os = "macos")]
-use tauri::{RunEvent, WindowEvent};
+use tauri::WindowEvent;
 
 mod backend;
 mod codex;
 mod files;
 mod dictation;
+mod event_log;
 mod event_sink;
 mod git;
 mod git_utils;
 mod local_usage;
+mod logging;
 mod menu;
 mod notifications;
 mod prompts;
@@ -35,9 +37,13 @@ pub fn run() {
         }
     }
 
+    let log_levels = logging::WorkspaceLogLevels::default();
+    logging::init_tracing(log_levels.clone());
+
     let builder = tauri::Builder::default()
         .enable_macos_default_menu(false)
         .manage(menu::MenuItemRegistry::<tauri::Wry>::default())
+        .manage(log_levels.clone())
         .menu(menu::build_menu)
         .on_menu_event(menu::handle_menu_event)
         .on_window_event(|window, event| {
@@ -50,8 +56,12 @@ pub fn run() {
                 let _ = window.hide();
             }
         })
-        .setup(|app| {
+        .setup(move |app| {
             let state = state::AppState::load(&app.handle());
+            state.evict_idle_sessions(app.handle().clone());
+            if let Ok(workspaces) = state.workspaces.try_lock() {
+                log_levels.seed(workspaces.values());
+            }
             app.manage(state);
             #[cfg(desktop)]
             {
@@ -73,13 +83,26 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             settings::get_app_settings,
             settings::update_app_settings,
+            settings::get_global_settings,
+            settings::update_global_settings,
             settings::get_codex_config_path,
+            settings::get_event_log_path,
             files::file_read,
+            files::file_read_chunk,
             files::file_write,
+            files::file_preview_write,
+            files::file_merge,
+            files::find_agents_files,
+            files::prune_claude_json,
+            files::backup_configs,
+            files::restore_configs,
             codex::get_config_model,
             menu::menu_set_accelerators,
+            workspaces::reload_log_level,
             codex::codex_doctor,
             workspaces::list_workspaces,
+            workspaces::reload_workspaces,
+            workspaces::get_global_status,
             workspaces::is_workspace_path_dir,
             workspaces::add_workspace,
             workspaces::add_clone,
@@ -93,9 +116,28 @@ pub fn run() {
             workspaces::apply_worktree_changes,
             workspaces::update_workspace_settings,
             workspaces::update_workspace_codex_bin,
+            workspaces::rename_workspace,
+            workspaces::set_monitor_only,
+            workspaces::set_workspace_description,
+            workspaces::set_workspace_pin_order,
+            workspaces::cancel_connect,
+            workspaces::check_workspace_lock,
+            workspaces::clear_stale_session_state,
             codex::start_thread,
             codex::send_user_message,
+            codex::get_thread_preferences,
+            codex::set_thread_preferences,
             codex::turn_interrupt,
+            codex::get_current_turn_output,
+            codex::get_session_pending_requests,
+            codex::supports_method,
+            codex::set_runtime_approval_policy,
+            codex::get_session_info,
+            codex::get_session_process_info,
+            codex::get_path_diagnostics,
+            codex::recheck_codex,
+            codex::get_unread_threads,
+            codex::get_recent_threads,
             codex::start_review,
             codex::respond_to_server_request,
             codex::remember_approval_rule,
@@ -105,23 +147,55 @@ pub fn run() {
             codex::resume_thread,
             codex::fork_thread,
             codex::list_threads,
+            codex::search_threads,
             codex::list_mcp_server_status,
             codex::archive_thread,
+            codex::bulk_archive_threads,
+            codex::pin_thread,
+            codex::unpin_thread,
+            codex::export_thread_markdown,
             codex::set_thread_name,
             codex::collaboration_mode_list,
             workspaces::connect_workspace,
+            workspaces::reconnect_workspace,
             git::get_git_status,
+            git::stream_git_status,
             git::list_git_roots,
             git::get_git_diffs,
+            git::get_git_file_diff,
+            git::diff_against_branch,
+            git::get_working_patch_id,
+            git::get_git_diff_for_file,
             git::get_git_log,
+            git::get_head_info,
+            git::list_staged_files,
+            git::changed_files_between,
+            git::get_reflog,
+            git::reset_to_commit,
+            git::get_repo_operation_state,
+            git::export_review_bundle,
             git::get_git_commit_diff,
+            git::get_commit_details,
+            git::get_commit_file_stats,
+            git::get_project_summary,
+            git::detect_repo_conventions,
             git::get_git_remote,
+            git::set_git_remote,
+            git::remove_git_remote,
+            git::list_stashes,
+            git::stash_apply,
+            git::git_stash_push,
+            git::git_stash_pop,
             git::stage_git_file,
             git::stage_git_all,
+            git::create_workspace_file,
+            git::delete_workspace_file,
+            git::move_workspace_file,
             git::unstage_git_file,
             git::revert_git_file,
             git::revert_git_all,
             git::commit_git,
+            git::create_git_commit,
             git::push_git,
             git::pull_git,
             git::sync_git,
@@ -137,6 +211,7 @@ pub fn run() {
             git::checkout_git_branch,
             git::create_git_branch,
             codex::model_list,
+            codex::list_models,
             codex::account_rate_limits,
             codex::account_read,
             codex::codex_login,
@@ -170,6 +245,17 @@ pub fn run() {
         .expect("error while running tauri application");
 
     app.run(|app_handle, event| {
+        if let RunEvent::Exit = event {
+            let state = app_handle.state::<state::AppState>();
+            let flushed = tauri::async_runtime::block_on(
+                state
+                    .workspace_saves
+                    .flush(&state.workspaces, &state.storage_path),
+            );
+            if let Err(error) = flushed {
+                eprintln!("Failed to save workspaces on exit: {error}");
+            }
+        }
         #[cfg(target_os = "macos")]
         if let RunEvent::Reopen { .. } = event {
             if let Some(window) = app_handle.get_webview_window("main") {
@@ -822,6 +822,9 @@ mod tests {
             parent_id: None,
             worktree: None,
             settings: settings_a,
+            last_error: None,
+            monitor_only: false,
+            description: None,
         };
         let mut settings_b = WorkspaceSettings::default();
         settings_b.codex_home = Some(
@@ -839,6 +842,9 @@ mod tests {
             parent_id: None,
             worktree: None,
             settings: settings_b,
+            last_error: None,
+            monitor_only: false,
+            description: None,
         };
         workspaces.insert(entry_a.id.clone(), entry_a.clone());
         workspaces.insert(entry_b.id.clone(), entry_b.clone());
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+
+use crate::types::WorkspaceEntry;
+
+const DEFAULT_LOG_LEVEL: LevelFilter = LevelFilter::INFO;
+
+/// Per-workspace verbosity, shared between the tracing layer and the reload command.
+#[derive(Clone, Default)]
+pub(crate) struct WorkspaceLogLevels {
+    levels: Arc<RwLock<HashMap<String, LevelFilter>>>,
+}
+
+impl WorkspaceLogLevels {
+    pub(crate) fn set(&self, workspace_id: &str, level: LevelFilter) {
+        if let Ok(mut levels) = self.levels.write() {
+            levels.insert(workspace_id.to_string(), level);
+        }
+    }
+
+    /// Applies a workspace's `logLevel` setting; `None` falls back to the default level.
+    pub(crate) fn apply(&self, workspace_id: &str, level: Option<&str>) -> Result<(), String> {
+        match level {
+            Some(level) => self.set(workspace_id, parse_log_level(level)?),
+            None => {
+                if let Ok(mut levels) = self.levels.write() {
+                    levels.remove(workspace_id);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn seed<'a>(&self, entries: impl IntoIterator<Item = &'a WorkspaceEntry>) {
+        for entry in entries {
+            if let Err(error) = self.apply(&entry.id, entry.settings.log_level.as_deref()) {
+                tracing::warn!(workspace_id = %entry.id, "{error}");
+            }
+        }
+    }
+
+    fn level_for(&self, workspace_id: Option<&str>) -> LevelFilter {
+        let Some(workspace_id) = workspace_id else {
+            return DEFAULT_LOG_LEVEL;
+        };
+        self.levels
+            .read()
+            .ok()
+            .and_then(|levels| levels.get(workspace_id).copied())
+            .unwrap_or(DEFAULT_LOG_LEVEL)
+    }
+}
+
+pub(crate) fn parse_log_level(value: &str) -> Result<LevelFilter, String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "error" => Ok(LevelFilter::ERROR),
+        "warn" => Ok(LevelFilter::WARN),
+        "info" => Ok(LevelFilter::INFO),
+        "debug" => Ok(LevelFilter::DEBUG),
+        "trace" => Ok(LevelFilter::TRACE),
+        _ => Err(format!("invalid log level: {value}")),
+    }
+}
+
+#[derive(Default)]
+struct WorkspaceIdVisitor {
+    workspace_id: Option<String>,
+}
+
+impl Visit for WorkspaceIdVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "workspace_id" {
+            self.workspace_id = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "workspace_id" {
+            self.workspace_id = Some(format!("{value:?}").trim_matches('"').to_string());
+        }
+    }
+}
+
+/// Drops events below the level configured for their `workspace_id` field.
+struct WorkspaceLevelLayer {
+    levels: WorkspaceLogLevels,
+}
+
+impl<S: Subscriber> Layer<S> for WorkspaceLevelLayer {
+    fn event_enabled(&self, event: &Event<'_>, _ctx: Context<'_, S>) -> bool {
+        let mut visitor = WorkspaceIdVisitor::default();
+        event.record(&mut visitor);
+        *event.metadata().level() <= self.levels.level_for(visitor.workspace_id.as_deref())
+    }
+}
+
+pub(crate) fn init_tracing(levels: WorkspaceLogLevels) {
+    let _ = tracing_subscriber::registry()
+        .with(WorkspaceLevelLayer { levels })
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+        .try_init();
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing_subscriber::filter::LevelFilter;
+
+    use super::{parse_log_level, WorkspaceLogLevels};
+
+    #[test]
+    fn log_levels_fall_back_to_info() {
+        let levels = WorkspaceLogLevels::default();
+        levels.set("ws-1", parse_log_level("Trace").expect("level"));
+
+        assert_eq!(levels.level_for(Some("ws-1")), LevelFilter::TRACE);
+        assert_eq!(levels.level_for(Some("ws-2")), LevelFilter::INFO);
+        assert_eq!(levels.level_for(None), LevelFilter::INFO);
+        assert!(parse_log_level("verbose").is_err());
+
+        levels.apply("ws-1", None).expect("clear");
+        assert_eq!(levels.level_for(Some("ws-1")), LevelFilter::INFO);
+        assert!(levels.apply("ws-1", Some("loud")).is_err());
+    }
+}
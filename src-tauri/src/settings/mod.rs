@@ -3,8 +3,9 @@ use tauri::{State, Window};
 use crate::state::AppState;
 use crate::shared::settings_core::{
     get_app_settings_core, get_codex_config_path_core, update_app_settings_core,
+    update_global_settings_core,
 };
-use crate::types::AppSettings;
+use crate::types::{AppSettings, GlobalSettings};
 use crate::window;
 
 #[tauri::command]
@@ -25,10 +26,37 @@ pub(crate) async fn update_app_settings(
 ) -> Result<AppSettings, String> {
     let updated =
         update_app_settings_core(settings, &state.app_settings, &state.settings_path).await?;
+    state.event_log.set_enabled(updated.event_log_enabled);
     let _ = window::apply_window_appearance(&window, updated.theme.as_str());
     Ok(updated)
 }
 
+#[tauri::command]
+pub(crate) async fn get_global_settings(
+    state: State<'_, AppState>,
+) -> Result<GlobalSettings, String> {
+    Ok(state.global_settings.lock().await.clone())
+}
+
+#[tauri::command]
+pub(crate) async fn update_global_settings(
+    settings: GlobalSettings,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    update_global_settings_core(
+        settings,
+        &state.global_settings,
+        &state.global_settings_path,
+    )
+    .await
+}
+
+/// Location of the event log mirror, whether or not logging is currently enabled.
+#[tauri::command]
+pub(crate) async fn get_event_log_path(state: State<'_, AppState>) -> Result<String, String> {
+    Ok(state.event_log.path().to_string_lossy().to_string())
+}
+
 #[tauri::command]
 pub(crate) async fn get_codex_config_path() -> Result<String, String> {
     get_codex_config_path_core()
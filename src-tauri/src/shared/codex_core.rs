@@ -1,23 +1,35 @@
 use serde_json::{json, Map, Value};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::pin::Pin;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
 
-use tokio::sync::{Mutex, oneshot};
+use futures::future::join_all;
+use tokio::sync::{Mutex, Semaphore, oneshot};
 use tokio::sync::oneshot::error::TryRecvError;
 use tokio::time::timeout;
 use tokio::time::Instant;
 
-use crate::backend::app_server::WorkspaceSession;
+use crate::backend::app_server::{
+    check_codex_installation, codex_path_diagnostics, WorkspaceSession,
+};
+use crate::backend::process_info::session_process_info;
 use crate::codex::config as codex_config;
+use crate::codex::export::{thread_markdown, timestamp_ms};
 use crate::codex::home::{resolve_default_codex_home, resolve_workspace_codex_home};
 use crate::rules;
 use crate::shared::account::{build_account_response, read_auth_account};
-use crate::types::WorkspaceEntry;
+use crate::storage::{read_thread_preferences, write_pins, write_thread_preferences};
+use crate::types::{
+    AppSettings, BulkOperationFailure, BulkOperationResult, ModelInfo, PathDiagnostics,
+    PendingRequestInfo, SessionInfo, SessionProcessInfo, ThreadPreferences, WorkspaceEntry,
+    WorkspaceSettings,
+};
 
 const LOGIN_START_TIMEOUT: Duration = Duration::from_secs(30);
+pub(crate) const MONITOR_ONLY_ERROR: &str = "workspace is monitor-only";
 
 pub(crate) enum CodexLoginCancelState {
     PendingStart(oneshot::Sender<()>),
@@ -35,6 +47,43 @@ async fn get_session_clone(
         .ok_or_else(|| "workspace not connected".to_string())
 }
 
+/// Like `get_session_clone`, but refuses sessions of monitor-only workspaces. Interrupting a
+/// turn, answering an approval and app-local state such as pins stay allowed.
+async fn get_mutable_session_clone(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: &str,
+) -> Result<Arc<WorkspaceSession>, String> {
+    let session = get_session_clone(sessions, workspace_id).await?;
+    if session.monitor_only.load(Ordering::SeqCst) {
+        return Err(MONITOR_ONLY_ERROR.to_string());
+    }
+    Ok(session)
+}
+
+/// `get_mutable_session_clone` plus the workspace's settings, read while the sessions lock is
+/// still held instead of taking it a second time.
+async fn get_mutable_session_with_settings(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: &str,
+) -> Result<(Arc<WorkspaceSession>, WorkspaceSettings), String> {
+    let sessions = sessions.lock().await;
+    let session = sessions
+        .get(workspace_id)
+        .cloned()
+        .ok_or_else(|| "workspace not connected".to_string())?;
+    if session.monitor_only.load(Ordering::SeqCst) {
+        return Err(MONITOR_ONLY_ERROR.to_string());
+    }
+    let settings = workspaces
+        .lock()
+        .await
+        .get(workspace_id)
+        .map(|entry| entry.settings.clone())
+        .unwrap_or_default();
+    Ok((session, settings))
+}
+
 async fn resolve_workspace_and_parent(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: &str,
@@ -66,10 +115,16 @@ pub(crate) async fn start_thread_core(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     workspace_id: String,
 ) -> Result<Value, String> {
-    let session = get_session_clone(sessions, &workspace_id).await?;
+    let session = get_mutable_session_clone(sessions, &workspace_id).await?;
+    let approval_policy = session
+        .approval_policy
+        .lock()
+        .await
+        .clone()
+        .unwrap_or_else(|| DEFAULT_APPROVAL_POLICY.to_string());
     let params = json!({
         "cwd": session.entry.path,
-        "approvalPolicy": "on-request"
+        "approvalPolicy": approval_policy
     });
     session.send_request("thread/start", params).await
 }
@@ -89,20 +144,152 @@ pub(crate) async fn fork_thread_core(
     workspace_id: String,
     thread_id: String,
 ) -> Result<Value, String> {
-    let session = get_session_clone(sessions, &workspace_id).await?;
+    let session = get_mutable_session_clone(sessions, &workspace_id).await?;
     let params = json!({ "threadId": thread_id });
     session.send_request("thread/fork", params).await
 }
 
 pub(crate) async fn list_threads_core(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    pins: &Mutex<HashMap<String, HashSet<String>>>,
     workspace_id: String,
     cursor: Option<String>,
     limit: Option<u32>,
 ) -> Result<Value, String> {
     let session = get_session_clone(sessions, &workspace_id).await?;
     let params = json!({ "cursor": cursor, "limit": limit });
-    session.send_request("thread/list", params).await
+    let mut response = session.send_request("thread/list", params).await?;
+    if let Some(pinned) = pins.lock().await.get(&workspace_id) {
+        annotate_pinned_threads(&mut response, pinned);
+    }
+    Ok(response)
+}
+
+/// Marks threads in a `thread/list` response that are pinned in the local registry.
+fn annotate_pinned_threads(response: &mut Value, pinned: &HashSet<String>) {
+    let data = if response.get("result").is_some() {
+        response["result"].get_mut("data")
+    } else {
+        response.get_mut("data")
+    };
+    let Some(threads) = data.and_then(Value::as_array_mut) else {
+        return;
+    };
+    for thread in threads {
+        let is_pinned = thread
+            .get("id")
+            .and_then(Value::as_str)
+            .is_some_and(|id| pinned.contains(id));
+        if !is_pinned {
+            continue;
+        }
+        if let Some(thread) = thread.as_object_mut() {
+            thread.insert("pinned".to_string(), Value::Bool(true));
+        }
+    }
+}
+
+fn set_local_pin(
+    pins: &mut HashMap<String, HashSet<String>>,
+    workspace_id: &str,
+    thread_id: &str,
+    pinned: bool,
+) {
+    if pinned {
+        pins.entry(workspace_id.to_string())
+            .or_default()
+            .insert(thread_id.to_string());
+    } else if let Some(threads) = pins.get_mut(workspace_id) {
+        threads.remove(thread_id);
+        if threads.is_empty() {
+            pins.remove(workspace_id);
+        }
+    }
+}
+
+const DEFAULT_THREAD_SEARCH_LIMIT: u32 = 50;
+const THREAD_SEARCH_FALLBACK_PAGE: u32 = 1000;
+fn response_error(response: &Value) -> Option<String> {
+    let error = response.get("error")?;
+    Some(
+        error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown error")
+            .to_string(),
+    )
+}
+
+fn thread_list_data(response: &Value) -> Vec<Value> {
+    response
+        .get("result")
+        .unwrap_or(response)
+        .get("data")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Case-insensitive substring match against the thread's name and preview.
+fn filter_threads_by_query(threads: Vec<Value>, query: &str, limit: usize) -> Vec<Value> {
+    let query = query.to_lowercase();
+    threads
+        .into_iter()
+        .filter(|thread| {
+            ["name", "title", "preview"].iter().any(|key| {
+                thread
+                    .get(key)
+                    .and_then(Value::as_str)
+                    .is_some_and(|value| value.to_lowercase().contains(&query))
+            })
+        })
+        .take(limit)
+        .collect()
+}
+
+/// Searches threads with the app-server's `thread/search`. Servers that do not implement it
+/// (method-not-found) get a client-side fallback: one large `thread/list` page filtered by a
+/// case-insensitive substring match on thread names and previews, so older threads beyond
+/// that page are not searched. `searchMethod` reports which path produced `data`.
+pub(crate) async fn search_threads_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+    query: String,
+    limit: Option<u32>,
+) -> Result<Value, String> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Err("Search query cannot be empty".to_string());
+    }
+    let limit = limit.unwrap_or(DEFAULT_THREAD_SEARCH_LIMIT);
+    let session = get_session_clone(sessions, &workspace_id).await?;
+    let response = session
+        .send_request_if_supported("thread/search", json!({ "query": query, "limit": limit }))
+        .await?;
+    if let Some(response) = response {
+        if let Some(message) = response_error(&response) {
+            return Err(format!("Thread search failed: {message}"));
+        }
+        return Ok(json!({
+            "searchMethod": "server",
+            "data": thread_list_data(&response),
+        }));
+    }
+
+    let response = session
+        .send_request(
+            "thread/list",
+            json!({ "cursor": null, "limit": THREAD_SEARCH_FALLBACK_PAGE }),
+        )
+        .await?;
+    if let Some(message) = response_error(&response) {
+        return Err(format!("Thread search failed: {message}"));
+    }
+    let threads = filter_threads_by_query(thread_list_data(&response), query, limit as usize);
+    Ok(json!({
+        "searchMethod": "client",
+        "data": threads,
+    }))
 }
 
 pub(crate) async fn list_mcp_server_status_core(
@@ -121,9 +308,151 @@ pub(crate) async fn archive_thread_core(
     workspace_id: String,
     thread_id: String,
 ) -> Result<Value, String> {
-    let session = get_session_clone(sessions, &workspace_id).await?;
+    let session = get_mutable_session_clone(sessions, &workspace_id).await?;
     let params = json!({ "threadId": thread_id });
-    session.send_request("thread/archive", params).await
+    let response = session.send_request("thread/archive", params).await?;
+    if response_error(&response).is_none() {
+        session.forget_thread_activity([&thread_id]).await;
+    }
+    Ok(response)
+}
+
+/// Pins or unpins a thread with the app-server's `thread/pin` / `thread/unpin`. When the server
+/// does not implement them (method-not-found), the pin is kept in the local registry persisted
+/// to `pins_path`, which `list_threads_core` uses to mark threads as `pinned`.
+pub(crate) async fn set_thread_pinned_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    pins: &Mutex<HashMap<String, HashSet<String>>>,
+    pins_path: &PathBuf,
+    workspace_id: String,
+    thread_id: String,
+    pinned: bool,
+) -> Result<Value, String> {
+    let session = get_session_clone(sessions, &workspace_id).await?;
+    let method = if pinned { "thread/pin" } else { "thread/unpin" };
+    let response = session
+        .send_request_if_supported(method, json!({ "threadId": thread_id }))
+        .await?;
+    if let Some(response) = response {
+        return Ok(response);
+    }
+
+    let mut pins = pins.lock().await;
+    let mut updated = pins.clone();
+    set_local_pin(&mut updated, &workspace_id, &thread_id, pinned);
+    write_pins(pins_path, &updated)?;
+    *pins = updated;
+    Ok(json!({ "result": { "threadId": thread_id, "pinned": pinned } }))
+}
+
+/// Drops local pins of workspaces that no longer exist, so `pins.json` doesn't keep growing
+/// as workspaces and worktrees are removed.
+pub(crate) async fn prune_pins_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    pins: &Mutex<HashMap<String, HashSet<String>>>,
+    pins_path: &PathBuf,
+) -> Result<(), String> {
+    let known: HashSet<String> = workspaces.lock().await.keys().cloned().collect();
+    let mut pins = pins.lock().await;
+    if pins.keys().all(|workspace_id| known.contains(workspace_id)) {
+        return Ok(());
+    }
+    let mut updated = pins.clone();
+    updated.retain(|workspace_id, _| known.contains(workspace_id));
+    write_pins(pins_path, &updated)?;
+    *pins = updated;
+    Ok(())
+}
+
+/// Fetches every turn with `turn/list`, following `nextCursor`. Servers without `turn/list`
+/// fall back to the read-only `thread/read`, which returns the turns without loading the
+/// thread the way `thread/resume` would.
+async fn thread_turns(session: &WorkspaceSession, thread_id: &str) -> Result<Vec<Value>, String> {
+    let mut turns = Vec::new();
+    let mut cursor: Option<String> = None;
+    let mut seen_cursors = HashSet::new();
+    loop {
+        let params = json!({ "threadId": thread_id, "cursor": cursor });
+        let Some(response) = session.send_request_if_supported("turn/list", params).await? else {
+            let params = json!({ "threadId": thread_id, "includeTurns": true });
+            let Some(response) = session.send_request_if_supported("thread/read", params).await?
+            else {
+                return Err("This Codex version cannot list a thread's turns".to_string());
+            };
+            if let Some(message) = response_error(&response) {
+                return Err(format!("Failed to read thread: {message}"));
+            }
+            let thread = response.get("result").unwrap_or(&response).get("thread");
+            return Ok(thread
+                .and_then(|thread| thread.get("turns"))
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default());
+        };
+        if let Some(message) = response_error(&response) {
+            return Err(format!("Failed to list turns: {message}"));
+        }
+        turns.extend(thread_list_data(&response));
+        cursor = response
+            .get("result")
+            .and_then(|result| result.get("nextCursor"))
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        let Some(next) = cursor.as_ref() else {
+            return Ok(turns);
+        };
+        if !seen_cursors.insert(next.clone()) {
+            return Err("turn/list returned a cursor it already returned".to_string());
+        }
+    }
+}
+
+pub(crate) async fn export_thread_markdown_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+    thread_id: String,
+) -> Result<String, String> {
+    let session = get_session_clone(sessions, &workspace_id).await?;
+    let turns = thread_turns(&session, &thread_id).await?;
+    Ok(thread_markdown(&thread_id, &turns))
+}
+
+const BULK_ARCHIVE_CONCURRENCY: usize = 8;
+
+pub(crate) async fn bulk_archive_threads_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+    thread_ids: Vec<String>,
+) -> Result<BulkOperationResult, String> {
+    let session = get_mutable_session_clone(sessions, &workspace_id).await?;
+    let permits = Semaphore::new(BULK_ARCHIVE_CONCURRENCY);
+    let archives = thread_ids.into_iter().map(|thread_id| {
+        let session = &session;
+        let permits = &permits;
+        async move {
+            let _permit = permits.acquire().await;
+            let result = session
+                .send_request("thread/archive", json!({ "threadId": thread_id }))
+                .await
+                .and_then(|response| match response_error(&response) {
+                    Some(message) => Err(message),
+                    None => Ok(()),
+                });
+            (thread_id, result)
+        }
+    });
+    let mut outcome = BulkOperationResult::default();
+    for (thread_id, result) in join_all(archives).await {
+        match result {
+            Ok(()) => outcome.succeeded.push(thread_id),
+            Err(error) => outcome.failed.push(BulkOperationFailure {
+                id: thread_id,
+                error,
+            }),
+        }
+    }
+    session.forget_thread_activity(&outcome.succeeded).await;
+    Ok(outcome)
 }
 
 pub(crate) async fn set_thread_name_core(
@@ -132,12 +461,52 @@ pub(crate) async fn set_thread_name_core(
     thread_id: String,
     name: String,
 ) -> Result<Value, String> {
-    let session = get_session_clone(sessions, &workspace_id).await?;
+    let session = get_mutable_session_clone(sessions, &workspace_id).await?;
     let params = json!({ "threadId": thread_id, "name": name });
     session.send_request("thread/name/set", params).await
 }
 
+/// Resolves `focus_paths` against the workspace root and returns `mention` input blocks.
+/// Paths that escape the workspace or are not files are rejected.
+fn focus_path_inputs(workspace_path: &str, focus_paths: Vec<String>) -> Result<Vec<Value>, String> {
+    let root = Path::new(workspace_path)
+        .canonicalize()
+        .map_err(|err| format!("Failed to resolve workspace root: {err}"))?;
+    let mut resolved: Vec<PathBuf> = Vec::new();
+    for path in focus_paths {
+        let trimmed = path.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let canonical = root
+            .join(trimmed)
+            .canonicalize()
+            .map_err(|err| format!("Invalid focus path {trimmed}: {err}"))?;
+        if !canonical.starts_with(&root) {
+            return Err(format!("Focus path {trimmed} is outside the workspace"));
+        }
+        if !canonical.is_file() {
+            return Err(format!("Focus path {trimmed} is not a file"));
+        }
+        if !resolved.contains(&canonical) {
+            resolved.push(canonical);
+        }
+    }
+    Ok(resolved
+        .into_iter()
+        .map(|path| {
+            let name = path
+                .strip_prefix(&root)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            json!({ "type": "mention", "name": name, "path": path.to_string_lossy() })
+        })
+        .collect())
+}
+
 pub(crate) async fn send_user_message_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     workspace_id: String,
     thread_id: String,
@@ -147,9 +516,18 @@ pub(crate) async fn send_user_message_core(
     access_mode: Option<String>,
     images: Option<Vec<String>>,
     collaboration_mode: Option<Value>,
+    focus_paths: Option<Vec<String>>,
+    thread_preferences_path: &PathBuf,
 ) -> Result<Value, String> {
-    let session = get_session_clone(sessions, &workspace_id).await?;
-    let access_mode = access_mode.unwrap_or_else(|| "current".to_string());
+    let (session, defaults) =
+        get_mutable_session_with_settings(workspaces, sessions, &workspace_id).await?;
+    let stored = thread_preferences_for(&session, &thread_id, thread_preferences_path).await;
+    let preferences = ThreadPreferences {
+        model: normalize_preference(model).or(stored.model),
+        effort: normalize_preference(effort).or(stored.effort),
+    };
+    let (model, effort) = turn_model_and_effort(&preferences, &defaults);
+    let access_mode = resolve_access_mode(access_mode, defaults.default_access_mode);
     let sandbox_policy = match access_mode.as_str() {
         "full-access" => json!({ "type": "dangerFullAccess" }),
         "read-only" => json!({ "type": "readOnly" }),
@@ -161,9 +539,14 @@ pub(crate) async fn send_user_message_core(
     };
 
     let approval_policy = if access_mode == "full-access" {
-        "never"
+        "never".to_string()
     } else {
-        "on-request"
+        session
+            .approval_policy
+            .lock()
+            .await
+            .clone()
+            .unwrap_or_else(|| DEFAULT_APPROVAL_POLICY.to_string())
     };
 
     let trimmed_text = text.trim();
@@ -190,6 +573,9 @@ pub(crate) async fn send_user_message_core(
     if input.is_empty() {
         return Err("empty user message".to_string());
     }
+    if let Some(focus_paths) = focus_paths {
+        input.extend(focus_path_inputs(&session.entry.path, focus_paths)?);
+    }
 
     let mut params = Map::new();
     params.insert("threadId".to_string(), json!(thread_id));
@@ -204,9 +590,124 @@ pub(crate) async fn send_user_message_core(
             params.insert("collaborationMode".to_string(), mode);
         }
     }
-    session
+    let response = session
         .send_request("turn/start", Value::Object(params))
+        .await?;
+    session
+        .thread_preferences
+        .lock()
+        .await
+        .insert(thread_id, preferences);
+    Ok(response)
+}
+
+const ACCESS_MODES: [&str; 3] = ["full-access", "read-only", "current"];
+const DEFAULT_ACCESS_MODE: &str = "current";
+
+/// Checks a workspace's `defaultAccessMode`; a blank value clears it.
+pub(crate) fn validate_default_access_mode(
+    value: Option<String>,
+) -> Result<Option<String>, String> {
+    let Some(mode) = normalize_preference(value) else {
+        return Ok(None);
+    };
+    if ACCESS_MODES.contains(&mode.as_str()) {
+        Ok(Some(mode))
+    } else {
+        Err(format!(
+            "invalid default access mode: {mode} (expected one of {})",
+            ACCESS_MODES.join(", ")
+        ))
+    }
+}
+
+/// The caller's access mode, else the workspace default, else `current`.
+fn resolve_access_mode(requested: Option<String>, workspace_default: Option<String>) -> String {
+    requested
+        .or(normalize_preference(workspace_default))
+        .unwrap_or_else(|| DEFAULT_ACCESS_MODE.to_string())
+}
+
+/// The thread's model and effort, with the workspace defaults filling the gaps. Defaults are
+/// not remembered as the thread's own choice, so changing them affects later turns too.
+fn turn_model_and_effort(
+    preferences: &ThreadPreferences,
+    defaults: &WorkspaceSettings,
+) -> (Option<String>, Option<String>) {
+    (
+        preferences.model.clone().or(normalize_preference(defaults.model.clone())),
+        preferences.effort.clone().or(normalize_preference(defaults.effort.clone())),
+    )
+}
+
+fn normalize_preference(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Session-held preferences for the thread, hydrated from disk the first time it is seen.
+async fn thread_preferences_for(
+    session: &WorkspaceSession,
+    thread_id: &str,
+    path: &PathBuf,
+) -> ThreadPreferences {
+    let mut preferences = session.thread_preferences.lock().await;
+    if let Some(found) = preferences.get(thread_id) {
+        return found.clone();
+    }
+    let stored = read_thread_preferences(path)
+        .ok()
+        .and_then(|mut stored| stored.remove(thread_id));
+    match stored {
+        Some(stored) => {
+            preferences.insert(thread_id.to_string(), stored.clone());
+            stored
+        }
+        None => ThreadPreferences::default(),
+    }
+}
+
+pub(crate) async fn get_thread_preferences_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+    thread_id: String,
+    thread_preferences_path: &PathBuf,
+) -> Result<ThreadPreferences, String> {
+    let session = get_session_clone(sessions, &workspace_id).await?;
+    Ok(thread_preferences_for(&session, &thread_id, thread_preferences_path).await)
+}
+
+/// Replaces the thread's preferences; `persist` also writes them so they survive a restart.
+pub(crate) async fn set_thread_preferences_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+    thread_id: String,
+    model: Option<String>,
+    effort: Option<String>,
+    persist: bool,
+    thread_preferences_path: &PathBuf,
+) -> Result<ThreadPreferences, String> {
+    let session = get_session_clone(sessions, &workspace_id).await?;
+    let preferences = ThreadPreferences {
+        model: normalize_preference(model),
+        effort: normalize_preference(effort),
+    };
+    session
+        .thread_preferences
+        .lock()
         .await
+        .insert(thread_id.clone(), preferences.clone());
+    if persist {
+        let mut stored = read_thread_preferences(thread_preferences_path)?;
+        if preferences == ThreadPreferences::default() {
+            stored.remove(&thread_id);
+        } else {
+            stored.insert(thread_id, preferences.clone());
+        }
+        write_thread_preferences(thread_preferences_path, &stored)?;
+    }
+    Ok(preferences)
 }
 
 pub(crate) async fn collaboration_mode_list_core(
@@ -230,6 +731,243 @@ pub(crate) async fn turn_interrupt_core(
     session.send_request("turn/interrupt", params).await
 }
 
+pub(crate) async fn get_current_turn_output_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+    thread_id: String,
+) -> Result<Value, String> {
+    let session = get_session_clone(sessions, &workspace_id).await?;
+    let text = session.current_turn_output(&thread_id).await;
+    Ok(json!({
+        "threadId": thread_id,
+        "inProgress": text.is_some(),
+        "text": text.unwrap_or_default(),
+    }))
+}
+
+pub(crate) async fn get_session_pending_requests_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+) -> Result<Vec<PendingRequestInfo>, String> {
+    let session = get_session_clone(sessions, &workspace_id).await?;
+    Ok(session.pending_requests().await)
+}
+
+pub(crate) async fn supports_method_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+    method: String,
+) -> Result<Option<bool>, String> {
+    let session = get_session_clone(sessions, &workspace_id).await?;
+    Ok(session.supports_method(&method).await)
+}
+
+const DEFAULT_APPROVAL_POLICY: &str = "on-request";
+const APPROVAL_POLICIES: [&str; 4] = ["untrusted", "on-failure", "on-request", "never"];
+const SET_APPROVAL_POLICY_METHOD: &str = "approvalPolicy/set";
+
+fn validate_approval_policy(policy: &str) -> Result<String, String> {
+    let policy = policy.trim();
+    if APPROVAL_POLICIES.contains(&policy) {
+        Ok(policy.to_string())
+    } else {
+        Err(format!(
+            "invalid approval policy: {policy} (expected one of {})",
+            APPROVAL_POLICIES.join(", ")
+        ))
+    }
+}
+
+/// Updates the session's approval policy. When the app-server can't apply it live, the new
+/// policy is still kept for later turns and the error says so.
+pub(crate) async fn set_runtime_approval_policy_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+    policy: String,
+) -> Result<(), String> {
+    let policy = validate_approval_policy(&policy)?;
+    let session = get_mutable_session_clone(sessions, &workspace_id).await?;
+    *session.approval_policy.lock().await = Some(policy.clone());
+    if session.supports_method(SET_APPROVAL_POLICY_METHOD).await != Some(true) {
+        return Err(format!(
+            "This Codex version cannot change the approval policy mid-session; \
+             \"{policy}\" will take effect on the next turn."
+        ));
+    }
+    let response = session
+        .send_request(SET_APPROVAL_POLICY_METHOD, json!({ "approvalPolicy": policy }))
+        .await?;
+    if let Some(error) = response.get("error") {
+        let message = error
+            .get("message")
+            .and_then(|value| value.as_str())
+            .unwrap_or("unknown error");
+        return Err(format!(
+            "Failed to update approval policy ({message}); it will take effect on the next turn."
+        ));
+    }
+    Ok(())
+}
+
+pub(crate) async fn get_session_info_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+) -> Result<SessionInfo, String> {
+    let session = get_session_clone(sessions, &workspace_id).await?;
+    Ok(session.session_info())
+}
+
+pub(crate) async fn get_session_process_info_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+) -> Result<SessionProcessInfo, String> {
+    let session = get_session_clone(sessions, &workspace_id).await?;
+    let pid = session
+        .child
+        .lock()
+        .await
+        .id()
+        .ok_or("Codex process has exited")?;
+    tokio::task::spawn_blocking(move || session_process_info(pid))
+        .await
+        .map_err(|err| err.to_string())
+}
+
+/// Threads with activity after their last-viewed time (unix ms); never-viewed threads count.
+fn unread_thread_ids(
+    activity: &HashMap<String, u64>,
+    last_viewed: &HashMap<String, u64>,
+) -> Vec<String> {
+    let mut unread: Vec<String> = activity
+        .iter()
+        .filter(|(thread_id, last_activity)| {
+            !matches!(last_viewed.get(*thread_id), Some(viewed) if viewed >= *last_activity)
+        })
+        .map(|(thread_id, _)| thread_id.clone())
+        .collect();
+    unread.sort();
+    unread
+}
+
+pub(crate) async fn get_unread_threads_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+    last_viewed: HashMap<String, u64>,
+) -> Result<Vec<String>, String> {
+    let session = get_session_clone(sessions, &workspace_id).await?;
+    let activity = session.thread_activity().await;
+    Ok(unread_thread_ids(&activity, &last_viewed))
+}
+
+const DEFAULT_RECENT_THREADS_LIMIT: u32 = 20;
+const MAX_RECENT_THREADS_LIMIT: u32 = 100;
+const RECENT_THREADS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Unix ms of a thread's `updatedAt`/`createdAt`, which servers report in seconds, ms or
+/// RFC 3339.
+fn thread_timestamp_ms(thread: &Value) -> u64 {
+    ["updatedAt", "updated_at", "createdAt", "created_at"]
+        .iter()
+        .find_map(|key| thread.get(key).filter(|value| !value.is_null()))
+        .and_then(timestamp_ms)
+        .map_or(0, |millis| millis.max(0) as u64)
+}
+
+/// Merges each workspace's threads, newest first, tagging them with `workspaceId` and
+/// `lastActivityAt` (unix ms). Activity seen live in the session beats a stale `updatedAt`.
+fn merge_recent_threads(
+    per_workspace: Vec<(String, Vec<Value>, HashMap<String, u64>)>,
+    limit: usize,
+) -> Vec<Value> {
+    let mut merged: Vec<(u64, Value)> = Vec::new();
+    for (workspace_id, threads, activity) in per_workspace {
+        for mut thread in threads {
+            let live = thread
+                .get("id")
+                .and_then(Value::as_str)
+                .and_then(|id| activity.get(id).copied())
+                .unwrap_or(0);
+            let last_activity = thread_timestamp_ms(&thread).max(live);
+            let Some(object) = thread.as_object_mut() else {
+                continue;
+            };
+            object.insert("workspaceId".to_string(), json!(workspace_id));
+            object.insert("lastActivityAt".to_string(), json!(last_activity));
+            merged.push((last_activity, thread));
+        }
+    }
+    merged.sort_by_key(|(last_activity, _)| std::cmp::Reverse(*last_activity));
+    merged.into_iter().take(limit).map(|(_, thread)| thread).collect()
+}
+
+/// The most recently active threads across every connected workspace. Each session is asked
+/// for its first `thread/list` page of `limit` threads; sessions that error or don't answer
+/// within a few seconds are skipped rather than failing the whole list.
+pub(crate) async fn get_recent_threads_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    limit: Option<u32>,
+) -> Result<Vec<Value>, String> {
+    let limit = limit
+        .unwrap_or(DEFAULT_RECENT_THREADS_LIMIT)
+        .clamp(1, MAX_RECENT_THREADS_LIMIT);
+    let sessions: Vec<(String, Arc<WorkspaceSession>)> = sessions
+        .lock()
+        .await
+        .iter()
+        .map(|(workspace_id, session)| (workspace_id.clone(), Arc::clone(session)))
+        .collect();
+    let lists = sessions.into_iter().map(|(workspace_id, session)| async move {
+        let params = json!({ "cursor": null, "limit": limit });
+        let request = session.send_request("thread/list", params);
+        let response = match timeout(RECENT_THREADS_TIMEOUT, request).await {
+            Ok(Ok(response)) if response_error(&response).is_none() => response,
+            _ => return None,
+        };
+        let activity = session.thread_activity().await;
+        Some((workspace_id, thread_list_data(&response), activity))
+    });
+    let per_workspace = join_all(lists).await.into_iter().flatten().collect();
+    Ok(merge_recent_threads(per_workspace, limit as usize))
+}
+
+/// The workspace's own `codex_bin`, falling back to the app-wide setting like a spawn does.
+async fn resolve_workspace_codex_bin(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: &str,
+) -> Result<Option<String>, String> {
+    let codex_bin = {
+        let workspaces = workspaces.lock().await;
+        let entry = workspaces
+            .get(workspace_id)
+            .ok_or_else(|| "workspace not found".to_string())?;
+        entry.codex_bin.clone()
+    };
+    let default_bin = app_settings.lock().await.codex_bin.clone();
+    Ok(codex_bin
+        .filter(|value| !value.trim().is_empty())
+        .or(default_bin))
+}
+
+pub(crate) async fn get_path_diagnostics_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: String,
+) -> Result<PathDiagnostics, String> {
+    let codex_bin = resolve_workspace_codex_bin(workspaces, app_settings, &workspace_id).await?;
+    Ok(codex_path_diagnostics(codex_bin))
+}
+
+/// Runs the `codex --version` check for the workspace's binary without spawning a session.
+pub(crate) async fn recheck_codex_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: String,
+) -> Result<Option<String>, String> {
+    let codex_bin = resolve_workspace_codex_bin(workspaces, app_settings, &workspace_id).await?;
+    check_codex_installation(codex_bin).await
+}
+
 pub(crate) async fn start_review_core(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     workspace_id: String,
@@ -237,7 +975,7 @@ pub(crate) async fn start_review_core(
     target: Value,
     delivery: Option<String>,
 ) -> Result<Value, String> {
-    let session = get_session_clone(sessions, &workspace_id).await?;
+    let session = get_mutable_session_clone(sessions, &workspace_id).await?;
     let mut params = Map::new();
     params.insert("threadId".to_string(), json!(thread_id));
     params.insert("target".to_string(), target);
@@ -257,6 +995,83 @@ pub(crate) async fn model_list_core(
     session.send_request("model/list", json!({})).await
 }
 
+fn first_str<'a>(item: &'a Value, keys: &[&str]) -> Option<&'a str> {
+    keys.iter()
+        .find_map(|key| item.get(*key).and_then(|value| value.as_str()))
+        .filter(|value| !value.trim().is_empty())
+}
+
+fn model_info_from_value(item: &Value) -> Option<ModelInfo> {
+    let id = first_str(item, &["id", "model"])?.to_string();
+    let model = first_str(item, &["model", "id"])?.to_string();
+    let display_name = first_str(item, &["displayName", "display_name"])
+        .unwrap_or(id.as_str())
+        .to_string();
+    let context_window = ["contextWindow", "context_window", "maxContextWindow"]
+        .iter()
+        .find_map(|key| item.get(*key).and_then(|value| value.as_u64()));
+    let supported_efforts = item
+        .get("supportedReasoningEfforts")
+        .and_then(|value| value.as_array())
+        .map(|efforts| {
+            efforts
+                .iter()
+                .filter_map(|effort| {
+                    effort
+                        .as_str()
+                        .or_else(|| first_str(effort, &["reasoningEffort"]))
+                        .map(str::to_string)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let flag = |key: &str| item.get(key).and_then(|value| value.as_bool());
+    Some(ModelInfo {
+        id,
+        model,
+        display_name,
+        context_window,
+        supported_efforts,
+        default_effort: first_str(item, &["defaultReasoningEffort"]).map(str::to_string),
+        is_default: flag("isDefault").unwrap_or(false),
+        selectable: flag("selectable").unwrap_or(true) && !flag("hidden").unwrap_or(false),
+        raw: item.clone(),
+    })
+}
+
+fn parse_model_list(response: &Value) -> Result<Vec<ModelInfo>, String> {
+    let data = response
+        .get("result")
+        .unwrap_or(response)
+        .get("data")
+        .and_then(|value| value.as_array());
+    let models: Vec<ModelInfo> = data
+        .map(|items| items.iter().filter_map(model_info_from_value).collect())
+        .unwrap_or_default();
+    if models.is_empty() {
+        return Err("Codex returned no available models".to_string());
+    }
+    Ok(models)
+}
+
+/// Normalized model list, cached on the session until `refresh` is set or it reconnects.
+pub(crate) async fn list_models_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+    refresh: bool,
+) -> Result<Vec<ModelInfo>, String> {
+    let session = get_session_clone(sessions, &workspace_id).await?;
+    if !refresh {
+        if let Some(models) = session.models.lock().await.clone() {
+            return Ok(models);
+        }
+    }
+    let response = session.send_request("model/list", json!({})).await?;
+    let models = parse_model_list(&response)?;
+    *session.models.lock().await = Some(models.clone());
+    Ok(models)
+}
+
 pub(crate) async fn account_rate_limits_core(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     workspace_id: String,
@@ -485,3 +1300,315 @@ pub(crate) async fn get_config_model_core(
     let model = codex_config::read_config_model(Some(codex_home))?;
     Ok(json!({ "model": model }))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+    use std::fs;
+
+    use serde_json::json;
+    use tokio::sync::Mutex;
+
+    use super::{
+        annotate_pinned_threads, filter_threads_by_query, focus_path_inputs, merge_recent_threads,
+        normalize_preference, parse_model_list, prune_pins_core, recheck_codex_core,
+        resolve_access_mode, set_local_pin, thread_timestamp_ms, turn_model_and_effort,
+        unread_thread_ids, validate_approval_policy, validate_default_access_mode,
+    };
+    use crate::types::{
+        AppSettings, ThreadPreferences, WorkspaceEntry, WorkspaceKind, WorkspaceSettings,
+    };
+
+    #[test]
+    fn focus_path_inputs_stay_within_workspace() {
+        let base = std::env::temp_dir().join(format!("codex-monitor-test-{}", uuid::Uuid::new_v4()));
+        let root = base.join("workspace");
+        fs::create_dir_all(root.join("src")).expect("create workspace");
+        fs::write(root.join("src/main.rs"), "fn main() {}\n").expect("write file");
+        fs::write(base.join("outside.txt"), "secret\n").expect("write file");
+        let root_str = root.to_string_lossy().to_string();
+
+        let inputs = focus_path_inputs(
+            &root_str,
+            vec!["src/main.rs".to_string(), " ".to_string(), "./src/main.rs".to_string()],
+        )
+        .expect("inputs");
+        assert_eq!(inputs.len(), 1);
+        assert_eq!(inputs[0]["type"], "mention");
+        assert_eq!(inputs[0]["name"], "src/main.rs");
+
+        assert!(focus_path_inputs(&root_str, vec!["../outside.txt".to_string()]).is_err());
+        assert!(focus_path_inputs(&root_str, vec!["src".to_string()]).is_err());
+        assert!(focus_path_inputs(&root_str, vec!["missing.rs".to_string()]).is_err());
+    }
+
+    #[test]
+    fn approval_policy_must_be_known() {
+        assert_eq!(validate_approval_policy(" never ").as_deref(), Ok("never"));
+        assert!(validate_approval_policy("on-request").is_ok());
+        assert!(validate_approval_policy("always").is_err());
+        assert!(validate_approval_policy("").is_err());
+    }
+
+    #[test]
+    fn workspace_model_and_effort_fill_gaps_in_thread_preferences() {
+        let defaults = WorkspaceSettings {
+            model: Some("gpt-5".to_string()),
+            effort: Some(" ".to_string()),
+            ..WorkspaceSettings::default()
+        };
+        let unset = ThreadPreferences::default();
+        assert_eq!(turn_model_and_effort(&unset, &defaults), (Some("gpt-5".to_string()), None));
+
+        let chosen = ThreadPreferences {
+            model: Some("o3".to_string()),
+            effort: Some("high".to_string()),
+        };
+        assert_eq!(
+            turn_model_and_effort(&chosen, &defaults),
+            (Some("o3".to_string()), Some("high".to_string()))
+        );
+
+        let effort_only = ThreadPreferences {
+            model: None,
+            effort: Some("low".to_string()),
+        };
+        assert_eq!(
+            turn_model_and_effort(&effort_only, &WorkspaceSettings::default()),
+            (None, Some("low".to_string()))
+        );
+    }
+
+    #[test]
+    fn access_mode_falls_back_to_workspace_default_then_current() {
+        let workspace_default = Some("read-only".to_string());
+        assert_eq!(
+            resolve_access_mode(Some("full-access".to_string()), workspace_default.clone()),
+            "full-access"
+        );
+        assert_eq!(resolve_access_mode(None, workspace_default), "read-only");
+        assert_eq!(resolve_access_mode(None, Some(" ".to_string())), "current");
+        assert_eq!(resolve_access_mode(None, None), "current");
+
+        assert_eq!(
+            validate_default_access_mode(Some(" read-only ".to_string())),
+            Ok(Some("read-only".to_string()))
+        );
+        assert_eq!(validate_default_access_mode(Some(String::new())), Ok(None));
+        let error = validate_default_access_mode(Some("danger".to_string())).expect_err("bad");
+        assert!(error.contains("full-access, read-only, current"));
+    }
+
+    #[test]
+    fn model_list_is_normalized() {
+        let response = json!({
+            "result": {
+                "data": [
+                    {
+                        "id": "gpt-5",
+                        "model": "gpt-5",
+                        "displayName": "GPT-5",
+                        "contextWindow": 272000,
+                        "supportedReasoningEfforts": [
+                            { "reasoningEffort": "low", "description": "" },
+                            { "reasoningEffort": "high", "description": "" }
+                        ],
+                        "defaultReasoningEffort": "high",
+                        "isDefault": true,
+                        "upgrade": "gpt-6"
+                    },
+                    { "model": "legacy", "hidden": true },
+                    { "displayName": "no id" }
+                ]
+            }
+        });
+        let models = parse_model_list(&response).expect("models");
+        assert_eq!(models.len(), 2);
+        assert_eq!(models[0].display_name, "GPT-5");
+        assert_eq!(models[0].context_window, Some(272000));
+        assert_eq!(models[0].supported_efforts, vec!["low", "high"]);
+        assert_eq!(models[0].default_effort.as_deref(), Some("high"));
+        assert!(models[0].is_default && models[0].selectable);
+        assert_eq!(models[0].raw["upgrade"], "gpt-6");
+        assert_eq!(models[1].id, "legacy");
+        assert_eq!(models[1].display_name, "legacy");
+        assert!(!models[1].selectable);
+
+        assert!(parse_model_list(&json!({ "result": { "data": [] } })).is_err());
+    }
+
+    #[test]
+    fn blank_thread_preferences_are_unset() {
+        assert_eq!(normalize_preference(Some("  gpt-5 ".to_string())).as_deref(), Some("gpt-5"));
+        assert_eq!(normalize_preference(Some("   ".to_string())), None);
+        assert_eq!(normalize_preference(None), None);
+    }
+
+    #[test]
+    fn recheck_codex_uses_workspace_binary() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("build runtime");
+        let missing = std::env::temp_dir()
+            .join(format!("codex-monitor-test-{}", uuid::Uuid::new_v4()))
+            .join("codex");
+        let entry = WorkspaceEntry {
+            id: "w1".to_string(),
+            name: "w1".to_string(),
+            path: "/tmp".to_string(),
+            codex_bin: Some(missing.to_string_lossy().to_string()),
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+            last_error: None,
+            monitor_only: false,
+            description: None,
+        };
+        let workspaces = Mutex::new(HashMap::from([("w1".to_string(), entry)]));
+        let app_settings = Mutex::new(AppSettings::default());
+
+        let error = runtime
+            .block_on(recheck_codex_core(&workspaces, &app_settings, "missing".to_string()))
+            .expect_err("unknown workspace");
+        assert_eq!(error, "workspace not found");
+        let error = runtime
+            .block_on(recheck_codex_core(&workspaces, &app_settings, "w1".to_string()))
+            .expect_err("missing binary");
+        assert!(error.contains("not found"));
+    }
+
+    #[test]
+    fn unread_threads_have_activity_after_last_view() {
+        let activity = HashMap::from([
+            ("viewed-before".to_string(), 200),
+            ("viewed-after".to_string(), 200),
+            ("viewed-same".to_string(), 200),
+            ("never-viewed".to_string(), 50),
+        ]);
+        let last_viewed = HashMap::from([
+            ("viewed-before".to_string(), 100),
+            ("viewed-after".to_string(), 300),
+            ("viewed-same".to_string(), 200),
+            ("idle".to_string(), 100),
+        ]);
+        assert_eq!(
+            unread_thread_ids(&activity, &last_viewed),
+            vec!["never-viewed".to_string(), "viewed-before".to_string()]
+        );
+    }
+
+    #[test]
+    fn thread_timestamps_normalize_to_millis() {
+        assert_eq!(thread_timestamp_ms(&json!({ "updatedAt": 1_700_000_000 })), 1_700_000_000_000);
+        assert_eq!(
+            thread_timestamp_ms(&json!({ "createdAt": 1_700_000_000_123u64 })),
+            1_700_000_000_123
+        );
+        assert_eq!(
+            thread_timestamp_ms(&json!({ "updatedAt": "2024-01-01T00:00:00Z" })),
+            1_704_067_200_000
+        );
+        assert_eq!(thread_timestamp_ms(&json!({ "id": "t" })), 0);
+    }
+
+    #[test]
+    fn recent_threads_merge_across_workspaces_newest_first() {
+        let per_workspace = vec![
+            (
+                "ws-1".to_string(),
+                vec![
+                    json!({ "id": "a", "updatedAt": 100 }),
+                    json!({ "id": "b", "updatedAt": 300 }),
+                ],
+                HashMap::from([("a".to_string(), 500_000)]),
+            ),
+            ("ws-2".to_string(), vec![json!({ "id": "c", "updatedAt": 200 })], HashMap::new()),
+        ];
+        let merged = merge_recent_threads(per_workspace, 3);
+        let ids: Vec<(&str, &str)> = merged
+            .iter()
+            .map(|thread| {
+                (thread["id"].as_str().unwrap(), thread["workspaceId"].as_str().unwrap())
+            })
+            .collect();
+        assert_eq!(ids, vec![("a", "ws-1"), ("b", "ws-1"), ("c", "ws-2")]);
+        assert_eq!(merged[0]["lastActivityAt"], json!(500_000));
+        assert_eq!(merged[1]["lastActivityAt"], json!(300_000));
+        assert_eq!(merge_recent_threads(Vec::new(), 5), Vec::<serde_json::Value>::new());
+    }
+
+    #[test]
+    fn thread_search_fallback_matches_name_and_preview() {
+        let threads = vec![
+            json!({ "id": "a", "preview": "Fix the Login flow" }),
+            json!({ "id": "b", "name": "login cleanup", "preview": "unrelated" }),
+            json!({ "id": "c", "preview": "Refactor parser" }),
+            json!({ "id": "d", "preview": "LOGIN tests" }),
+        ];
+        let ids = |threads: Vec<serde_json::Value>| -> Vec<String> {
+            threads
+                .iter()
+                .map(|thread| thread["id"].as_str().unwrap_or_default().to_string())
+                .collect()
+        };
+        assert_eq!(ids(filter_threads_by_query(threads.clone(), "LogIn", 10)), ["a", "b", "d"]);
+        assert_eq!(ids(filter_threads_by_query(threads, "login", 2)), ["a", "b"]);
+    }
+
+    #[test]
+    fn local_pins_annotate_listed_threads() {
+        let mut pins = HashMap::new();
+        set_local_pin(&mut pins, "w1", "a", true);
+        set_local_pin(&mut pins, "w1", "c", true);
+        set_local_pin(&mut pins, "w1", "c", false);
+        assert_eq!(pins.get("w1"), Some(&HashSet::from(["a".to_string()])));
+
+        let mut response = json!({
+            "id": 1,
+            "result": { "data": [{ "id": "a" }, { "id": "b" }], "nextCursor": null },
+        });
+        annotate_pinned_threads(&mut response, &pins["w1"]);
+        assert_eq!(response["result"]["data"][0]["pinned"], json!(true));
+        assert!(response["result"]["data"][1].get("pinned").is_none());
+
+        set_local_pin(&mut pins, "w1", "a", false);
+        assert!(pins.is_empty());
+    }
+
+    #[test]
+    fn pins_of_removed_workspaces_are_pruned() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .expect("build runtime");
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("create temp dir");
+        let pins_path = dir.join("pins.json");
+        let entry = WorkspaceEntry {
+            id: "kept".to_string(),
+            name: "kept".to_string(),
+            path: "/tmp".to_string(),
+            codex_bin: None,
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+            last_error: None,
+            monitor_only: false,
+            description: None,
+        };
+        let workspaces = Mutex::new(HashMap::from([("kept".to_string(), entry)]));
+        let mut initial = HashMap::new();
+        set_local_pin(&mut initial, "kept", "a", true);
+        set_local_pin(&mut initial, "removed", "b", true);
+        let pins = Mutex::new(initial);
+
+        runtime
+            .block_on(prune_pins_core(&workspaces, &pins, &pins_path))
+            .expect("prune pins");
+        let pruned = runtime.block_on(pins.lock()).clone();
+        assert_eq!(pruned.keys().collect::<Vec<_>>(), vec!["kept"]);
+        assert_eq!(crate::storage::read_pins(&pins_path).expect("read pins"), pruned);
+    }
+}
@@ -1,14 +1,34 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use tokio::sync::Mutex;
 
 use crate::claude_code::home as claude_code_home;
-use crate::files::io::TextFileResponse;
-use crate::files::ops::{read_with_policy, write_with_policy};
-use crate::files::policy::{policy_for, FileKind, FileScope};
+use crate::files::backup::{
+    new_backup_dir, read_backup_files, write_backup_files, ConfigBackupResponse,
+    BACKUP_FILE_KINDS,
+};
+use crate::files::cache::FileCache;
+use crate::files::discover::{find_agents_files_inner, AgentsFileEntry, MAX_AGENTS_SEARCH_DEPTH};
+use crate::files::io::{TextFileChunk, TextFileResponse};
+use crate::files::merge::{merge_texts, preview_write, MergeResult, WritePreview};
+use crate::files::ops::{
+    read_chunk_with_policy, read_with_policy, resolve_subpath_root, restore_with_policy,
+    write_with_policy,
+};
+use crate::files::policy::{policy_for, FileKind, FilePolicy, FileScope};
+use crate::files::prune::{prune_json_history_with_policy, PruneResponse};
+use crate::files::validate::{validate_agents_content, FileWriteResponse};
+use crate::shared::codex_core::MONITOR_ONLY_ERROR;
 use crate::types::WorkspaceEntry;
 
+const DEFAULT_FILE_CHUNK_BYTES: u32 = 256 * 1024;
+const MAX_FILE_CHUNK_BYTES: u32 = 1024 * 1024;
+// Large enough to hold any single UTF-8 character.
+const MIN_FILE_CHUNK_BYTES: u32 = 4;
+/// Instruction files past this are not merged; the diff would be too slow to be useful.
+const MAX_MERGE_BYTES: usize = 1024 * 1024;
+
 fn resolve_default_claude_home() -> Result<PathBuf, String> {
     claude_code_home::resolve_default_claude_home()
         .ok_or_else(|| "Unable to resolve CLAUDE_HOME".to_string())
@@ -40,6 +60,30 @@ async fn resolve_workspace_root(
     Ok(PathBuf::from(&entry.path))
 }
 
+/// Refuses writes into a monitor-only workspace; global files stay writable.
+async fn ensure_workspace_writable(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    scope: FileScope,
+    kind: FileKind,
+    workspace_id: Option<&str>,
+) -> Result<(), String> {
+    if scope != FileScope::Workspace || kind == FileKind::ClaudeJson {
+        return Ok(());
+    }
+    let Some(workspace_id) = workspace_id else {
+        return Ok(());
+    };
+    let monitor_only = workspaces
+        .lock()
+        .await
+        .get(workspace_id)
+        .is_some_and(|entry| entry.monitor_only);
+    if monitor_only {
+        return Err(MONITOR_ONLY_ERROR.to_string());
+    }
+    Ok(())
+}
+
 pub(crate) async fn resolve_root_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     scope: FileScope,
@@ -55,33 +99,249 @@ pub(crate) async fn resolve_root_core(
     }
 }
 
+async fn resolve_file_root(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    scope: FileScope,
+    kind: FileKind,
+    workspace_id: Option<&str>,
+    subpath: Option<&str>,
+) -> Result<PathBuf, String> {
+    if kind == FileKind::ClaudeJson {
+        return resolve_user_home();
+    }
+    let root = resolve_root_core(workspaces, scope, workspace_id).await?;
+    match subpath.filter(|value| !value.trim().is_empty()) {
+        None => Ok(root),
+        Some(subpath) if scope == FileScope::Workspace && kind == FileKind::Agents => {
+            resolve_subpath_root(&root, subpath)
+        }
+        Some(_) => Err("subpath is only supported for workspace agents files".to_string()),
+    }
+}
+
+async fn read_with_cache(
+    cache: &Mutex<FileCache>,
+    root: &PathBuf,
+    policy: FilePolicy,
+) -> Result<TextFileResponse, String> {
+    let path = root.join(policy.filename);
+    let Ok(modified) = std::fs::metadata(&path).and_then(|metadata| metadata.modified()) else {
+        return read_with_policy(root, policy);
+    };
+    if let Some(cached) = cache.lock().await.get(&path, modified) {
+        return Ok(cached);
+    }
+    let response = read_with_policy(root, policy)?;
+    cache.lock().await.insert(path, modified, response.clone());
+    Ok(response)
+}
+
 pub(crate) async fn file_read_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    cache: &Mutex<FileCache>,
     scope: FileScope,
     kind: FileKind,
     workspace_id: Option<String>,
+    subpath: Option<String>,
 ) -> Result<TextFileResponse, String> {
     let policy = policy_for(scope, kind)?;
-    let root = if kind == FileKind::ClaudeJson {
-        resolve_user_home()?
-    } else {
-        resolve_root_core(workspaces, scope, workspace_id.as_deref()).await?
-    };
-    read_with_policy(&root, policy)
+    let root = resolve_file_root(
+        workspaces,
+        scope,
+        kind,
+        workspace_id.as_deref(),
+        subpath.as_deref(),
+    )
+    .await?;
+    read_with_cache(cache, &root, policy).await
+}
+
+pub(crate) async fn file_read_chunk_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    scope: FileScope,
+    kind: FileKind,
+    workspace_id: Option<String>,
+    subpath: Option<String>,
+    offset: u64,
+    max_bytes: Option<u32>,
+) -> Result<TextFileChunk, String> {
+    let policy = policy_for(scope, kind)?;
+    let root = resolve_file_root(
+        workspaces,
+        scope,
+        kind,
+        workspace_id.as_deref(),
+        subpath.as_deref(),
+    )
+    .await?;
+    let max_bytes = max_bytes
+        .unwrap_or(DEFAULT_FILE_CHUNK_BYTES)
+        .clamp(MIN_FILE_CHUNK_BYTES, MAX_FILE_CHUNK_BYTES) as usize;
+    tokio::task::spawn_blocking(move || read_chunk_with_policy(&root, policy, offset, max_bytes))
+        .await
+        .map_err(|err| format!("Failed to read file chunk: {err}"))?
 }
 
 pub(crate) async fn file_write_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    cache: &Mutex<FileCache>,
     scope: FileScope,
     kind: FileKind,
     workspace_id: Option<String>,
+    subpath: Option<String>,
     content: String,
-) -> Result<(), String> {
+) -> Result<FileWriteResponse, String> {
     let policy = policy_for(scope, kind)?;
-    let root = if kind == FileKind::ClaudeJson {
-        resolve_user_home()?
-    } else {
-        resolve_root_core(workspaces, scope, workspace_id.as_deref()).await?
+    let warnings = match kind {
+        FileKind::Agents => validate_agents_content(&content)?,
+        FileKind::Config | FileKind::ClaudeJson => Vec::new(),
     };
-    write_with_policy(&root, policy, &content)
+    ensure_workspace_writable(workspaces, scope, kind, workspace_id.as_deref()).await?;
+    let root = resolve_file_root(
+        workspaces,
+        scope,
+        kind,
+        workspace_id.as_deref(),
+        subpath.as_deref(),
+    )
+    .await?;
+    let result = write_with_policy(&root, policy, &content);
+    cache.lock().await.invalidate(&root.join(policy.filename));
+    result.map(|()| FileWriteResponse { warnings })
+}
+
+pub(crate) async fn file_preview_write_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    cache: &Mutex<FileCache>,
+    scope: FileScope,
+    kind: FileKind,
+    workspace_id: Option<String>,
+    subpath: Option<String>,
+    content: String,
+) -> Result<WritePreview, String> {
+    let current = file_read_core(workspaces, cache, scope, kind, workspace_id, subpath).await?;
+    if current.truncated {
+        return Err("File is too large to preview".to_string());
+    }
+    Ok(preview_write(current.exists, &current.content, &content))
+}
+
+pub(crate) async fn file_merge_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    kind: FileKind,
+) -> Result<MergeResult, String> {
+    let workspace_policy = policy_for(FileScope::Workspace, kind)?;
+    let global_policy = policy_for(FileScope::Global, kind)?;
+    let workspace_root =
+        resolve_root_core(workspaces, FileScope::Workspace, Some(&workspace_id)).await?;
+    let global_root = resolve_root_core(workspaces, FileScope::Global, None).await?;
+    tokio::task::spawn_blocking(move || {
+        let workspace_file = read_with_policy(&workspace_root, workspace_policy)?;
+        let global_file = read_with_policy(&global_root, global_policy)?;
+        let too_large = |file: &TextFileResponse| {
+            file.truncated || file.content.len() > MAX_MERGE_BYTES
+        };
+        if too_large(&workspace_file) || too_large(&global_file) {
+            return Err("File is too large to merge".to_string());
+        }
+        Ok(merge_texts(&workspace_file.content, &global_file.content))
+    })
+    .await
+    .map_err(|err| format!("Failed to merge files: {err}"))?
+}
+
+pub(crate) async fn prune_claude_json_core(
+    cache: &Mutex<FileCache>,
+    keep_recent: usize,
+) -> Result<PruneResponse, String> {
+    let policy = policy_for(FileScope::Global, FileKind::ClaudeJson)?;
+    let root = resolve_user_home()?;
+    let path = root.join(policy.filename);
+    let result = tokio::task::spawn_blocking(move || {
+        prune_json_history_with_policy(&root, policy, keep_recent)
+    })
+    .await
+    .map_err(|err| format!("Failed to prune {}: {err}", policy.filename))?;
+    cache.lock().await.invalidate(&path);
+    result
+}
+
+/// Copies every existing global managed file into a new timestamped directory under
+/// `data_dir`.
+pub(crate) async fn backup_configs_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    cache: &Mutex<FileCache>,
+    data_dir: &Path,
+) -> Result<ConfigBackupResponse, String> {
+    let mut files = Vec::new();
+    for kind in BACKUP_FILE_KINDS {
+        let policy = policy_for(FileScope::Global, kind)?;
+        let current = file_read_core(workspaces, cache, FileScope::Global, kind, None, None).await?;
+        if current.truncated {
+            return Err(format!("{} is too large to back up", policy.filename));
+        }
+        if current.exists {
+            files.push((policy.filename, current.content));
+        }
+    }
+    if files.is_empty() {
+        return Err("No config files to back up".to_string());
+    }
+
+    let backup_dir = new_backup_dir(data_dir);
+    tokio::task::spawn_blocking(move || {
+        write_backup_files(&backup_dir, &files)?;
+        Ok(ConfigBackupResponse {
+            path: backup_dir.to_string_lossy().to_string(),
+            files: files.into_iter().map(|(filename, _)| filename.to_string()).collect(),
+        })
+    })
+    .await
+    .map_err(|err| format!("Failed to write backup: {err}"))?
+}
+
+/// Restores the files in a backup made by `backup_configs_core`. Every file is validated
+/// before any is written, and each one is replaced atomically. Managed files missing from the
+/// backup are left alone.
+pub(crate) async fn restore_configs_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    cache: &Mutex<FileCache>,
+    data_dir: &Path,
+    backup_path: &str,
+) -> Result<ConfigBackupResponse, String> {
+    let data_dir = data_dir.to_path_buf();
+    let backup_path = backup_path.to_string();
+    let (backup_dir, restores) =
+        tokio::task::spawn_blocking(move || read_backup_files(&data_dir, &backup_path))
+            .await
+            .map_err(|err| format!("Failed to read backup: {err}"))??;
+
+    let mut files = Vec::new();
+    for (kind, content) in restores {
+        let policy = policy_for(FileScope::Global, kind)?;
+        let root = resolve_file_root(workspaces, FileScope::Global, kind, None, None).await?;
+        let path = root.join(policy.filename);
+        let result =
+            tokio::task::spawn_blocking(move || restore_with_policy(&root, policy, &content))
+                .await
+                .map_err(|err| format!("Failed to restore {}: {err}", policy.filename))?;
+        cache.lock().await.invalidate(&path);
+        result?;
+        files.push(policy.filename.to_string());
+    }
+    Ok(ConfigBackupResponse {
+        path: backup_dir.to_string_lossy().to_string(),
+        files,
+    })
+}
+
+pub(crate) async fn find_agents_files_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+) -> Result<Vec<AgentsFileEntry>, String> {
+    let root = resolve_workspace_root(workspaces, workspace_id).await?;
+    tokio::task::spawn_blocking(move || find_agents_files_inner(&root, MAX_AGENTS_SEARCH_DEPTH))
+        .await
+        .map_err(|err| format!("Failed to search for agents files: {err}"))
 }
@@ -3,8 +3,8 @@ use std::path::PathBuf;
 use tokio::sync::Mutex;
 
 use crate::codex::config as codex_config;
-use crate::storage::write_settings;
-use crate::types::AppSettings;
+use crate::storage::{write_global_settings, write_settings};
+use crate::types::{AppSettings, GlobalSettings};
 
 fn normalize_personality(value: &str) -> Option<&'static str> {
     match value.trim() {
@@ -61,6 +61,34 @@ pub(crate) async fn update_app_settings_core(
     Ok(settings)
 }
 
+fn validate_global_settings(settings: &GlobalSettings) -> Result<(), String> {
+    if !matches!(settings.theme.as_str(), "system" | "light" | "dark" | "dim") {
+        return Err(format!("Unsupported theme: {}", settings.theme));
+    }
+    if settings.max_workspaces == Some(0) {
+        return Err("maxWorkspaces must be at least 1".to_string());
+    }
+    if settings
+        .locale
+        .as_deref()
+        .is_some_and(|locale| locale.trim().is_empty())
+    {
+        return Err("locale must not be empty".to_string());
+    }
+    Ok(())
+}
+
+pub(crate) async fn update_global_settings_core(
+    settings: GlobalSettings,
+    global_settings: &Mutex<GlobalSettings>,
+    global_settings_path: &PathBuf,
+) -> Result<(), String> {
+    validate_global_settings(&settings)?;
+    write_global_settings(global_settings_path, &settings)?;
+    *global_settings.lock().await = settings;
+    Ok(())
+}
+
 pub(crate) fn get_codex_config_path_core() -> Result<String, String> {
     codex_config::config_toml_path()
         .ok_or_else(|| "Unable to resolve CODEX_HOME".to_string())
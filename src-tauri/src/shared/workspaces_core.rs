@@ -1,22 +1,35 @@
 use std::collections::HashMap;
 use std::future::Future;
 use std::path::PathBuf;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
 
+use rand::Rng;
+use serde_json::json;
 use tokio::sync::Mutex;
 
-use crate::backend::app_server::WorkspaceSession;
+use crate::backend::app_server::{validate_keepalive_secs, WorkspaceSession};
+use crate::backend::events::{AppServerEvent, EventSink};
 use crate::codex::args::resolve_workspace_codex_args;
 use crate::codex::home::resolve_workspace_codex_home;
-use crate::storage::write_workspaces;
+use crate::logging::parse_log_level;
+use crate::shared::codex_core::validate_default_access_mode;
+use crate::storage::{read_workspaces, write_workspaces};
 use crate::types::{
-    AppSettings, WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings, WorktreeInfo,
-    WorktreeSetupStatus,
+    AppSettings, SessionState, WorkspaceActivity, WorkspaceEntry, WorkspaceInfo, WorkspaceKind,
+    WorkspaceSettings, WorktreeInfo, WorktreeSetupStatus,
 };
 use uuid::Uuid;
 
 pub(crate) const WORKTREE_SETUP_MARKERS_DIR: &str = "worktree-setup";
 pub(crate) const WORKTREE_SETUP_MARKER_EXT: &str = "ran";
+const MAX_WORKSPACE_NAME_CHARS: usize = 128;
+const MAX_WORKSPACE_DESCRIPTION_CHARS: usize = 2000;
+const WORKSPACE_NOT_FOUND_ERROR: &str = "workspace not found";
+const DEFAULT_RECONNECT_ATTEMPTS: u32 = 5;
+const RECONNECT_BASE_DELAY_MS: u64 = 1_000;
+const RECONNECT_MAX_DELAY_MS: u64 = 30_000;
 
 pub(crate) fn normalize_setup_script(script: Option<String>) -> Option<String> {
     match script {
@@ -41,25 +54,63 @@ pub(crate) async fn list_workspaces_core(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
 ) -> Vec<WorkspaceInfo> {
     let workspaces = workspaces.lock().await;
-    let sessions = sessions.lock().await;
+    let sessions = sessions.lock().await.clone();
     let mut result = Vec::new();
     for entry in workspaces.values() {
-        result.push(WorkspaceInfo {
-            id: entry.id.clone(),
-            name: entry.name.clone(),
-            path: entry.path.clone(),
-            codex_bin: entry.codex_bin.clone(),
-            connected: sessions.contains_key(&entry.id),
-            kind: entry.kind.clone(),
-            parent_id: entry.parent_id.clone(),
-            worktree: entry.worktree.clone(),
-            settings: entry.settings.clone(),
-        });
+        let session_state = match sessions.get(&entry.id) {
+            Some(session) => Some(session.state().await),
+            None => None,
+        };
+        result.push(WorkspaceInfo::from_entry(
+            entry.clone(),
+            sessions.contains_key(&entry.id),
+            session_state,
+        ));
     }
     sort_workspaces(&mut result);
     result
 }
 
+async fn session_state_for(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    id: &str,
+) -> Option<SessionState> {
+    let session = sessions.lock().await.get(id).cloned()?;
+    Some(session.state().await)
+}
+
+/// Snapshot of every workspace's session activity; reads only in-memory state.
+pub(crate) async fn get_global_status_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+) -> Vec<WorkspaceActivity> {
+    let entries: Vec<(String, String)> = {
+        let workspaces = workspaces.lock().await;
+        workspaces
+            .values()
+            .map(|entry| (entry.id.clone(), entry.name.clone()))
+            .collect()
+    };
+    let sessions = sessions.lock().await.clone();
+    let mut result = Vec::with_capacity(entries.len());
+    for (id, name) in entries {
+        let session = sessions.get(&id);
+        let active_turns = match session {
+            Some(session) => session.active_turn_count().await,
+            None => 0,
+        };
+        result.push(WorkspaceActivity {
+            connected: session.is_some(),
+            active_turns,
+            last_event_at_ms: session.and_then(|session| session.last_event_at_ms()),
+            workspace_id: id,
+            name,
+        });
+    }
+    result.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.workspace_id.cmp(&b.workspace_id)));
+    result
+}
+
 async fn resolve_entry_and_parent(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: &str,
@@ -68,7 +119,7 @@ async fn resolve_entry_and_parent(
     let entry = workspaces
         .get(workspace_id)
         .cloned()
-        .ok_or_else(|| "workspace not found".to_string())?;
+        .ok_or_else(|| WORKSPACE_NOT_FOUND_ERROR.to_string())?;
     let parent_entry = entry
         .parent_id
         .as_ref()
@@ -173,6 +224,9 @@ where
         parent_id: None,
         worktree: None,
         settings: WorkspaceSettings::default(),
+        last_error: None,
+        monitor_only: false,
+        description: None,
     };
 
     let (default_bin, codex_args) = {
@@ -200,19 +254,10 @@ where
         return Err(error);
     }
 
+    let session_state = session.state().await;
     sessions.lock().await.insert(entry.id.clone(), session);
 
-    Ok(WorkspaceInfo {
-        id: entry.id,
-        name: entry.name,
-        path: entry.path,
-        codex_bin: entry.codex_bin,
-        connected: true,
-        kind: entry.kind,
-        parent_id: entry.parent_id,
-        worktree: entry.worktree,
-        settings: entry.settings,
-    })
+    Ok(WorkspaceInfo::from_entry(entry, true, Some(session_state)))
 }
 
 pub(crate) fn run_git_command_unit<F, Fut>(
@@ -347,6 +392,9 @@ where
             ),
             ..WorkspaceSettings::default()
         },
+        last_error: None,
+        monitor_only: false,
+        description: None,
     };
 
     let (default_bin, codex_args) = {
@@ -366,19 +414,18 @@ where
         write_workspaces(storage_path, &list)?;
     }
 
+    let session_state = session.state().await;
     sessions.lock().await.insert(entry.id.clone(), session);
 
-    Ok(WorkspaceInfo {
-        id: entry.id,
-        name: entry.name,
-        path: entry.path,
-        codex_bin: entry.codex_bin,
-        connected: true,
-        kind: entry.kind,
-        parent_id: entry.parent_id,
-        worktree: entry.worktree,
-        settings: entry.settings,
-    })
+    Ok(WorkspaceInfo::from_entry(entry, true, Some(session_state)))
+}
+
+/// Random delay used to spread out session spawns when workspaces are restored on startup.
+pub(crate) fn startup_jitter(max_ms: u64) -> Duration {
+    if max_ms == 0 {
+        return Duration::ZERO;
+    }
+    Duration::from_millis(rand::thread_rng().gen_range(0..max_ms))
 }
 
 pub(crate) async fn connect_workspace_core<F, Fut>(
@@ -386,6 +433,7 @@ pub(crate) async fn connect_workspace_core<F, Fut>(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     app_settings: &Mutex<AppSettings>,
+    apply_startup_jitter: bool,
     spawn_session: F,
 ) -> Result<(), String>
 where
@@ -393,6 +441,13 @@ where
     Fut: Future<Output = Result<Arc<WorkspaceSession>, String>>,
 {
     let (entry, parent_entry) = resolve_entry_and_parent(workspaces, &workspace_id).await?;
+    if apply_startup_jitter {
+        tokio::time::sleep(startup_jitter(entry.settings.startup_jitter_ms)).await;
+        // Another caller may have connected while this one was waiting.
+        if sessions.lock().await.contains_key(&entry.id) {
+            return Ok(());
+        }
+    }
     let (default_bin, codex_args) = {
         let settings = app_settings.lock().await;
         (
@@ -401,18 +456,114 @@ where
         )
     };
     let codex_home = resolve_workspace_codex_home(&entry, parent_entry.as_ref());
-    let session = spawn_session(entry.clone(), default_bin, codex_args, codex_home).await?;
-    sessions.lock().await.insert(entry.id, session);
+    let result = spawn_session(entry.clone(), default_bin, codex_args, codex_home).await;
+    if let Some(stored) = workspaces.lock().await.get_mut(&entry.id) {
+        stored.last_error = result.as_ref().err().cloned();
+    }
+    sessions.lock().await.insert(entry.id, result?);
     Ok(())
 }
 
+/// Wait after failed reconnect attempt `attempt` (1-based): 1s, doubling each time, capped
+/// at 30s.
+pub(crate) fn reconnect_delay(attempt: u32) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(16);
+    let delay_ms = RECONNECT_BASE_DELAY_MS.saturating_mul(1 << exponent);
+    Duration::from_millis(delay_ms.min(RECONNECT_MAX_DELAY_MS))
+}
+
+/// Retries `connect_workspace_core` with exponential backoff, reporting each attempt as a
+/// `codex/reconnecting` event and giving up with `codex/reconnect_failed`. The first attempt
+/// runs immediately; retries stop early once the workspace is removed.
+pub(crate) async fn reconnect_workspace_core<F, Fut, E>(
+    workspace_id: String,
+    max_attempts: Option<u32>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    app_settings: &Mutex<AppSettings>,
+    event_sink: &E,
+    spawn_session: F,
+) -> Result<(), String>
+where
+    F: Fn(WorkspaceEntry, Option<String>, Option<String>, Option<PathBuf>) -> Fut,
+    Fut: Future<Output = Result<Arc<WorkspaceSession>, String>>,
+    E: EventSink,
+{
+    let max_attempts = max_attempts.unwrap_or(DEFAULT_RECONNECT_ATTEMPTS).max(1);
+    let mut last_error = String::new();
+    for attempt in 1..=max_attempts {
+        let delay = if attempt == 1 {
+            Duration::ZERO
+        } else {
+            reconnect_delay(attempt - 1)
+        };
+        event_sink.emit_app_server_event(AppServerEvent::new(
+            workspace_id.clone(),
+            json!({
+                "method": "codex/reconnecting",
+                "params": {
+                    "workspaceId": workspace_id,
+                    "attempt": attempt,
+                    "maxAttempts": max_attempts,
+                    "delayMs": delay.as_millis() as u64,
+                },
+            }),
+        ));
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+        if !workspaces.lock().await.contains_key(&workspace_id) {
+            return Err(WORKSPACE_NOT_FOUND_ERROR.to_string());
+        }
+        // Another caller may have connected while this one was waiting.
+        if sessions.lock().await.contains_key(&workspace_id) {
+            return Ok(());
+        }
+        match connect_workspace_core(
+            workspace_id.clone(),
+            workspaces,
+            sessions,
+            app_settings,
+            false,
+            &spawn_session,
+        )
+        .await
+        {
+            Ok(()) => return Ok(()),
+            Err(error) if error == WORKSPACE_NOT_FOUND_ERROR => return Err(error),
+            Err(error) => last_error = error,
+        }
+    }
+    event_sink.emit_app_server_event(AppServerEvent::new(
+        workspace_id.clone(),
+        json!({
+            "method": "codex/reconnect_failed",
+            "params": {
+                "workspaceId": workspace_id,
+                "attempts": max_attempts,
+                "error": last_error,
+            },
+        }),
+    ));
+    Err(last_error)
+}
+
+const WORKSPACE_REMOVED_ERROR: &str = "workspace removed";
+const SESSION_RESTARTED_ERROR: &str = "workspace session restarted";
+
+/// Kills the session's process, then fails any requests still awaiting a reply.
 async fn kill_session_by_id(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     id: &str,
+    reason: &str,
 ) {
     if let Some(session) = sessions.lock().await.remove(id) {
-        let mut child = session.child.lock().await;
-        let _ = child.kill().await;
+        session.mark_removed();
+        {
+            let mut child = session.child.lock().await;
+            let _ = child.kill().await;
+        }
+        session.fail_pending(reason).await;
     }
 }
 
@@ -460,7 +611,7 @@ where
     let mut failures: Vec<(String, String)> = Vec::new();
 
     for child in &child_worktrees {
-        kill_session_by_id(sessions, &child.id).await;
+        kill_session_by_id(sessions, &child.id, WORKSPACE_REMOVED_ERROR).await;
 
         let child_path = PathBuf::from(&child.path);
         if child_path.exists() {
@@ -496,7 +647,7 @@ where
 
     let mut ids_to_remove = removed_child_ids;
     if failures.is_empty() || !require_all_children_removed_to_remove_parent {
-        kill_session_by_id(sessions, &id).await;
+        kill_session_by_id(sessions, &id, WORKSPACE_REMOVED_ERROR).await;
         ids_to_remove.push(id.clone());
     }
 
@@ -562,7 +713,7 @@ where
 
     let parent_path = PathBuf::from(&parent.path);
     let entry_path = PathBuf::from(&entry.path);
-    kill_session_by_id(sessions, &entry.id).await;
+    kill_session_by_id(sessions, &entry.id, WORKSPACE_REMOVED_ERROR).await;
 
     if entry_path.exists() {
         if let Err(error) = run_git_command(
@@ -717,7 +868,7 @@ where
 
     let was_connected = sessions.lock().await.contains_key(&entry_snapshot.id);
     if was_connected {
-        kill_session_by_id(sessions, &entry_snapshot.id).await;
+        kill_session_by_id(sessions, &entry_snapshot.id, SESSION_RESTARTED_ERROR).await;
         let (default_bin, codex_args) = {
             let settings = app_settings.lock().await;
             (
@@ -743,17 +894,8 @@ where
     }
 
     let connected = sessions.lock().await.contains_key(&entry_snapshot.id);
-    Ok(WorkspaceInfo {
-        id: entry_snapshot.id,
-        name: entry_snapshot.name,
-        path: entry_snapshot.path,
-        codex_bin: entry_snapshot.codex_bin,
-        connected,
-        kind: entry_snapshot.kind,
-        parent_id: entry_snapshot.parent_id,
-        worktree: entry_snapshot.worktree,
-        settings: entry_snapshot.settings,
-    })
+    let session_state = session_state_for(sessions, &entry_snapshot.id).await;
+    Ok(WorkspaceInfo::from_entry(entry_snapshot, connected, session_state))
 }
 
 pub(crate) async fn rename_worktree_upstream_core<
@@ -868,27 +1010,39 @@ where
     Ok(())
 }
 
+/// `persist` receives the updated workspace list; callers may write it immediately or
+/// defer the write, since the in-memory map is already current.
 pub(crate) async fn update_workspace_settings_core<
     FApplySettings,
     FSpawn,
     FutSpawn,
+    FPersist,
 >(
     id: String,
     mut settings: WorkspaceSettings,
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     app_settings: &Mutex<AppSettings>,
-    storage_path: &PathBuf,
     apply_settings_update: FApplySettings,
     spawn_session: FSpawn,
+    persist: FPersist,
 ) -> Result<WorkspaceInfo, String>
 where
     FApplySettings: Fn(&mut HashMap<String, WorkspaceEntry>, &str, WorkspaceSettings)
         -> Result<WorkspaceEntry, String>,
     FSpawn: Fn(WorkspaceEntry, Option<String>, Option<String>, Option<PathBuf>) -> FutSpawn,
     FutSpawn: Future<Output = Result<Arc<WorkspaceSession>, String>>,
+    FPersist: FnOnce(&[WorkspaceEntry]) -> Result<(), String>,
 {
     settings.worktree_setup_script = normalize_setup_script(settings.worktree_setup_script);
+    settings.default_access_mode = validate_default_access_mode(settings.default_access_mode)?;
+    settings.ping_interval_secs =
+        validate_keepalive_secs("pingIntervalSecs", settings.ping_interval_secs)?;
+    settings.stall_timeout_secs =
+        validate_keepalive_secs("stallTimeoutSecs", settings.stall_timeout_secs)?;
+    if let Some(level) = settings.log_level.as_deref() {
+        parse_log_level(level)?;
+    }
 
     let (
         previous_entry,
@@ -1026,53 +1180,167 @@ where
         let workspaces = workspaces.lock().await;
         workspaces.values().cloned().collect()
     };
-    write_workspaces(storage_path, &list)?;
-    Ok(WorkspaceInfo {
-        id: entry_snapshot.id,
-        name: entry_snapshot.name,
-        path: entry_snapshot.path,
-        codex_bin: entry_snapshot.codex_bin,
-        connected,
-        kind: entry_snapshot.kind,
-        parent_id: entry_snapshot.parent_id,
-        worktree: entry_snapshot.worktree,
-        settings: entry_snapshot.settings,
-    })
+    persist(&list)?;
+    let session_state = session_state_for(sessions, &entry_snapshot.id).await;
+    Ok(WorkspaceInfo::from_entry(entry_snapshot, connected, session_state))
 }
 
-pub(crate) async fn update_workspace_codex_bin_core(
-    id: String,
-    codex_bin: Option<String>,
+/// Applies `update` to the stored entry, persists the list and returns the updated info.
+async fn update_workspace_entry(
+    id: &str,
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     storage_path: &PathBuf,
+    update: impl FnOnce(&mut WorkspaceEntry),
 ) -> Result<WorkspaceInfo, String> {
     let (entry_snapshot, list) = {
         let mut workspaces = workspaces.lock().await;
-        let entry_snapshot = match workspaces.get_mut(&id) {
-            Some(entry) => {
-                entry.codex_bin = codex_bin.clone();
-                entry.clone()
-            }
-            None => return Err("workspace not found".to_string()),
-        };
+        let entry = workspaces
+            .get_mut(id)
+            .ok_or_else(|| WORKSPACE_NOT_FOUND_ERROR.to_string())?;
+        update(entry);
+        let entry_snapshot = entry.clone();
         let list: Vec<_> = workspaces.values().cloned().collect();
         (entry_snapshot, list)
     };
     write_workspaces(storage_path, &list)?;
 
-    let connected = sessions.lock().await.contains_key(&id);
-    Ok(WorkspaceInfo {
-        id: entry_snapshot.id,
-        name: entry_snapshot.name,
-        path: entry_snapshot.path,
-        codex_bin: entry_snapshot.codex_bin,
-        connected,
-        kind: entry_snapshot.kind,
-        parent_id: entry_snapshot.parent_id,
-        worktree: entry_snapshot.worktree,
-        settings: entry_snapshot.settings,
+    let connected = sessions.lock().await.contains_key(id);
+    let session_state = session_state_for(sessions, id).await;
+    Ok(WorkspaceInfo::from_entry(entry_snapshot, connected, session_state))
+}
+
+pub(crate) async fn update_workspace_codex_bin_core(
+    id: String,
+    codex_bin: Option<String>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    storage_path: &PathBuf,
+) -> Result<WorkspaceInfo, String> {
+    update_workspace_entry(&id, workspaces, sessions, storage_path, |entry| {
+        entry.codex_bin = codex_bin;
+    })
+    .await
+}
+
+/// Changes only the display name; the id, and any session running under it, are untouched.
+pub(crate) async fn rename_workspace_core(
+    id: String,
+    name: String,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    storage_path: &PathBuf,
+) -> Result<WorkspaceInfo, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Workspace name cannot be empty.".to_string());
+    }
+    if name.chars().count() > MAX_WORKSPACE_NAME_CHARS {
+        return Err(format!(
+            "Workspace name must be at most {MAX_WORKSPACE_NAME_CHARS} characters."
+        ));
+    }
+    update_workspace_entry(&id, workspaces, sessions, storage_path, |entry| {
+        entry.name = name;
     })
+    .await
+}
+
+/// Drops control characters other than newlines and tabs; blank text clears the note.
+fn sanitize_workspace_description(text: &str) -> Result<Option<String>, String> {
+    let cleaned: String = text
+        .replace("\r\n", "\n")
+        .chars()
+        .filter(|ch| !ch.is_control() || *ch == '\n' || *ch == '\t')
+        .collect();
+    let cleaned = cleaned.trim();
+    if cleaned.is_empty() {
+        return Ok(None);
+    }
+    if cleaned.chars().count() > MAX_WORKSPACE_DESCRIPTION_CHARS {
+        return Err(format!(
+            "Workspace description must be at most {MAX_WORKSPACE_DESCRIPTION_CHARS} characters."
+        ));
+    }
+    Ok(Some(cleaned.to_string()))
+}
+
+pub(crate) async fn set_workspace_description_core(
+    id: String,
+    text: String,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    storage_path: &PathBuf,
+) -> Result<WorkspaceInfo, String> {
+    let description = sanitize_workspace_description(&text)?;
+    update_workspace_entry(&id, workspaces, sessions, storage_path, |entry| {
+        entry.description = description;
+    })
+    .await
+}
+
+pub(crate) async fn set_monitor_only_core(
+    id: String,
+    value: bool,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    storage_path: &PathBuf,
+) -> Result<WorkspaceInfo, String> {
+    let info = update_workspace_entry(&id, workspaces, sessions, storage_path, |entry| {
+        entry.monitor_only = value;
+    })
+    .await?;
+    if let Some(session) = sessions.lock().await.get(&id) {
+        session.monitor_only.store(value, Ordering::SeqCst);
+    }
+    Ok(info)
+}
+
+pub(crate) async fn set_workspace_pin_order_core<F>(
+    id: String,
+    order: u32,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    storage_path: &PathBuf,
+    apply_pin_order: F,
+) -> Result<(), String>
+where
+    F: Fn(&mut HashMap<String, WorkspaceEntry>, &str, u32) -> Result<WorkspaceEntry, String>,
+{
+    let list = {
+        let mut workspaces = workspaces.lock().await;
+        apply_pin_order(&mut workspaces, &id, order)?;
+        workspaces.values().cloned().collect::<Vec<_>>()
+    };
+    write_workspaces(storage_path, &list)
+}
+
+/// Re-reads `workspaces.json` into memory. Live sessions survive for entries that are
+/// still present; sessions for entries that disappeared from disk are shut down.
+pub(crate) async fn reload_workspaces_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    storage_path: &PathBuf,
+) -> Result<Vec<WorkspaceInfo>, String> {
+    let mut stored = read_workspaces(storage_path)?;
+    let removed: Vec<String> = {
+        let mut workspaces = workspaces.lock().await;
+        for (id, entry) in stored.iter_mut() {
+            if let Some(current) = workspaces.get(id) {
+                entry.last_error = current.last_error.clone();
+            }
+        }
+        let removed = workspaces
+            .keys()
+            .filter(|id| !stored.contains_key(*id))
+            .cloned()
+            .collect();
+        *workspaces = stored;
+        removed
+    };
+    for id in removed {
+        kill_session_by_id(sessions, &id, WORKSPACE_REMOVED_ERROR).await;
+    }
+    Ok(list_workspaces_core(workspaces, sessions).await)
 }
 
 pub(crate) async fn list_workspace_files_core<F>(
@@ -1102,6 +1370,11 @@ where
 
 fn sort_workspaces(workspaces: &mut [WorkspaceInfo]) {
     workspaces.sort_by(|a, b| {
+        let a_pin = a.settings.pin_order.unwrap_or(u32::MAX);
+        let b_pin = b.settings.pin_order.unwrap_or(u32::MAX);
+        if a_pin != b_pin {
+            return a_pin.cmp(&b_pin);
+        }
         let a_order = a.settings.sort_order.unwrap_or(u32::MAX);
         let b_order = b.settings.sort_order.unwrap_or(u32::MAX);
         if a_order != b_order {
@@ -1,25 +1,56 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::json;
 use tauri::{AppHandle, Manager};
 use tokio::sync::Mutex;
 
+use crate::backend::events::{AppServerEvent, EventSink};
 use crate::dictation::DictationState;
+use crate::event_log::{EventLog, EVENT_LOG_FILENAME};
+use crate::event_sink::TauriEventSink;
+use crate::files::cache::FileCache;
+use crate::git::watcher::GitStatusWatcher;
+use crate::git_utils::RepositoryCache;
 use crate::shared::codex_core::CodexLoginCancelState;
-use crate::storage::{read_settings, read_workspaces};
-use crate::types::{AppSettings, WorkspaceEntry};
+use crate::storage::{
+    read_global_settings, read_pins, read_settings, read_workspaces, PINS_FILENAME,
+    THREAD_PREFERENCES_FILENAME,
+};
+use crate::types::{AppSettings, GlobalSettings, ProjectSummary, WorkspaceEntry};
+use crate::workspaces::cancel::ConnectCancellations;
+use crate::workspaces::persist::WorkspaceSaveDebouncer;
+
+const SESSION_EVICTION_INTERVAL: Duration = Duration::from_secs(5 * 60);
 
 pub(crate) struct AppState {
     pub(crate) workspaces: Mutex<HashMap<String, WorkspaceEntry>>,
-    pub(crate) sessions: Mutex<HashMap<String, Arc<crate::codex::WorkspaceSession>>>,
+    pub(crate) sessions: Arc<Mutex<HashMap<String, Arc<crate::codex::WorkspaceSession>>>>,
     pub(crate) terminal_sessions:
         Mutex<HashMap<String, Arc<crate::terminal::TerminalSession>>>,
     pub(crate) remote_backend: Mutex<Option<crate::remote_backend::RemoteBackend>>,
     pub(crate) storage_path: PathBuf,
     pub(crate) settings_path: PathBuf,
     pub(crate) app_settings: Mutex<AppSettings>,
+    pub(crate) global_settings_path: PathBuf,
+    pub(crate) global_settings: Mutex<GlobalSettings>,
+    pub(crate) thread_preferences_path: PathBuf,
+    pub(crate) pins_path: PathBuf,
+    /// Pinned thread ids per workspace, for app-servers without `thread/pin`.
+    pub(crate) pins: Mutex<HashMap<String, HashSet<String>>>,
     pub(crate) dictation: Mutex<DictationState>,
     pub(crate) codex_login_cancels: Mutex<HashMap<String, CodexLoginCancelState>>,
+    pub(crate) file_cache: Mutex<FileCache>,
+    pub(crate) connect_cancellations: ConnectCancellations,
+    /// Project summaries keyed by workspace id, tagged with the HEAD they were computed at.
+    pub(crate) project_summaries: Mutex<HashMap<String, (Option<String>, ProjectSummary)>>,
+    pub(crate) event_log: EventLog,
+    pub(crate) workspace_saves: WorkspaceSaveDebouncer,
+    /// File watchers for workspaces with `autoRefreshGitStatus`, keyed by workspace id.
+    pub(crate) watchers: Mutex<HashMap<String, GitStatusWatcher>>,
+    pub(crate) repositories: Arc<RepositoryCache>,
 }
 
 impl AppState {
@@ -30,18 +61,85 @@ impl AppState {
             .unwrap_or_else(|_| std::env::current_dir().unwrap_or_else(|_| ".".into()));
         let storage_path = data_dir.join("workspaces.json");
         let settings_path = data_dir.join("settings.json");
+        let global_settings_path = data_dir.join("global_settings.json");
         let workspaces = read_workspaces(&storage_path).unwrap_or_default();
         let app_settings = read_settings(&settings_path).unwrap_or_default();
+        let global_settings = read_global_settings(&global_settings_path).unwrap_or_default();
+        let pins_path = data_dir.join(PINS_FILENAME);
+        let pins = read_pins(&pins_path).unwrap_or_default();
+        let event_log = EventLog::new(
+            data_dir.join(EVENT_LOG_FILENAME),
+            app_settings.event_log_enabled,
+        );
         Self {
             workspaces: Mutex::new(workspaces),
-            sessions: Mutex::new(HashMap::new()),
+            sessions: Arc::new(Mutex::new(HashMap::new())),
             terminal_sessions: Mutex::new(HashMap::new()),
             remote_backend: Mutex::new(None),
             storage_path,
             settings_path,
             app_settings: Mutex::new(app_settings),
+            global_settings_path,
+            global_settings: Mutex::new(global_settings),
+            thread_preferences_path: data_dir.join(THREAD_PREFERENCES_FILENAME),
+            pins_path,
+            pins: Mutex::new(pins),
             dictation: Mutex::new(DictationState::default()),
             codex_login_cancels: Mutex::new(HashMap::new()),
+            file_cache: Mutex::new(FileCache::default()),
+            connect_cancellations: ConnectCancellations::default(),
+            project_summaries: Mutex::new(HashMap::new()),
+            event_log,
+            workspace_saves: WorkspaceSaveDebouncer::default(),
+            watchers: Mutex::new(HashMap::new()),
+            repositories: Arc::new(RepositoryCache::default()),
         }
     }
+
+    /// Periodically drops sessions whose app-server process has exited. This only backs up
+    /// the disconnect paths; the task holds a weak reference so it never outlives the state.
+    pub(crate) fn evict_idle_sessions(&self, app: AppHandle) {
+        let sessions = Arc::downgrade(&self.sessions);
+        let event_sink = TauriEventSink::new(app);
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(SESSION_EVICTION_INTERVAL);
+            // The first tick completes immediately; nothing is stale at startup.
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                let Some(sessions) = sessions.upgrade() else {
+                    break;
+                };
+                let snapshot: Vec<_> = sessions
+                    .lock()
+                    .await
+                    .iter()
+                    .map(|(id, session)| (id.clone(), Arc::clone(session)))
+                    .collect();
+                for (workspace_id, session) in snapshot {
+                    if session.is_healthy().await {
+                        continue;
+                    }
+                    {
+                        let mut sessions = sessions.lock().await;
+                        // Only evict if a reconnect has not replaced the session meanwhile.
+                        match sessions.get(&workspace_id) {
+                            Some(current) if Arc::ptr_eq(current, &session) => {
+                                sessions.remove(&workspace_id);
+                                session.mark_removed();
+                            }
+                            _ => continue,
+                        }
+                    }
+                    event_sink.emit_app_server_event(AppServerEvent::new(
+                        workspace_id.clone(),
+                        json!({
+                            "method": "codex/staleSessionEvicted",
+                            "params": { "workspaceId": workspace_id },
+                        }),
+                    ));
+                }
+            }
+        });
+    }
 }
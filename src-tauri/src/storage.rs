@@ -1,7 +1,10 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
-use crate::types::{AppSettings, WorkspaceEntry};
+use crate::types::{AppSettings, GlobalSettings, ThreadPreferences, WorkspaceEntry};
+
+pub(crate) const THREAD_PREFERENCES_FILENAME: &str = "thread_preferences.json";
+pub(crate) const PINS_FILENAME: &str = "pins.json";
 
 pub(crate) fn read_workspaces(path: &PathBuf) -> Result<HashMap<String, WorkspaceEntry>, String> {
     if !path.exists() {
@@ -39,10 +42,80 @@ pub(crate) fn write_settings(path: &PathBuf, settings: &AppSettings) -> Result<(
     std::fs::write(path, data).map_err(|e| e.to_string())
 }
 
+pub(crate) fn read_global_settings(path: &PathBuf) -> Result<GlobalSettings, String> {
+    if !path.exists() {
+        return Ok(GlobalSettings::default());
+    }
+    let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn write_global_settings(path: &PathBuf, settings: &GlobalSettings) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let data = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    // Write beside the target and rename so a crash never leaves a truncated file.
+    let temp_path = path.with_extension("json.tmp");
+    std::fs::write(&temp_path, data).map_err(|e| e.to_string())?;
+    std::fs::rename(&temp_path, path).map_err(|e| e.to_string())
+}
+
+pub(crate) fn read_thread_preferences(
+    path: &PathBuf,
+) -> Result<HashMap<String, ThreadPreferences>, String> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn write_thread_preferences(
+    path: &PathBuf,
+    preferences: &HashMap<String, ThreadPreferences>,
+) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let data = serde_json::to_string_pretty(preferences).map_err(|e| e.to_string())?;
+    let temp_path = path.with_extension("json.tmp");
+    std::fs::write(&temp_path, data).map_err(|e| e.to_string())?;
+    std::fs::rename(&temp_path, path).map_err(|e| e.to_string())
+}
+
+/// Locally pinned thread ids keyed by workspace id.
+pub(crate) fn read_pins(path: &PathBuf) -> Result<HashMap<String, HashSet<String>>, String> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn write_pins(
+    path: &PathBuf,
+    pins: &HashMap<String, HashSet<String>>,
+) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let data = serde_json::to_string_pretty(pins).map_err(|e| e.to_string())?;
+    let temp_path = path.with_extension("json.tmp");
+    std::fs::write(&temp_path, data).map_err(|e| e.to_string())?;
+    std::fs::rename(&temp_path, path).map_err(|e| e.to_string())
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{read_workspaces, write_workspaces};
-    use crate::types::{WorkspaceEntry, WorkspaceKind, WorkspaceSettings};
+    use super::{
+        read_global_settings, read_pins, read_thread_preferences, read_workspaces,
+        write_global_settings, write_pins, write_thread_preferences, write_workspaces,
+    };
+    use crate::types::{
+        GlobalSettings, ThreadPreferences, WorkspaceEntry, WorkspaceKind, WorkspaceSettings,
+    };
+    use std::collections::{HashMap, HashSet};
     use uuid::Uuid;
 
     #[test]
@@ -68,6 +141,9 @@ mod tests {
             parent_id: None,
             worktree: None,
             settings: settings.clone(),
+            last_error: None,
+            monitor_only: false,
+            description: None,
         };
 
         write_workspaces(&path, &[entry]).expect("write workspaces");
@@ -82,4 +158,61 @@ mod tests {
             Some("--profile personal")
         );
     }
+
+    #[test]
+    fn global_settings_round_trip_and_default_when_missing() {
+        let temp_dir =
+            std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let path = temp_dir.join("global_settings.json");
+        assert_eq!(
+            read_global_settings(&path).expect("read missing"),
+            GlobalSettings::default()
+        );
+
+        let settings = GlobalSettings {
+            default_codex_bin: Some("/usr/local/bin/codex".to_string()),
+            theme: "dark".to_string(),
+            locale: Some("en-US".to_string()),
+            telemetry_enabled: true,
+            max_workspaces: Some(12),
+        };
+        write_global_settings(&path, &settings).expect("write global settings");
+        assert_eq!(read_global_settings(&path).expect("read"), settings);
+        assert!(!path.with_extension("json.tmp").exists());
+    }
+
+    #[test]
+    fn thread_preferences_round_trip_by_thread_id() {
+        let temp_dir =
+            std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let path = temp_dir.join("thread_preferences.json");
+        assert!(read_thread_preferences(&path).expect("read missing").is_empty());
+
+        let mut preferences = HashMap::new();
+        preferences.insert(
+            "thread-1".to_string(),
+            ThreadPreferences {
+                model: Some("gpt-5".to_string()),
+                effort: Some("high".to_string()),
+            },
+        );
+        write_thread_preferences(&path, &preferences).expect("write preferences");
+        assert_eq!(read_thread_preferences(&path).expect("read"), preferences);
+    }
+
+    #[test]
+    fn pins_round_trip_by_workspace() {
+        let temp_dir =
+            std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let path = temp_dir.join("pins.json");
+        assert!(read_pins(&path).expect("read missing").is_empty());
+
+        let mut pins = HashMap::new();
+        pins.insert(
+            "w1".to_string(),
+            HashSet::from(["thread-1".to_string(), "thread-2".to_string()]),
+        );
+        write_pins(&path, &pins).expect("write pins");
+        assert_eq!(read_pins(&path).expect("read"), pins);
+    }
 }
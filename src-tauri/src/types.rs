@@ -6,6 +6,35 @@ pub(crate) struct GitFileStatus {
     pub(crate) status: String,
     pub(crate) additions: i64,
     pub(crate) deletions: i64,
+    #[serde(default, rename = "renameFrom")]
+    pub(crate) rename_from: Option<String>,
+}
+
+/// A staged path without diff stats, for cheap index listings.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct GitStagedFile {
+    pub(crate) path: String,
+    pub(crate) status: String,
+    #[serde(default, rename = "renameFrom")]
+    pub(crate) rename_from: Option<String>,
+}
+
+/// A path that differs between two revisions, with its delta status letter.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct GitChangedFile {
+    pub(crate) path: String,
+    pub(crate) status: String,
+}
+
+/// Git status of one workspace path after a file operation; both statuses are `None` when the
+/// path is clean or the workspace is not a git repository.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitPathStatus {
+    pub(crate) path: String,
+    pub(crate) index_status: Option<String>,
+    pub(crate) worktree_status: Option<String>,
+    pub(crate) rename_from: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -24,6 +53,98 @@ pub(crate) struct GitFileDiff {
     pub(crate) old_image_mime: Option<String>,
     #[serde(rename = "newImageMime")]
     pub(crate) new_image_mime: Option<String>,
+    /// Set for working-tree diffs; `None` for commit and branch comparisons.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) mode: Option<GitDiffMode>,
+}
+
+/// Which working changes a diff covers: HEAD to index, index to workdir, or HEAD to workdir.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum GitDiffMode {
+    Staged,
+    Unstaged,
+    #[default]
+    Combined,
+}
+
+/// A window of hunks from one file's patch; pass `next_hunk_cursor` back to continue.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitDiffHunkPage {
+    pub(crate) path: String,
+    pub(crate) hunks: Vec<String>,
+    #[serde(rename = "totalHunks")]
+    pub(crate) total_hunks: usize,
+    #[serde(rename = "nextHunkCursor")]
+    pub(crate) next_hunk_cursor: Option<usize>,
+}
+
+/// One batch of a streamed status; totals are running sums up to and including this batch.
+#[derive(Debug, Serialize, Clone, Default)]
+pub(crate) struct GitStatusChunk {
+    pub(crate) files: Vec<GitFileStatus>,
+    #[serde(rename = "stagedFiles")]
+    pub(crate) staged_files: Vec<GitFileStatus>,
+    #[serde(rename = "unstagedFiles")]
+    pub(crate) unstaged_files: Vec<GitFileStatus>,
+    #[serde(rename = "totalAdditions")]
+    pub(crate) total_additions: i64,
+    #[serde(rename = "totalDeletions")]
+    pub(crate) total_deletions: i64,
+    pub(crate) done: bool,
+    #[serde(rename = "branchName", skip_serializing_if = "Option::is_none")]
+    pub(crate) branch_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitCommitFileStat {
+    pub(crate) path: String,
+    pub(crate) status: String,
+    pub(crate) additions: i64,
+    pub(crate) deletions: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProjectLanguageStat {
+    pub(crate) extension: String,
+    pub(crate) files: usize,
+    pub(crate) lines: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProjectSummary {
+    pub(crate) tracked_files: usize,
+    pub(crate) total_lines: usize,
+    /// Largest extensions by line count, biggest first.
+    pub(crate) languages: Vec<ProjectLanguageStat>,
+    pub(crate) latest_commit_timestamp: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EditorConfigDefaults {
+    pub(crate) end_of_line: Option<String>,
+    pub(crate) indent_style: Option<String>,
+    pub(crate) indent_size: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RepoConventions {
+    pub(crate) sampled_files: usize,
+    pub(crate) lf_files: usize,
+    pub(crate) crlf_files: usize,
+    pub(crate) mixed_files: usize,
+    /// `"lf"` or `"crlf"`, whichever most sampled files use.
+    pub(crate) line_ending: Option<String>,
+    /// `"tabs"` or `"spaces"`, by indented line count.
+    pub(crate) indent_style: Option<String>,
+    pub(crate) indent_width: Option<u32>,
+    pub(crate) core_autocrlf: Option<String>,
+    /// Settings from the `[*]` section of a root `.editorconfig`.
+    pub(crate) editorconfig: Option<EditorConfigDefaults>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -55,7 +176,12 @@ pub(crate) struct GitLogEntry {
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct GitLogResponse {
-    pub(crate) total: usize,
+    /// Commits reachable from HEAD; only counted when `get_git_log` is asked to.
+    #[serde(default)]
+    pub(crate) total: Option<usize>,
+    /// Pass back as `cursor` to fetch the page after `entries`.
+    #[serde(default, rename = "nextCursor")]
+    pub(crate) next_cursor: Option<String>,
     pub(crate) entries: Vec<GitLogEntry>,
     #[serde(default)]
     pub(crate) ahead: usize,
@@ -69,6 +195,100 @@ pub(crate) struct GitLogResponse {
     pub(crate) upstream: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct GitRemoteInfo {
+    pub(crate) name: String,
+    pub(crate) url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct GitStashEntry {
+    /// Position in the stash list; `0` is `stash@{0}`, the most recent.
+    pub(crate) index: usize,
+    pub(crate) message: String,
+    pub(crate) sha: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitSignatureInfo {
+    pub(crate) name: String,
+    pub(crate) email: String,
+    pub(crate) timestamp: i64,
+}
+
+/// An in-progress multi-step git operation, from `git2::RepositoryState`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitOperationState {
+    /// camelCase `RepositoryState` name, e.g. `clean`, `merge`, `rebaseMerge`, `cherryPick`.
+    pub(crate) state: String,
+    pub(crate) in_progress: bool,
+    /// Human-readable progress such as the branch being rebased and the current step.
+    pub(crate) detail: Option<String>,
+    pub(crate) conflicted_paths: Vec<String>,
+}
+
+/// Branch, per-file stats and the combined working-tree patch, for sharing a review
+/// outside the app.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitReviewBundle {
+    pub(crate) branch_name: String,
+    pub(crate) head_sha: Option<String>,
+    pub(crate) files: Vec<GitFileStatus>,
+    pub(crate) total_additions: i64,
+    pub(crate) total_deletions: i64,
+    /// HEAD-to-working-tree patch, including staged and untracked changes.
+    pub(crate) patch: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum GitResetMode {
+    Soft,
+    Mixed,
+    Hard,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitResetResult {
+    pub(crate) sha: String,
+    /// Paths the reset rewrote: in HEAD for soft, in the index for mixed, on disk for hard.
+    pub(crate) affected_files: usize,
+}
+
+/// One reflog entry, newest first; `old_sha` is all zeros when the ref was created.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitReflogEntry {
+    pub(crate) old_sha: String,
+    pub(crate) new_sha: String,
+    pub(crate) committer: GitSignatureInfo,
+    pub(crate) message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitCommitDetails {
+    pub(crate) sha: String,
+    pub(crate) summary: String,
+    pub(crate) message: String,
+    pub(crate) author: GitSignatureInfo,
+    pub(crate) committer: GitSignatureInfo,
+    pub(crate) parents: Vec<String>,
+    #[serde(rename = "filesChanged")]
+    pub(crate) files_changed: usize,
+    pub(crate) insertions: usize,
+    pub(crate) deletions: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub(crate) enum GitCommitError {
+    CommitFailed { message: String },
+    CommitOkPushFailed { sha: String, message: String },
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct GitHubIssue {
     pub(crate) number: u64,
@@ -180,8 +400,119 @@ pub(crate) struct LocalUsageSnapshot {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub(crate) struct BranchInfo {
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PendingRequestInfo {
+    pub(crate) id: u64,
+    pub(crate) method: String,
+    pub(crate) enqueued_at_ms: u64,
+    pub(crate) age_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceActivity {
+    pub(crate) workspace_id: String,
     pub(crate) name: String,
+    pub(crate) connected: bool,
+    pub(crate) active_turns: usize,
+    pub(crate) last_event_at_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceLockStatus {
+    pub(crate) held_elsewhere: bool,
+    pub(crate) pid: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StaleSessionCleanup {
+    /// Pid recorded in the removed lock file, when it could be read.
+    pub(crate) removed_lock_pid: Option<u32>,
+    pub(crate) removed_lock: bool,
+    pub(crate) removed_temp_files: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionInfo {
+    pub(crate) uptime_secs: u64,
+    /// Seconds since the app-server last sent anything; uptime if it never has.
+    pub(crate) idle_secs: u64,
+}
+
+/// The app-server's PID, plus CPU, memory and run state where the platform reports them.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionProcessInfo {
+    pub(crate) pid: u32,
+    pub(crate) cpu_percent: Option<f32>,
+    pub(crate) memory_bytes: Option<u64>,
+    pub(crate) status: Option<String>,
+}
+
+/// Outcome of applying one operation to many ids; failures do not stop the rest.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub(crate) struct BulkOperationResult {
+    pub(crate) succeeded: Vec<String>,
+    pub(crate) failed: Vec<BulkOperationFailure>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct BulkOperationFailure {
+    pub(crate) id: String,
+    pub(crate) error: String,
+}
+
+/// How the Codex binary is looked up for a workspace, to explain "codex not found".
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PathDiagnostics {
+    /// `PATH` given to the codex process: the inherited entries, then `added_entries`.
+    pub(crate) path: Option<String>,
+    pub(crate) added_entries: Vec<String>,
+    pub(crate) codex_bin: String,
+    /// Every file `codex_bin` resolves to, in lookup order; the first one is used.
+    pub(crate) matches: Vec<String>,
+    pub(crate) found: bool,
+}
+
+/// Model and effort last used on a thread; unset fields fall back to the server defaults.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadPreferences {
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    #[serde(default)]
+    pub(crate) effort: Option<String>,
+}
+
+/// A `model/list` entry normalized for the model picker; `raw` keeps the server's fields.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ModelInfo {
+    pub(crate) id: String,
+    pub(crate) model: String,
+    pub(crate) display_name: String,
+    pub(crate) context_window: Option<u64>,
+    pub(crate) supported_efforts: Vec<String>,
+    pub(crate) default_effort: Option<String>,
+    pub(crate) is_default: bool,
+    pub(crate) selectable: bool,
+    pub(crate) raw: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitBranchInfo {
+    pub(crate) name: String,
+    pub(crate) is_remote: bool,
+    pub(crate) is_head: bool,
+    /// Upstream of a local branch, e.g. `origin/main`.
+    pub(crate) upstream: Option<String>,
+    pub(crate) last_commit_sha: Option<String>,
+    /// Commit time in seconds; 0 when the branch tip cannot be read.
     pub(crate) last_commit: i64,
 }
 
@@ -199,6 +530,25 @@ pub(crate) struct WorkspaceEntry {
     pub(crate) worktree: Option<WorktreeInfo>,
     #[serde(default)]
     pub(crate) settings: WorkspaceSettings,
+    // Most recent connection failure; kept in memory only.
+    #[serde(skip)]
+    pub(crate) last_error: Option<String>,
+    #[serde(default, rename = "monitorOnly")]
+    pub(crate) monitor_only: bool,
+    /// Freeform note; absent in workspace files written before it existed.
+    #[serde(default)]
+    pub(crate) description: Option<String>,
+}
+
+/// App-server lifecycle as seen by the monitor.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SessionState {
+    Spawning,
+    /// `initialize` succeeded but the `initialized` notification has not been sent yet.
+    Initializing,
+    Ready,
+    Dead,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -216,6 +566,39 @@ pub(crate) struct WorkspaceInfo {
     pub(crate) worktree: Option<WorktreeInfo>,
     #[serde(default)]
     pub(crate) settings: WorkspaceSettings,
+    #[serde(default, rename = "lastError", skip_serializing_if = "Option::is_none")]
+    pub(crate) last_error: Option<String>,
+    #[serde(default, rename = "monitorOnly")]
+    pub(crate) monitor_only: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) description: Option<String>,
+    /// `None` when no session exists; `connected` stays for older clients.
+    #[serde(default, rename = "sessionState", skip_serializing_if = "Option::is_none")]
+    pub(crate) session_state: Option<SessionState>,
+}
+
+impl WorkspaceInfo {
+    pub(crate) fn from_entry(
+        entry: WorkspaceEntry,
+        connected: bool,
+        session_state: Option<SessionState>,
+    ) -> Self {
+        Self {
+            id: entry.id,
+            name: entry.name,
+            path: entry.path,
+            codex_bin: entry.codex_bin,
+            connected,
+            kind: entry.kind,
+            parent_id: entry.parent_id,
+            worktree: entry.worktree,
+            settings: entry.settings,
+            last_error: entry.last_error,
+            monitor_only: entry.monitor_only,
+            description: entry.description,
+            session_state,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -252,12 +635,15 @@ pub(crate) struct WorkspaceGroup {
     pub(crate) copies_folder: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct WorkspaceSettings {
     #[serde(default, rename = "sidebarCollapsed")]
     pub(crate) sidebar_collapsed: bool,
     #[serde(default, rename = "sortOrder")]
     pub(crate) sort_order: Option<u32>,
+    /// Pinned workspaces sort ahead of the rest, ordered by this value.
+    #[serde(default, rename = "pinOrder")]
+    pub(crate) pin_order: Option<u32>,
     #[serde(default, rename = "groupId")]
     pub(crate) group_id: Option<String>,
     #[serde(default, rename = "gitRoot")]
@@ -272,6 +658,75 @@ pub(crate) struct WorkspaceSettings {
     pub(crate) launch_scripts: Option<Vec<LaunchScriptEntry>>,
     #[serde(default, rename = "worktreeSetupScript")]
     pub(crate) worktree_setup_script: Option<String>,
+    #[serde(default, rename = "gitPushAfterCommit")]
+    pub(crate) git_push_after_commit: bool,
+    #[serde(default = "default_git_push_remote_name", rename = "gitPushRemoteName")]
+    pub(crate) git_push_remote_name: String,
+    #[serde(default = "default_startup_jitter_ms", rename = "startupJitterMs")]
+    pub(crate) startup_jitter_ms: u64,
+    #[serde(default, rename = "gitSignCommits")]
+    pub(crate) git_sign_commits: bool,
+    /// Overrides `user.signingKey`; falls back to the repo's git config when unset.
+    #[serde(default, rename = "gitSigningKey")]
+    pub(crate) git_signing_key: Option<String>,
+    /// One of error/warn/info/debug/trace; the app-wide default applies when unset.
+    #[serde(default, rename = "logLevel")]
+    pub(crate) log_level: Option<String>,
+    /// Opt-in: watches the workspace and emits `codex/gitChanged` when files change.
+    #[serde(default, rename = "autoRefreshGitStatus")]
+    pub(crate) auto_refresh_git_status: bool,
+    /// Used by `send_user_message` when neither the call nor the thread names a model.
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    #[serde(default)]
+    pub(crate) effort: Option<String>,
+    /// `full-access`, `read-only` or `current`; used when `send_user_message` gets no mode.
+    #[serde(default, rename = "defaultAccessMode")]
+    pub(crate) default_access_mode: Option<String>,
+    /// Seconds between keepalive pings to the app-server; 30 when unset, at most 3600.
+    #[serde(default, rename = "pingIntervalSecs")]
+    pub(crate) ping_interval_secs: Option<u64>,
+    /// Seconds a ping may go unanswered before `codex/sessionStalled`; 60 when unset, at
+    /// most 3600.
+    #[serde(default, rename = "stallTimeoutSecs")]
+    pub(crate) stall_timeout_secs: Option<u64>,
+}
+
+impl Default for WorkspaceSettings {
+    fn default() -> Self {
+        Self {
+            sidebar_collapsed: false,
+            sort_order: None,
+            pin_order: None,
+            group_id: None,
+            git_root: None,
+            codex_home: None,
+            codex_args: None,
+            launch_script: None,
+            launch_scripts: None,
+            worktree_setup_script: None,
+            git_push_after_commit: false,
+            git_push_remote_name: default_git_push_remote_name(),
+            startup_jitter_ms: default_startup_jitter_ms(),
+            git_sign_commits: false,
+            git_signing_key: None,
+            log_level: None,
+            auto_refresh_git_status: false,
+            model: None,
+            effort: None,
+            default_access_mode: None,
+            ping_interval_secs: None,
+            stall_timeout_secs: None,
+        }
+    }
+}
+
+fn default_git_push_remote_name() -> String {
+    "origin".to_string()
+}
+
+fn default_startup_jitter_ms() -> u64 {
+    2000
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -303,6 +758,32 @@ pub(crate) struct OpenAppTarget {
     pub(crate) args: Vec<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub(crate) struct GlobalSettings {
+    #[serde(default, rename = "defaultCodexBin")]
+    pub(crate) default_codex_bin: Option<String>,
+    #[serde(default = "default_theme")]
+    pub(crate) theme: String,
+    #[serde(default)]
+    pub(crate) locale: Option<String>,
+    #[serde(default, rename = "telemetryEnabled")]
+    pub(crate) telemetry_enabled: bool,
+    #[serde(default, rename = "maxWorkspaces")]
+    pub(crate) max_workspaces: Option<u32>,
+}
+
+impl Default for GlobalSettings {
+    fn default() -> Self {
+        Self {
+            default_codex_bin: None,
+            theme: default_theme(),
+            locale: None,
+            telemetry_enabled: false,
+            max_workspaces: None,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct AppSettings {
     #[serde(default, rename = "codexBin")]
@@ -497,6 +978,9 @@ pub(crate) struct AppSettings {
     pub(crate) open_app_targets: Vec<OpenAppTarget>,
     #[serde(default = "default_selected_open_app_id", rename = "selectedOpenAppId")]
     pub(crate) selected_open_app_id: String,
+    /// Mirrors every app-server event to a rotating JSONL file for bug reports.
+    #[serde(default, rename = "eventLogEnabled")]
+    pub(crate) event_log_enabled: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -827,6 +1311,7 @@ impl Default for AppSettings {
             workspace_groups: default_workspace_groups(),
             open_app_targets: default_open_app_targets(),
             selected_open_app_id: default_selected_open_app_id(),
+            event_log_enabled: false,
         }
     }
 }
@@ -926,6 +1411,7 @@ mod tests {
         assert!(!settings.composer_code_block_copy_use_modifier);
         assert!(settings.workspace_groups.is_empty());
         assert_eq!(settings.selected_open_app_id, "vscode");
+        assert!(!settings.event_log_enabled);
         assert_eq!(settings.open_app_targets.len(), 6);
         assert_eq!(settings.open_app_targets[0].id, "vscode");
     }
@@ -977,5 +1463,21 @@ mod tests {
         assert!(settings.sort_order.is_none());
         assert!(settings.group_id.is_none());
         assert!(settings.git_root.is_none());
+        assert!(!settings.git_push_after_commit);
+        assert_eq!(settings.git_push_remote_name, "origin");
+        assert_eq!(settings.startup_jitter_ms, 2000);
+        assert!(!settings.git_sign_commits);
+        assert!(settings.git_signing_key.is_none());
+        assert!(settings.log_level.is_none());
+        assert!(!settings.auto_refresh_git_status);
+    }
+
+    #[test]
+    fn workspace_settings_push_remote_defaults_from_empty_json() {
+        let settings: WorkspaceSettings =
+            serde_json::from_str("{}").expect("workspace settings deserialize");
+        assert!(!settings.git_push_after_commit);
+        assert_eq!(settings.git_push_remote_name, "origin");
+        assert!(!settings.auto_refresh_git_status);
     }
 }
@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+use std::future::{poll_fn, Future};
+use std::pin::{pin, Pin};
+use std::task::Poll;
+
+use tokio::sync::{oneshot, Mutex};
+
+pub(crate) const CONNECT_CANCELLED_ERROR: &str = "connect cancelled";
+
+/// In-flight add/connect operations that the frontend may abort by id.
+#[derive(Default)]
+pub(crate) struct ConnectCancellations {
+    senders: Mutex<HashMap<String, oneshot::Sender<()>>>,
+}
+
+impl ConnectCancellations {
+    /// Runs `operation`, dropping it early if `cancel` is called with the same id. Dropping
+    /// the spawn future is what tears down a half-initialized app-server.
+    pub(crate) async fn run<T, Fut>(
+        &self,
+        operation_id: Option<String>,
+        operation: Fut,
+    ) -> Result<T, String>
+    where
+        Fut: Future<Output = Result<T, String>>,
+    {
+        let Some(operation_id) = operation_id else {
+            return operation.await;
+        };
+        let (tx, mut rx) = oneshot::channel();
+        self.senders.lock().await.insert(operation_id.clone(), tx);
+
+        let mut operation = pin!(operation);
+        let result = poll_fn(|cx| {
+            if Pin::new(&mut rx).poll(cx).is_ready() {
+                return Poll::Ready(Err(CONNECT_CANCELLED_ERROR.to_string()));
+            }
+            operation.as_mut().poll(cx)
+        })
+        .await;
+
+        self.senders.lock().await.remove(&operation_id);
+        result
+    }
+
+    pub(crate) async fn cancel(&self, operation_id: &str) -> Result<(), String> {
+        match self.senders.lock().await.remove(operation_id) {
+            Some(sender) => {
+                let _ = sender.send(());
+                Ok(())
+            }
+            None => Err("connect operation not found".to_string()),
+        }
+    }
+}
@@ -15,27 +15,53 @@ use super::git::{
     git_remote_exists, is_missing_worktree_error, run_git_command, run_git_command_bytes,
     run_git_command_owned, run_git_diff, unique_branch_name,
 };
-use super::settings::apply_workspace_settings_update;
+use super::settings::{apply_workspace_pin_order, apply_workspace_settings_update};
 use super::worktree::{
     build_clone_destination_path, null_device_path, sanitize_worktree_name, unique_worktree_path,
     unique_worktree_path_for_rename,
 };
 
 use crate::backend::app_server::WorkspaceSession;
+use crate::backend::workspace_lock::{
+    check_workspace_lock as check_workspace_lock_inner,
+    clear_stale_session_state as clear_stale_session_state_inner,
+};
 use crate::codex::spawn_workspace_session;
 use crate::codex::args::resolve_workspace_codex_args;
 use crate::codex::home::resolve_workspace_codex_home;
+use crate::event_sink::TauriEventSink;
+use crate::git::prune_repository_cache;
+use crate::git::watcher::{prune_git_status_watchers, sync_git_status_watcher};
 use crate::git_utils::resolve_git_root;
+use crate::logging::{parse_log_level, WorkspaceLogLevels};
 use crate::remote_backend;
+use crate::shared::codex_core;
 use crate::shared::process_core::tokio_command;
 use crate::shared::workspaces_core;
 use crate::state::AppState;
 use crate::storage::write_workspaces;
 use crate::types::{
-    WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings, WorktreeSetupStatus,
+    StaleSessionCleanup, WorkspaceActivity, WorkspaceEntry, WorkspaceInfo, WorkspaceKind,
+    WorkspaceLockStatus, WorkspaceSettings, WorktreeSetupStatus,
 };
 use crate::utils::{git_env_path, resolve_git_binary};
 
+pub(crate) fn app_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map_err(|err| format!("Failed to resolve app data dir: {err}"))
+}
+
+async fn workspace_path(state: &AppState, id: &str) -> Result<String, String> {
+    state
+        .workspaces
+        .lock()
+        .await
+        .get(id)
+        .map(|entry| entry.path.clone())
+        .ok_or_else(|| "workspace not found".to_string())
+}
+
 fn spawn_with_app(
     app: &AppHandle,
     entry: WorkspaceEntry,
@@ -88,6 +114,21 @@ pub(crate) async fn list_workspaces(
 }
 
 
+#[tauri::command]
+pub(crate) async fn get_global_status(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<WorkspaceActivity>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "get_global_status", json!({})).await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    Ok(workspaces_core::get_global_status_core(&state.workspaces, &state.sessions).await)
+}
+
+
 #[tauri::command]
 pub(crate) async fn is_workspace_path_dir(
     path: String,
@@ -112,6 +153,7 @@ pub(crate) async fn is_workspace_path_dir(
 pub(crate) async fn add_workspace(
     path: String,
     codex_bin: Option<String>,
+    operation_id: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<WorkspaceInfo, String> {
@@ -122,14 +164,18 @@ pub(crate) async fn add_workspace(
             &*state,
             app,
             "add_workspace",
-            json!({ "path": path, "codex_bin": codex_bin }),
+            json!({ "path": path, "codex_bin": codex_bin, "operationId": operation_id }),
         )
         .await?;
         return serde_json::from_value(response).map_err(|err| err.to_string());
     }
 
-    workspaces_core::add_workspace_core(
-        path,
+    let codex_bin = match codex_bin {
+        Some(codex_bin) => Some(codex_bin),
+        None => state.global_settings.lock().await.default_codex_bin.clone(),
+    };
+    let add = workspaces_core::add_workspace_core(
+        path.clone(),
         codex_bin,
         &state.workspaces,
         &state.sessions,
@@ -138,8 +184,9 @@ pub(crate) async fn add_workspace(
         |entry, default_bin, codex_args, codex_home| {
             spawn_with_app(&app, entry, default_bin, codex_args, codex_home)
         },
-    )
-    .await
+    );
+    let workspace = state.connect_cancellations.run(operation_id, add).await?;
+    Ok(workspace)
 }
 
 
@@ -218,6 +265,9 @@ pub(crate) async fn add_clone(
             group_id: inherited_group_id,
             ..WorkspaceSettings::default()
         },
+        last_error: None,
+        monitor_only: false,
+        description: None,
     };
 
     let (default_bin, codex_args) = {
@@ -260,23 +310,14 @@ pub(crate) async fn add_clone(
         return Err(error);
     }
 
+    let session_state = session.state().await;
     state
         .sessions
         .lock()
         .await
         .insert(entry.id.clone(), session);
 
-    Ok(WorkspaceInfo {
-        id: entry.id,
-        name: entry.name,
-        path: entry.path,
-        codex_bin: entry.codex_bin,
-        connected: true,
-        kind: entry.kind,
-        parent_id: entry.parent_id,
-        worktree: entry.worktree,
-        settings: entry.settings,
-    })
+    Ok(WorkspaceInfo::from_entry(entry, true, Some(session_state)))
 }
 
 
@@ -410,7 +451,10 @@ pub(crate) async fn remove_workspace(
         true,
         true,
     )
-    .await
+    .await?;
+    prune_git_status_watchers(&state).await;
+    prune_repository_cache(&state).await;
+    codex_core::prune_pins_core(&state.workspaces, &state.pins, &state.pins_path).await
 }
 
 
@@ -441,7 +485,10 @@ pub(crate) async fn remove_worktree(
                 .map_err(|err| format!("Failed to remove worktree folder: {err}"))
         },
     )
-    .await
+    .await?;
+    prune_git_status_watchers(&state).await;
+    prune_repository_cache(&state).await;
+    codex_core::prune_pins_core(&state.workspaces, &state.pins, &state.pins_path).await
 }
 
 
@@ -692,6 +739,7 @@ pub(crate) async fn update_workspace_settings(
     id: String,
     settings: WorkspaceSettings,
     state: State<'_, AppState>,
+    log_levels: State<'_, WorkspaceLogLevels>,
     app: AppHandle,
 ) -> Result<WorkspaceInfo, String> {
     if remote_backend::is_remote_mode(&*state).await {
@@ -705,24 +753,58 @@ pub(crate) async fn update_workspace_settings(
         return serde_json::from_value(response).map_err(|err| err.to_string());
     }
 
-    workspaces_core::update_workspace_settings_core(
-        id,
+    let workspace = workspaces_core::update_workspace_settings_core(
+        id.clone(),
         settings,
         &state.workspaces,
         &state.sessions,
         &state.app_settings,
-        &state.storage_path,
         |workspaces, workspace_id, next_settings| {
             apply_workspace_settings_update(workspaces, workspace_id, next_settings)
         },
         |entry, default_bin, codex_args, codex_home| {
             spawn_with_app(&app, entry, default_bin, codex_args, codex_home)
         },
+        |_| {
+            state.workspace_saves.schedule(&app);
+            Ok(())
+        },
     )
-    .await
+    .await?;
+    log_levels.apply(&id, workspace.settings.log_level.as_deref())?;
+
+    let entry = state.workspaces.lock().await.get(&id).cloned();
+    if let Some(entry) = entry {
+        let session = state.sessions.lock().await.get(&id).cloned();
+        sync_git_status_watcher(&app, &entry, session.as_ref()).await;
+    }
+    Ok(workspace)
 }
 
 
+/// Changes a workspace's tracing verbosity without restarting; not persisted to settings.
+#[tauri::command]
+pub(crate) async fn reload_log_level(
+    workspace_id: String,
+    level: String,
+    state: State<'_, AppState>,
+    log_levels: State<'_, WorkspaceLogLevels>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "reload_log_level",
+            json!({ "workspaceId": workspace_id, "level": level }),
+        )
+        .await?;
+        return Ok(());
+    }
+    log_levels.set(&workspace_id, parse_log_level(&level)?);
+    Ok(())
+}
+
 #[tauri::command]
 pub(crate) async fn update_workspace_codex_bin(
     id: String,
@@ -752,29 +834,261 @@ pub(crate) async fn update_workspace_codex_bin(
     .await
 }
 
+#[tauri::command]
+pub(crate) async fn rename_workspace(
+    id: String,
+    name: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceInfo, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "rename_workspace",
+            json!({ "id": id, "name": name }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    workspaces_core::rename_workspace_core(
+        id,
+        name,
+        &state.workspaces,
+        &state.sessions,
+        &state.storage_path,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn set_workspace_description(
+    id: String,
+    text: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceInfo, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "set_workspace_description",
+            json!({ "id": id, "text": text }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    workspaces_core::set_workspace_description_core(
+        id,
+        text,
+        &state.workspaces,
+        &state.sessions,
+        &state.storage_path,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn set_monitor_only(
+    id: String,
+    value: bool,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceInfo, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "set_monitor_only",
+            json!({ "id": id, "value": value }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    workspaces_core::set_monitor_only_core(
+        id,
+        value,
+        &state.workspaces,
+        &state.sessions,
+        &state.storage_path,
+    )
+    .await
+}
+
+
+#[tauri::command]
+pub(crate) async fn reload_workspaces(
+    state: State<'_, AppState>,
+    log_levels: State<'_, WorkspaceLogLevels>,
+    app: AppHandle,
+) -> Result<Vec<WorkspaceInfo>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(&*state, app, "reload_workspaces", json!({}))
+            .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let workspaces = workspaces_core::reload_workspaces_core(
+        &state.workspaces,
+        &state.sessions,
+        &state.storage_path,
+    )
+    .await?;
+    log_levels.seed(state.workspaces.lock().await.values());
+    Ok(workspaces)
+}
+
+#[tauri::command]
+pub(crate) async fn set_workspace_pin_order(
+    id: String,
+    order: u32,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "set_workspace_pin_order",
+            json!({ "id": id, "order": order }),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    workspaces_core::set_workspace_pin_order_core(
+        id,
+        order,
+        &state.workspaces,
+        &state.storage_path,
+        apply_workspace_pin_order,
+    )
+    .await
+}
+
 
 #[tauri::command]
 pub(crate) async fn connect_workspace(
     id: String,
+    restore: Option<bool>,
+    operation_id: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<(), String> {
     if remote_backend::is_remote_mode(&*state).await {
-        remote_backend::call_remote(&*state, app, "connect_workspace", json!({ "id": id }))
-            .await?;
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "connect_workspace",
+            json!({ "id": id, "restore": restore, "operationId": operation_id }),
+        )
+        .await?;
         return Ok(());
     }
 
-    workspaces_core::connect_workspace_core(
+    let connect = workspaces_core::connect_workspace_core(
         id,
         &state.workspaces,
         &state.sessions,
         &state.app_settings,
+        restore.unwrap_or(false),
         |entry, default_bin, codex_args, codex_home| {
             spawn_with_app(&app, entry, default_bin, codex_args, codex_home)
         },
-    )
-    .await
+    );
+    state.connect_cancellations.run(operation_id, connect).await?;
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) async fn reconnect_workspace(
+    id: String,
+    max_attempts: Option<u32>,
+    operation_id: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "reconnect_workspace",
+            json!({ "id": id, "maxAttempts": max_attempts, "operationId": operation_id }),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let event_sink = TauriEventSink::new(app.clone());
+    let reconnect = workspaces_core::reconnect_workspace_core(
+        id,
+        max_attempts,
+        &state.workspaces,
+        &state.sessions,
+        &state.app_settings,
+        &event_sink,
+        |entry, default_bin, codex_args, codex_home| {
+            spawn_with_app(&app, entry, default_bin, codex_args, codex_home)
+        },
+    );
+    state.connect_cancellations.run(operation_id, reconnect).await?;
+    Ok(())
+}
+
+/// Reports whether another monitor process holds the session lock for this workspace.
+#[tauri::command]
+pub(crate) async fn check_workspace_lock(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceLockStatus, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return Err("Workspace locks are only tracked for local sessions.".to_string());
+    }
+    let path = workspace_path(&state, &workspace_id).await?;
+    Ok(check_workspace_lock_inner(&app_data_dir(&app)?, &path))
+}
+
+/// Recovery path after a crash: drops the workspace's lock if its owner is dead and removes
+/// temp files left by interrupted writes.
+#[tauri::command]
+pub(crate) async fn clear_stale_session_state(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<StaleSessionCleanup, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return Err("Workspace locks are only tracked for local sessions.".to_string());
+    }
+    let path = workspace_path(&state, &workspace_id).await?;
+    let data_dir = app_data_dir(&app)?;
+    tokio::task::spawn_blocking(move || clear_stale_session_state_inner(&data_dir, &path))
+        .await
+        .map_err(|err| err.to_string())?
+}
+
+/// Aborts an in-flight `add_workspace`, `connect_workspace` or `reconnect_workspace` started
+/// with `operation_id`.
+#[tauri::command]
+pub(crate) async fn cancel_connect(
+    operation_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "cancel_connect",
+            json!({ "operationId": operation_id }),
+        )
+        .await?;
+        return Ok(());
+    }
+    state.connect_cancellations.cancel(&operation_id).await
 }
 
 
@@ -1,7 +1,9 @@
+pub(crate) mod cancel;
 mod commands;
 mod files;
 mod git;
 mod macos;
+pub(crate) mod persist;
 mod settings;
 mod worktree;
 
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+use crate::state::AppState;
+use crate::storage::write_workspaces;
+use crate::types::WorkspaceEntry;
+
+const WORKSPACE_SAVE_DELAY: Duration = Duration::from_millis(500);
+
+/// Coalesces settings writes to `workspaces.json`. The in-memory map is always current;
+/// only the disk write is deferred, and it snapshots whatever the map holds at flush time.
+#[derive(Default)]
+pub(crate) struct WorkspaceSaveDebouncer {
+    dirty: AtomicBool,
+}
+
+impl WorkspaceSaveDebouncer {
+    /// Returns true when this call started a new save window.
+    fn mark_dirty(&self) -> bool {
+        !self.dirty.swap(true, Ordering::SeqCst)
+    }
+
+    pub(crate) fn schedule(&self, app: &AppHandle) {
+        if !self.mark_dirty() {
+            return;
+        }
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(WORKSPACE_SAVE_DELAY).await;
+            let state = app.state::<AppState>();
+            if let Err(error) = state
+                .workspace_saves
+                .flush(&state.workspaces, &state.storage_path)
+                .await
+            {
+                eprintln!("Failed to save workspaces: {error}");
+            }
+        });
+    }
+
+    pub(crate) async fn flush(
+        &self,
+        workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+        storage_path: &PathBuf,
+    ) -> Result<(), String> {
+        if !self.dirty.swap(false, Ordering::SeqCst) {
+            return Ok(());
+        }
+        let list: Vec<_> = workspaces.lock().await.values().cloned().collect();
+        write_workspaces(storage_path, &list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use tokio::sync::Mutex;
+    use uuid::Uuid;
+
+    use super::WorkspaceSaveDebouncer;
+    use crate::storage::read_workspaces;
+    use crate::types::{WorkspaceEntry, WorkspaceKind, WorkspaceSettings};
+
+    #[test]
+    fn flush_writes_latest_state_once() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .expect("runtime");
+        let temp_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&temp_dir).expect("create temp dir");
+        let path = temp_dir.join("workspaces.json");
+        let entry = WorkspaceEntry {
+            id: "ws-1".to_string(),
+            name: "Workspace".to_string(),
+            path: "/tmp".to_string(),
+            codex_bin: None,
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+            last_error: None,
+            monitor_only: false,
+            description: None,
+        };
+        let workspaces = Mutex::new(HashMap::from([(entry.id.clone(), entry)]));
+        let debouncer = WorkspaceSaveDebouncer::default();
+
+        runtime.block_on(async {
+            assert!(debouncer.mark_dirty());
+            workspaces
+                .lock()
+                .await
+                .get_mut("ws-1")
+                .expect("entry")
+                .settings
+                .sidebar_collapsed = true;
+            assert!(!debouncer.mark_dirty());
+
+            debouncer.flush(&workspaces, &path).await.expect("flush");
+            let stored = read_workspaces(&path).expect("read workspaces");
+            assert!(stored["ws-1"].settings.sidebar_collapsed);
+
+            std::fs::remove_file(&path).expect("remove");
+            debouncer.flush(&workspaces, &path).await.expect("second flush");
+            assert!(!path.exists());
+        });
+    }
+}
@@ -7,10 +7,13 @@ use crate::types::WorkspaceInfo;
 #[cfg(test)]
 pub(crate) fn sort_workspaces(list: &mut Vec<WorkspaceInfo>) {
     list.sort_by(|a, b| {
+        let a_pin = a.settings.pin_order.unwrap_or(u32::MAX);
+        let b_pin = b.settings.pin_order.unwrap_or(u32::MAX);
         let a_order = a.settings.sort_order.unwrap_or(u32::MAX);
         let b_order = b.settings.sort_order.unwrap_or(u32::MAX);
-        a_order
-            .cmp(&b_order)
+        a_pin
+            .cmp(&b_pin)
+            .then_with(|| a_order.cmp(&b_order))
             .then_with(|| a.name.cmp(&b.name))
             .then_with(|| a.id.cmp(&b.id))
     });
@@ -29,3 +32,17 @@ pub(crate) fn apply_workspace_settings_update(
         None => Err("workspace not found".to_string()),
     }
 }
+
+pub(crate) fn apply_workspace_pin_order(
+    workspaces: &mut HashMap<String, WorkspaceEntry>,
+    id: &str,
+    order: u32,
+) -> Result<WorkspaceEntry, String> {
+    match workspaces.get_mut(id) {
+        Some(entry) => {
+            entry.settings.pin_order = Some(order);
+            Ok(entry.clone())
+        }
+        None => Err("workspace not found".to_string()),
+    }
+}
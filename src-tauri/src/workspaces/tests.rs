@@ -1,12 +1,27 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use super::settings::{apply_workspace_settings_update, sort_workspaces};
+use super::cancel::{ConnectCancellations, CONNECT_CANCELLED_ERROR};
+use super::settings::{
+    apply_workspace_pin_order, apply_workspace_settings_update, sort_workspaces,
+};
 use super::worktree::{
     build_clone_destination_path, sanitize_clone_dir_name, sanitize_worktree_name,
 };
+use crate::backend::app_server::WorkspaceSession;
+use crate::backend::events::{AppServerEvent, EventSink, TerminalExit, TerminalOutput};
+use crate::shared::workspaces_core::{
+    connect_workspace_core, list_workspaces_core, reconnect_delay, reconnect_workspace_core,
+    reload_workspaces_core,
+    rename_workspace_core, set_monitor_only_core, set_workspace_description_core, startup_jitter,
+};
 use crate::storage::{read_workspaces, write_workspaces};
-use crate::types::{WorktreeInfo, WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings};
+use crate::types::{
+    AppSettings, WorktreeInfo, WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings,
+};
+use tokio::sync::Mutex;
 use uuid::Uuid;
 
 fn workspace(name: &str, sort_order: Option<u32>) -> WorkspaceInfo {
@@ -39,19 +54,39 @@ fn workspace_with_id_and_kind(
         parent_id,
         worktree,
         settings: WorkspaceSettings {
-            sidebar_collapsed: false,
             sort_order,
-            group_id: None,
-            git_root: None,
-            codex_home: None,
-            codex_args: None,
-            launch_script: None,
-            launch_scripts: None,
-            worktree_setup_script: None,
+            ..WorkspaceSettings::default()
         },
+        last_error: None,
+        monitor_only: false,
+        description: None,
+        session_state: None,
+    }
+}
+
+fn workspace_entry(id: &str, name: &str) -> WorkspaceEntry {
+    WorkspaceEntry {
+        id: id.to_string(),
+        name: name.to_string(),
+        path: "/tmp".to_string(),
+        codex_bin: None,
+        kind: WorkspaceKind::Main,
+        parent_id: None,
+        worktree: None,
+        settings: WorkspaceSettings::default(),
+        last_error: None,
+        monitor_only: false,
+        description: None,
     }
 }
 
+fn test_runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .expect("build runtime")
+}
+
 #[test]
 fn sanitize_worktree_name_rewrites_specials() {
     assert_eq!(sanitize_worktree_name("feature/new-thing"), "feature-new-thing");
@@ -177,19 +212,35 @@ fn sort_workspaces_does_not_bias_kind() {
     ));
 }
 
+#[test]
+fn sort_workspaces_places_pinned_first_by_pin_order() {
+    let mut first_pin = workspace("zeta", Some(5));
+    first_pin.settings.pin_order = Some(0);
+    let mut second_pin = workspace("alpha", None);
+    second_pin.settings.pin_order = Some(1);
+    let mut items = vec![workspace("beta", Some(0)), second_pin, first_pin];
+
+    sort_workspaces(&mut items);
+
+    let names: Vec<_> = items.into_iter().map(|item| item.name).collect();
+    assert_eq!(names, vec!["zeta", "alpha", "beta"]);
+}
+
+#[test]
+fn apply_workspace_pin_order_updates_entry() {
+    let id = "workspace-1".to_string();
+    let entry = workspace_entry(&id, "Workspace");
+    let mut workspaces = HashMap::from([(id.clone(), entry)]);
+
+    let updated = apply_workspace_pin_order(&mut workspaces, &id, 2).expect("pin");
+    assert_eq!(updated.settings.pin_order, Some(2));
+    assert!(apply_workspace_pin_order(&mut workspaces, "missing", 0).is_err());
+}
+
 #[test]
 fn update_workspace_settings_persists_sort_and_group() {
     let id = "workspace-1".to_string();
-    let entry = WorkspaceEntry {
-        id: id.clone(),
-        name: "Workspace".to_string(),
-        path: "/tmp".to_string(),
-        codex_bin: None,
-        kind: WorkspaceKind::Main,
-        parent_id: None,
-        worktree: None,
-        settings: WorkspaceSettings::default(),
-    };
+    let entry = workspace_entry(&id, "Workspace");
     let mut workspaces = HashMap::from([(id.clone(), entry)]);
 
     let mut settings = WorkspaceSettings::default();
@@ -230,3 +281,295 @@ fn update_workspace_settings_persists_sort_and_group() {
         Some("pnpm install"),
     );
 }
+
+#[test]
+fn startup_jitter_stays_within_bounds() {
+    assert_eq!(startup_jitter(0), Duration::ZERO);
+    for _ in 0..50 {
+        assert!(startup_jitter(25) < Duration::from_millis(25));
+    }
+}
+
+#[test]
+fn concurrent_startup_jitter_completes_within_budget() {
+    let runtime = test_runtime();
+    let workspaces = 8u64;
+    let max_jitter_ms = 50u64;
+    let started = Instant::now();
+    runtime.block_on(async {
+        let handles: Vec<_> = (0..workspaces)
+            .map(|_| tokio::spawn(tokio::time::sleep(startup_jitter(max_jitter_ms))))
+            .collect();
+        for handle in handles {
+            handle.await.expect("jitter task");
+        }
+    });
+    let budget = Duration::from_millis(workspaces * max_jitter_ms) + Duration::from_secs(2);
+    assert!(started.elapsed() < budget);
+}
+
+#[test]
+fn connect_cancellation_aborts_pending_operation() {
+    let runtime = test_runtime();
+    let cancellations = Arc::new(ConnectCancellations::default());
+    runtime.block_on(async {
+        let running = Arc::clone(&cancellations);
+        let handle = tokio::spawn(async move {
+            running
+                .run(Some("op-1".to_string()), async {
+                    tokio::time::sleep(Duration::from_secs(30)).await;
+                    Ok::<_, String>(())
+                })
+                .await
+        });
+        // Let the operation register before cancelling it.
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        cancellations.cancel("op-1").await.expect("cancel");
+
+        let result = handle.await.expect("join");
+        assert_eq!(result, Err(CONNECT_CANCELLED_ERROR.to_string()));
+        assert!(cancellations.cancel("op-1").await.is_err());
+
+        let untracked = cancellations.run(None, async { Ok::<_, String>(7) }).await;
+        assert_eq!(untracked, Ok(7));
+    });
+}
+
+#[test]
+fn connect_failure_records_last_error_without_persisting() {
+    let runtime = test_runtime();
+    let id = "workspace-1".to_string();
+    let entry = workspace_entry(&id, "Workspace");
+    let workspaces = Mutex::new(HashMap::from([(id.clone(), entry)]));
+    let sessions: Mutex<HashMap<String, Arc<WorkspaceSession>>> = Mutex::new(HashMap::new());
+    let app_settings = Mutex::new(AppSettings::default());
+
+    runtime.block_on(async {
+        let result = connect_workspace_core(
+            id.clone(),
+            &workspaces,
+            &sessions,
+            &app_settings,
+            false,
+            |_, _, _, _| async { Err::<Arc<WorkspaceSession>, String>("codex not found".to_string()) },
+        )
+        .await;
+        assert!(result.is_err());
+
+        let listed = list_workspaces_core(&workspaces, &sessions).await;
+        assert_eq!(listed[0].last_error.as_deref(), Some("codex not found"));
+
+        let stored = serde_json::to_value(workspaces.lock().await.get(&id).cloned())
+            .expect("serialize entry");
+        assert!(stored.get("last_error").is_none());
+        assert!(stored.get("lastError").is_none());
+    });
+}
+
+#[test]
+fn reload_workspaces_reconciles_with_disk() {
+    let runtime = test_runtime();
+    let mut kept = workspace_entry("kept", "Kept");
+    kept.last_error = Some("spawn failed".to_string());
+    let workspaces = Mutex::new(HashMap::from([
+        ("kept".to_string(), kept),
+        ("removed".to_string(), workspace_entry("removed", "Removed")),
+    ]));
+    let sessions: Mutex<HashMap<String, Arc<WorkspaceSession>>> = Mutex::new(HashMap::new());
+
+    let temp_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&temp_dir).expect("create temp dir");
+    let path = temp_dir.join("workspaces.json");
+    let on_disk = [workspace_entry("kept", "Renamed"), workspace_entry("added", "Added")];
+    write_workspaces(&path, &on_disk).expect("write workspaces");
+
+    let listed = runtime
+        .block_on(reload_workspaces_core(&workspaces, &sessions, &path))
+        .expect("reload");
+
+    let names: Vec<_> = listed.iter().map(|item| item.name.as_str()).collect();
+    assert_eq!(names, vec!["Added", "Renamed"]);
+    let kept = listed.iter().find(|item| item.id == "kept").expect("kept");
+    assert_eq!(kept.last_error.as_deref(), Some("spawn failed"));
+    assert!(!runtime.block_on(workspaces.lock()).contains_key("removed"));
+}
+
+#[test]
+fn rename_workspace_updates_name_only() {
+    let runtime = test_runtime();
+    let mut entry = workspace_entry("w1", "Old");
+    entry.codex_bin = Some("codex".to_string());
+    entry.settings.sort_order = Some(3);
+    let workspaces = Mutex::new(HashMap::from([("w1".to_string(), entry)]));
+    let sessions: Mutex<HashMap<String, Arc<WorkspaceSession>>> = Mutex::new(HashMap::new());
+    let temp_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+    let path = temp_dir.join("workspaces.json");
+
+    let rename = |id: &str, name: &str| {
+        runtime.block_on(rename_workspace_core(
+            id.to_string(),
+            name.to_string(),
+            &workspaces,
+            &sessions,
+            &path,
+        ))
+    };
+    assert!(rename("w1", "   ").is_err());
+    assert!(rename("w1", &"x".repeat(129)).is_err());
+    assert!(rename("missing", "Name").expect_err("missing").contains("missing"));
+
+    let info = rename("w1", "  New name ").expect("rename");
+    assert_eq!(info.id, "w1");
+    assert_eq!(info.name, "New name");
+    assert_eq!(info.codex_bin.as_deref(), Some("codex"));
+    assert_eq!(info.settings.sort_order, Some(3));
+    assert!(!info.connected);
+    let stored = read_workspaces(&path).expect("read workspaces");
+    assert_eq!(stored.get("w1").expect("stored").name, "New name");
+}
+
+#[test]
+fn set_monitor_only_persists_flag() {
+    let runtime = test_runtime();
+    let entry = workspace_entry("w1", "Prod");
+    let workspaces = Mutex::new(HashMap::from([("w1".to_string(), entry)]));
+    let sessions: Mutex<HashMap<String, Arc<WorkspaceSession>>> = Mutex::new(HashMap::new());
+    let temp_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+    let path = temp_dir.join("workspaces.json");
+
+    let set = |id: &str, value: bool| {
+        runtime.block_on(set_monitor_only_core(
+            id.to_string(),
+            value,
+            &workspaces,
+            &sessions,
+            &path,
+        ))
+    };
+    assert!(set("missing", true).is_err());
+
+    let info = set("w1", true).expect("enable monitor-only");
+    assert!(info.monitor_only);
+    assert_eq!(info.name, "Prod");
+    let stored = read_workspaces(&path).expect("read workspaces");
+    assert!(stored.get("w1").expect("stored").monitor_only);
+
+    let info = set("w1", false).expect("disable monitor-only");
+    assert!(!info.monitor_only);
+    let stored = read_workspaces(&path).expect("read workspaces");
+    assert!(!stored.get("w1").expect("stored").monitor_only);
+}
+
+#[test]
+fn reconnect_delay_doubles_up_to_cap() {
+    let delays: Vec<u64> = (1..=7)
+        .map(|attempt| reconnect_delay(attempt).as_millis() as u64)
+        .collect();
+    assert_eq!(delays, vec![1_000, 2_000, 4_000, 8_000, 16_000, 30_000, 30_000]);
+    assert_eq!(reconnect_delay(u32::MAX), Duration::from_secs(30));
+}
+
+#[derive(Clone, Default)]
+struct RecordingSink {
+    methods: Arc<std::sync::Mutex<Vec<String>>>,
+}
+
+impl EventSink for RecordingSink {
+    fn emit_app_server_event(&self, event: AppServerEvent) {
+        let method = event.message["method"].as_str().unwrap_or_default().to_string();
+        self.methods.lock().expect("events lock").push(method);
+    }
+
+    fn emit_terminal_output(&self, _event: TerminalOutput) {}
+
+    fn emit_terminal_exit(&self, _event: TerminalExit) {}
+}
+
+#[test]
+fn reconnect_tries_immediately_and_stops_for_missing_workspaces() {
+    let runtime = test_runtime();
+    let id = "workspace-1".to_string();
+    let entry = workspace_entry(&id, "Workspace");
+    let workspaces = Mutex::new(HashMap::from([(id.clone(), entry)]));
+    let sessions: Mutex<HashMap<String, Arc<WorkspaceSession>>> = Mutex::new(HashMap::new());
+    let app_settings = Mutex::new(AppSettings::default());
+    let sink = RecordingSink::default();
+    let spawn_failing =
+        |_, _, _, _| async { Err::<Arc<WorkspaceSession>, String>("codex not found".to_string()) };
+
+    runtime.block_on(async {
+        let started = Instant::now();
+        let result = reconnect_workspace_core(
+            id.clone(),
+            Some(1),
+            &workspaces,
+            &sessions,
+            &app_settings,
+            &sink,
+            spawn_failing,
+        )
+        .await;
+        assert_eq!(result, Err("codex not found".to_string()));
+        assert!(started.elapsed() < Duration::from_millis(500));
+        assert_eq!(
+            *sink.methods.lock().expect("events lock"),
+            vec!["codex/reconnecting", "codex/reconnect_failed"]
+        );
+
+        workspaces.lock().await.clear();
+        sink.methods.lock().expect("events lock").clear();
+        let result = reconnect_workspace_core(
+            id.clone(),
+            Some(5),
+            &workspaces,
+            &sessions,
+            &app_settings,
+            &sink,
+            spawn_failing,
+        )
+        .await;
+        assert_eq!(result, Err("workspace not found".to_string()));
+        assert_eq!(*sink.methods.lock().expect("events lock"), vec!["codex/reconnecting"]);
+    });
+}
+
+#[test]
+fn set_workspace_description_sanitizes_and_persists() {
+    let runtime = test_runtime();
+    // Entries saved before descriptions existed still load.
+    let entry: WorkspaceEntry = serde_json::from_value(serde_json::json!({
+        "id": "w1",
+        "name": "Hotfix",
+        "path": "/tmp",
+        "codex_bin": null,
+    }))
+    .expect("legacy entry");
+    assert_eq!(entry.description, None);
+    let workspaces = Mutex::new(HashMap::from([("w1".to_string(), entry)]));
+    let sessions: Mutex<HashMap<String, Arc<WorkspaceSession>>> = Mutex::new(HashMap::new());
+    let temp_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+    let path = temp_dir.join("workspaces.json");
+
+    let set = |id: &str, text: &str| {
+        runtime.block_on(set_workspace_description_core(
+            id.to_string(),
+            text.to_string(),
+            &workspaces,
+            &sessions,
+            &path,
+        ))
+    };
+    assert!(set("missing", "note").is_err());
+    assert!(set("w1", &"x".repeat(2001)).is_err());
+
+    let info = set("w1", "  incident\u{7}\r\n\tfollow-up \u{1b}[0m ").expect("set description");
+    assert_eq!(info.description.as_deref(), Some("incident\n\tfollow-up [0m"));
+    let stored = read_workspaces(&path).expect("read workspaces");
+    assert_eq!(
+        stored.get("w1").expect("stored").description.as_deref(),
+        Some("incident\n\tfollow-up [0m")
+    );
+
+    let info = set("w1", " \n ").expect("clear description");
+    assert_eq!(info.description, None);
+}
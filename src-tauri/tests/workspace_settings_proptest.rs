@@ -0,0 +1,135 @@
+// `WorkspaceSettings` is crate-private, so the types module is included by path the same
+// way the daemon binary shares it. Run with `cargo test --test workspace_settings_proptest`.
+#[allow(dead_code)]
+#[path = "../src/types.rs"]
+mod types;
+
+use proptest::prelude::*;
+use serde_json::{json, Value};
+
+use types::{LaunchScriptEntry, WorkspaceSettings};
+
+fn launch_script_entry() -> impl Strategy<Value = LaunchScriptEntry> {
+    (".*", ".*", ".*", proptest::option::of(".*")).prop_map(|(id, script, icon, label)| {
+        LaunchScriptEntry {
+            id,
+            script,
+            icon,
+            label,
+        }
+    })
+}
+
+fn workspace_settings() -> impl Strategy<Value = WorkspaceSettings> {
+    let layout = (
+        any::<bool>(),
+        proptest::option::of(any::<u32>()),
+        proptest::option::of(any::<u32>()),
+        proptest::option::of(".*"),
+        proptest::option::of(".*"),
+        proptest::option::of(".*"),
+        proptest::option::of(".*"),
+        proptest::option::of(".*"),
+        proptest::option::of(proptest::collection::vec(launch_script_entry(), 0..4)),
+    );
+    let git = (
+        proptest::option::of(".*"),
+        any::<bool>(),
+        ".*",
+        any::<u64>(),
+        any::<bool>(),
+        proptest::option::of(".*"),
+        proptest::option::of(".*"),
+        any::<bool>(),
+    );
+    let codex = (
+        proptest::option::of(".*"),
+        proptest::option::of(".*"),
+        proptest::option::of(".*"),
+    );
+    let session = (
+        proptest::option::of(any::<u64>()),
+        proptest::option::of(any::<u64>()),
+    );
+    (layout, git, codex, session).prop_map(
+        |(
+            (
+                sidebar_collapsed,
+                sort_order,
+                pin_order,
+                group_id,
+                git_root,
+                codex_home,
+                codex_args,
+                launch_script,
+                launch_scripts,
+            ),
+            (
+                worktree_setup_script,
+                git_push_after_commit,
+                git_push_remote_name,
+                startup_jitter_ms,
+                git_sign_commits,
+                git_signing_key,
+                log_level,
+                auto_refresh_git_status,
+            ),
+            (model, effort, default_access_mode),
+            (ping_interval_secs, stall_timeout_secs),
+        )| WorkspaceSettings {
+            sidebar_collapsed,
+            sort_order,
+            pin_order,
+            group_id,
+            git_root,
+            codex_home,
+            codex_args,
+            launch_script,
+            launch_scripts,
+            worktree_setup_script,
+            git_push_after_commit,
+            git_push_remote_name,
+            startup_jitter_ms,
+            git_sign_commits,
+            git_signing_key,
+            log_level,
+            auto_refresh_git_status,
+            model,
+            effort,
+            default_access_mode,
+            ping_interval_secs,
+            stall_timeout_secs,
+        },
+    )
+}
+
+fn to_value(settings: &WorkspaceSettings) -> Value {
+    serde_json::to_value(settings).expect("serialize workspace settings")
+}
+
+proptest! {
+    #[test]
+    fn workspace_settings_round_trip_is_lossless(settings in workspace_settings()) {
+        let encoded = serde_json::to_string(&settings).expect("encode");
+        let decoded: WorkspaceSettings = serde_json::from_str(&encoded).expect("decode");
+        prop_assert_eq!(to_value(&decoded), to_value(&settings));
+    }
+
+    #[test]
+    fn workspace_settings_ignore_unknown_fields(
+        settings in workspace_settings(),
+        extra in "[a-z]{1,12}",
+    ) {
+        let mut value = to_value(&settings);
+        let key = format!("unknown_{extra}");
+        value[key.as_str()] = json!({ "nested": [1, 2, 3] });
+        let decoded: WorkspaceSettings = serde_json::from_value(value).expect("decode");
+        prop_assert_eq!(to_value(&decoded), to_value(&settings));
+    }
+}
+
+#[test]
+fn workspace_settings_empty_object_matches_default() {
+    let decoded: WorkspaceSettings = serde_json::from_str("{}").expect("decode");
+    assert_eq!(to_value(&decoded), to_value(&WorkspaceSettings::default()));
+}
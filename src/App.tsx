@@ -539,13 +539,15 @@ function MainApp() {
     await handleSetGitRoot(nextRoot);
   }, [activeWorkspace, handleSetGitRoot, normalizePath]);
   const fileStatus =
-    gitStatus.error
-      ? "Git status unavailable"
-      : gitStatus.files.length > 0
-        ? `${gitStatus.files.length} file${
-            gitStatus.files.length === 1 ? "" : "s"
-          } changed`
-        : "Working tree clean";
+    gitStatus.notGitRepo
+      ? "Not a git repository"
+      : gitStatus.error
+        ? "Git status unavailable"
+        : gitStatus.files.length > 0
+          ? `${gitStatus.files.length} file${
+              gitStatus.files.length === 1 ? "" : "s"
+            } changed`
+          : "Working tree clean";
 
   usePersistComposerSettings({
     appSettingsLoading,
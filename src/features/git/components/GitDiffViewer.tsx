@@ -13,6 +13,7 @@ type GitDiffViewerItem = {
   path: string;
   status: string;
   diff: string;
+  isBinary?: boolean;
   isImage?: boolean;
   oldImageData?: string | null;
   newImageData?: string | null;
@@ -159,6 +160,8 @@ const DiffCard = memo(function DiffCard({
             style={{ width: "100%", maxWidth: "100%", minWidth: 0 }}
           />
         </div>
+      ) : entry.isBinary ? (
+        <div className="diff-viewer-placeholder">Binary file changed.</div>
       ) : (
         <div className="diff-viewer-placeholder">Diff unavailable.</div>
       )}
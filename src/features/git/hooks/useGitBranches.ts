@@ -45,13 +45,9 @@ export function useGitBranches({ activeWorkspace, onDebug }: UseGitBranchesOptio
         label: "git/branches/list response",
         payload: response,
       });
-      const data = response?.branches ?? response?.result?.branches ?? response ?? [];
-      const normalized: BranchInfo[] = Array.isArray(data)
-        ? data.map((item: any) => ({
-            name: String(item?.name ?? ""),
-            lastCommit: Number(item?.lastCommit ?? item?.last_commit ?? 0),
-          }))
-        : [];
+      const normalized: BranchInfo[] = response
+        .filter((item) => !item.isRemote)
+        .map((item) => ({ name: item.name, lastCommit: item.lastCommit }));
       setBranches(normalized.filter((branch) => branch.name));
       lastFetchedWorkspaceId.current = workspaceId;
       setError(null);
@@ -106,6 +106,7 @@ export function useGitDiffs(
         path: file.path,
         status: file.status,
         diff: entry?.diff ?? "",
+        isBinary: entry?.isBinary,
         isImage: entry?.isImage,
         oldImageData: entry?.oldImageData,
         newImageData: entry?.newImageData,
@@ -46,7 +46,7 @@ export function useGitLog(
     requestIdRef.current = requestId;
     setState((prev) => ({ ...prev, isLoading: true, error: null }));
     try {
-      const response = await getGitLog(workspaceId);
+      const response = await getGitLog(workspaceId, undefined, { countTotal: true });
       if (
         requestIdRef.current !== requestId ||
         workspaceIdRef.current !== workspaceId
@@ -55,7 +55,7 @@ export function useGitLog(
       }
       setState({
         entries: response.entries,
-        total: response.total,
+        total: response.total ?? 0,
         ahead: response.ahead,
         behind: response.behind,
         aheadEntries: response.aheadEntries,
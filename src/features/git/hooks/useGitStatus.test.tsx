@@ -1,12 +1,23 @@
 // @vitest-environment jsdom
 import { act, renderHook } from "@testing-library/react";
 import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
-import type { WorkspaceInfo } from "../../../types";
-import { getGitStatus } from "../../../services/tauri";
+import type { AppServerEvent, WorkspaceInfo } from "../../../types";
+import { subscribeAppServerEvents } from "../../../services/events";
+import { GitRepoStatusError, getGitStatus } from "../../../services/tauri";
 import { useGitStatus } from "./useGitStatus";
 
-vi.mock("../../../services/tauri", () => ({
-  getGitStatus: vi.fn(),
+vi.mock("../../../services/tauri", async () => {
+  const actual = await vi.importActual<typeof import("../../../services/tauri")>(
+    "../../../services/tauri",
+  );
+  return {
+    GitRepoStatusError: actual.GitRepoStatusError,
+    getGitStatus: vi.fn(),
+  };
+});
+
+vi.mock("../../../services/events", () => ({
+  subscribeAppServerEvents: vi.fn(() => () => {}),
 }));
 
 const workspace: WorkspaceInfo = {
@@ -103,6 +114,44 @@ describe("useGitStatus", () => {
     unmount();
   });
 
+  it("refreshes when the workspace reports a git change", async () => {
+    const getGitStatusMock = vi.mocked(getGitStatus);
+    getGitStatusMock
+      .mockResolvedValueOnce(makeStatus("main"))
+      .mockResolvedValueOnce(makeStatus("watched", 7, 0));
+    let emit: (event: AppServerEvent) => void = () => {};
+    vi.mocked(subscribeAppServerEvents).mockImplementation((cb) => {
+      emit = cb;
+      return () => {};
+    });
+
+    const { result, unmount } = renderHook(
+      ({ active }: { active: WorkspaceInfo | null }) => useGitStatus(active),
+      { initialProps: { active: workspace } },
+    );
+    await act(async () => {
+      await Promise.resolve();
+    });
+
+    await act(async () => {
+      emit({
+        workspace_id: secondaryWorkspace.id,
+        message: { method: "codex/gitChanged" },
+      });
+      await Promise.resolve();
+    });
+    expect(getGitStatusMock).toHaveBeenCalledTimes(1);
+
+    await act(async () => {
+      emit({ workspace_id: workspace.id, message: { method: "codex/gitChanged" } });
+      await Promise.resolve();
+    });
+    expect(getGitStatusMock).toHaveBeenCalledTimes(2);
+    expect(result.current.status.branchName).toBe("watched");
+
+    unmount();
+  });
+
   it("refreshes on workspace changes and ignores stale results", async () => {
     const getGitStatusMock = vi.mocked(getGitStatus);
     let resolveFirst: (value: ReturnType<typeof makeStatus>) => void;
@@ -179,6 +228,28 @@ describe("useGitStatus", () => {
 
     expect(result.current.status.branchName).toBe("main");
     expect(result.current.status.error).toBe("boom");
+    expect(result.current.status.notGitRepo).toBe(false);
+
+    unmount();
+  });
+
+  it("flags workspaces that are not git repositories", async () => {
+    const getGitStatusMock = vi.mocked(getGitStatus);
+    getGitStatusMock.mockRejectedValueOnce(
+      new GitRepoStatusError({ kind: "not_a_git_repo", path: "/tmp/codex" }),
+    );
+
+    const { result, unmount } = renderHook(
+      ({ active }: { active: WorkspaceInfo | null }) => useGitStatus(active),
+      { initialProps: { active: workspace } },
+    );
+
+    await act(async () => {
+      await Promise.resolve();
+    });
+
+    expect(result.current.status.notGitRepo).toBe(true);
+    expect(result.current.status.error).toBe("Not a git repository: /tmp/codex");
 
     unmount();
   });
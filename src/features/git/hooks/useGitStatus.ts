@@ -1,6 +1,7 @@
 import { useCallback, useEffect, useRef, useState } from "react";
 import type { GitFileStatus, WorkspaceInfo } from "../../../types";
-import { getGitStatus } from "../../../services/tauri";
+import { subscribeAppServerEvents } from "../../../services/events";
+import { GitRepoStatusError, getGitStatus } from "../../../services/tauri";
 
 type GitStatusState = {
   branchName: string;
@@ -10,6 +11,7 @@ type GitStatusState = {
   totalAdditions: number;
   totalDeletions: number;
   error: string | null;
+  notGitRepo: boolean;
 };
 
 const emptyStatus: GitStatusState = {
@@ -20,6 +22,7 @@ const emptyStatus: GitStatusState = {
   totalAdditions: 0,
   totalDeletions: 0,
   error: null,
+  notGitRepo: false,
 };
 
 const REFRESH_INTERVAL_MS = 3000;
@@ -65,6 +68,7 @@ export function useGitStatus(activeWorkspace: WorkspaceInfo | null) {
           ...data,
           branchName: resolvedBranchName,
           error: null,
+          notGitRepo: false,
         };
         setStatus(nextStatus);
         cachedStatusRef.current.set(workspaceId, nextStatus);
@@ -78,10 +82,12 @@ export function useGitStatus(activeWorkspace: WorkspaceInfo | null) {
           return;
         }
         const message = err instanceof Error ? err.message : String(err);
+        const notGitRepo =
+          err instanceof GitRepoStatusError && err.kind === "not_a_git_repo";
         const cached = cachedStatusRef.current.get(workspaceId);
         const nextStatus = cached
-          ? { ...cached, error: message }
-          : { ...emptyStatus, branchName: "unknown", error: message };
+          ? { ...cached, error: message, notGitRepo }
+          : { ...emptyStatus, branchName: "unknown", error: message, notGitRepo };
         setStatus(nextStatus);
       });
   }, [resolveBranchName, workspaceId]);
@@ -117,5 +123,19 @@ export function useGitStatus(activeWorkspace: WorkspaceInfo | null) {
     };
   }, [refresh, workspaceId]);
 
+  useEffect(() => {
+    if (!workspaceId) {
+      return;
+    }
+    return subscribeAppServerEvents((payload) => {
+      if (
+        payload.workspace_id === workspaceId &&
+        payload.message.method === "codex/gitChanged"
+      ) {
+        refresh()?.catch(() => {});
+      }
+    });
+  }, [refresh, workspaceId]);
+
   return { status, refresh };
 }
@@ -68,6 +68,7 @@ type GitDiffViewerItem = {
   path: string;
   status: string;
   diff: string;
+  isBinary?: boolean;
   isImage?: boolean;
   oldImageData?: string | null;
   newImageData?: string | null;
@@ -78,6 +78,7 @@ const defaultSettings: AppSettings = {
   workspaceGroups: [],
   openAppTargets: DEFAULT_OPEN_APP_TARGETS,
   selectedOpenAppId: DEFAULT_OPEN_APP_ID,
+  eventLogEnabled: false,
 };
 
 function normalizeAppSettings(settings: AppSettings): AppSettings {
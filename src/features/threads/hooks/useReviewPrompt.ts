@@ -66,11 +66,14 @@ type PresetOption = (typeof PRESET_OPTIONS)[number];
 
 function extractBranches(response: unknown): BranchInfo[] {
   const record = (response ?? {}) as Record<string, unknown>;
-  const data = record.branches ?? (record.result as Record<string, unknown> | undefined)?.branches;
+  const data = Array.isArray(response)
+    ? response
+    : record.branches ?? (record.result as Record<string, unknown> | undefined)?.branches;
   if (!Array.isArray(data)) {
     return [];
   }
   const branches = data
+    .filter((item) => !(item as Record<string, unknown>)?.isRemote)
     .map((item) => {
       const entry = item as Record<string, unknown>;
       return {
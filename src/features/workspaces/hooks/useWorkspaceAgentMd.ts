@@ -59,13 +59,13 @@ export function useWorkspaceAgentMd({ activeWorkspace, onDebug }: UseWorkspaceAg
       payload: { workspaceId: requestWorkspaceId },
     });
     try {
-      await writeAgentMd(requestWorkspaceId, content);
+      const response = await writeAgentMd(requestWorkspaceId, content);
       onDebug?.({
         id: `${Date.now()}-server-agent-md-write`,
         timestamp: Date.now(),
         source: "server",
         label: "agents.md/write response",
-        payload: { ok: true },
+        payload: response,
       });
     } catch (error) {
       const message = error instanceof Error ? error.message : String(error);
@@ -4,7 +4,10 @@ import type { WorkspaceInfo } from "../../../types";
 type WorkspaceRestoreOptions = {
   workspaces: WorkspaceInfo[];
   hasLoaded: boolean;
-  connectWorkspace: (workspace: WorkspaceInfo) => Promise<void>;
+  connectWorkspace: (
+    workspace: WorkspaceInfo,
+    options?: { restore?: boolean },
+  ) => Promise<void>;
   listThreadsForWorkspace: (
     workspace: WorkspaceInfo,
     options?: { preserveState?: boolean },
@@ -31,7 +34,7 @@ export function useWorkspaceRestore({
       void (async () => {
         try {
           if (!workspace.connected) {
-            await connectWorkspace(workspace);
+            await connectWorkspace(workspace, { restore: true });
           }
           await listThreadsForWorkspace(workspace);
         } catch {
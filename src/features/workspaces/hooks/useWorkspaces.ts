@@ -179,6 +179,11 @@ export function useWorkspaces(options: UseWorkspacesOptions = {}) {
 
     const sortWorkspaces = (list: WorkspaceInfo[]) =>
       list.slice().sort((a, b) => {
+        const pinDiff =
+          getSortOrderValue(a.settings.pinOrder) - getSortOrderValue(b.settings.pinOrder);
+        if (pinDiff !== 0) {
+          return pinDiff;
+        }
         const orderDiff =
           getSortOrderValue(a.settings.sortOrder) - getSortOrderValue(b.settings.sortOrder);
         if (orderDiff !== 0) {
@@ -367,7 +372,10 @@ export function useWorkspaces(options: UseWorkspacesOptions = {}) {
     }
   }
 
-  async function connectWorkspace(entry: WorkspaceInfo) {
+  async function connectWorkspace(
+    entry: WorkspaceInfo,
+    options?: { restore?: boolean },
+  ) {
     onDebug?.({
       id: `${Date.now()}-client-connect-workspace`,
       timestamp: Date.now(),
@@ -376,7 +384,7 @@ export function useWorkspaces(options: UseWorkspacesOptions = {}) {
       payload: { workspaceId: entry.id, path: entry.path },
     });
     try {
-      await connectWorkspaceService(entry.id);
+      await connectWorkspaceService(entry.id, options);
     } catch (error) {
       onDebug?.({
         id: `${Date.now()}-client-connect-workspace-error`,
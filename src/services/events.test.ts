@@ -48,6 +48,38 @@ describe("events subscriptions", () => {
     expect(unlisten).toHaveBeenCalledTimes(1);
   });
 
+  it("filters app server events by thread id", async () => {
+    let listener: EventCallback<AppServerEvent> = () => {};
+    const unlisten = vi.fn();
+
+    vi.mocked(listen).mockImplementation((_event, handler) => {
+      listener = handler as EventCallback<AppServerEvent>;
+      return Promise.resolve(unlisten);
+    });
+
+    const onEvent = vi.fn();
+    const cleanup = subscribeAppServerEvents(onEvent, { threadId: "thread-1" });
+    const matching: AppServerEvent = {
+      workspace_id: "ws-1",
+      threadId: "thread-1",
+      message: { method: "item/agentMessage/delta" },
+    };
+    const other: AppServerEvent = {
+      workspace_id: "ws-1",
+      threadId: "thread-2",
+      message: { method: "item/agentMessage/delta" },
+    };
+
+    listener({ event: "app-server-event", id: 1, payload: other });
+    listener({ event: "app-server-event", id: 2, payload: matching });
+    expect(onEvent).toHaveBeenCalledTimes(1);
+    expect(onEvent).toHaveBeenCalledWith(matching);
+
+    cleanup();
+    await Promise.resolve();
+    expect(unlisten).toHaveBeenCalledTimes(1);
+  });
+
   it("cleans up listeners that resolve after unsubscribe", async () => {
     let resolveListener: (handler: UnlistenFn) => void = () => {};
     const unlisten = vi.fn();
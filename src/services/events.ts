@@ -113,9 +113,17 @@ const menuComposerCycleCollaborationHub = createEventHub<void>(
 
 export function subscribeAppServerEvents(
   onEvent: (event: AppServerEvent) => void,
-  options?: SubscriptionOptions,
+  options?: SubscriptionOptions & { threadId?: string },
 ): Unsubscribe {
-  return appServerHub.subscribe(onEvent, options);
+  const threadId = options?.threadId;
+  if (!threadId) {
+    return appServerHub.subscribe(onEvent, options);
+  }
+  return appServerHub.subscribe((event) => {
+    if (event.threadId === threadId) {
+      onEvent(event);
+    }
+  }, options);
 }
 
 export function subscribeDictationDownload(
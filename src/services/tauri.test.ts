@@ -3,6 +3,7 @@ import { invoke } from "@tauri-apps/api/core";
 import * as notification from "@tauri-apps/plugin-notification";
 import {
   addWorkspace,
+  createGitCommit,
   forkThread,
   getGitHubIssues,
   getGitLog,
@@ -78,6 +79,34 @@ describe("tauri invoke wrappers", () => {
     });
   });
 
+  it("turns tagged git status errors into messages", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockRejectedValueOnce({ kind: "not_a_git_repo", path: "/tmp/plain" });
+    await expect(getGitStatus("ws-1")).rejects.toMatchObject({
+      kind: "not_a_git_repo",
+      path: "/tmp/plain",
+      message: "Not a git repository: /tmp/plain",
+    });
+
+    invokeMock.mockRejectedValueOnce({ kind: "failed", message: "permission denied" });
+    await expect(getGitStatus("ws-1")).rejects.toThrow("permission denied");
+  });
+
+  it("keeps the commit sha when the push after createGitCommit fails", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockRejectedValueOnce({
+      kind: "commit_ok_push_failed",
+      sha: "0123456789abcdef",
+      message: "remote rejected",
+    });
+    await expect(createGitCommit("ws-1", "update")).rejects.toThrow(
+      "Committed 0123456 but push failed: remote rejected",
+    );
+
+    invokeMock.mockRejectedValueOnce({ kind: "commit_failed", message: "nothing staged" });
+    await expect(createGitCommit("ws-1", "update")).rejects.toThrow("nothing staged");
+  });
+
   it("maps workspace_id to workspaceId for GitHub issues", async () => {
     const invokeMock = vi.mocked(invoke);
     invokeMock.mockResolvedValueOnce({ total: 0, issues: [] });
@@ -116,6 +145,10 @@ describe("tauri invoke wrappers", () => {
     expect(invokeMock).toHaveBeenCalledWith("get_git_log", {
       workspaceId: "ws-3",
       limit: 40,
+      cursor: null,
+      countTotal: null,
+      path: null,
+      author: null,
     });
   });
 
@@ -1,23 +1,54 @@
-import { invoke } from "@tauri-apps/api/core";
+import { Channel, invoke } from "@tauri-apps/api/core";
 import { open } from "@tauri-apps/plugin-dialog";
 import type {
   AppSettings,
+  BulkOperationResult,
   ClaudeCodeDoctorResult,
   DictationModelStatus,
   DictationSessionState,
+  GlobalSettings,
   LocalUsageSnapshot,
+  LogLevel,
+  ModelInfo,
+  PathDiagnostics,
+  SessionInfo,
+  RecentThread,
+  SessionProcessInfo,
+  StaleSessionCleanup,
+  ThreadPreferences,
+  WorkspaceActivity,
   WorkspaceInfo,
+  WorkspaceLockStatus,
   WorkspaceSettings,
 } from "../types";
 import type {
+  GitBranchInfo,
+  GitDiffMode,
   GitFileDiff,
   GitFileStatus,
+  GitCommitDetails,
   GitCommitDiff,
+  GitCommitFileStat,
+  GitDiffHunkPage,
+  GitPathStatus,
+  GitStagedFile,
+  GitChangedFile,
+  GitReflogEntry,
+  GitOperationState,
+  GitReviewBundle,
+  GitResetMode,
+  GitResetResult,
+  GitStashEntry,
+  ProjectSummary,
+  RepoConventions,
   GitHubIssuesResponse,
   GitHubPullRequestComment,
   GitHubPullRequestDiff,
   GitHubPullRequestsResponse,
+  GitLogEntry,
   GitLogResponse,
+  GitRemoteInfo,
+  GitStatusChunk,
   ReviewTarget,
 } from "../types";
 
@@ -67,6 +98,14 @@ export async function listWorkspaces(): Promise<WorkspaceInfo[]> {
   }
 }
 
+export async function reloadWorkspaces(): Promise<WorkspaceInfo[]> {
+  return invoke<WorkspaceInfo[]>("reload_workspaces");
+}
+
+export async function getEventLogPath(): Promise<string> {
+  return invoke<string>("get_event_log_path");
+}
+
 export async function getClaudeCodeConfigPath(): Promise<string> {
   return invoke<string>("get_claude_code_config_path");
 }
@@ -77,19 +116,62 @@ export type TextFileResponse = {
   truncated: boolean;
 };
 
+export type TextFileChunk = {
+  exists: boolean;
+  content: string;
+  offset: number;
+  nextOffset: number;
+  totalBytes: number;
+  eof: boolean;
+};
+
 export type GlobalAgentsResponse = TextFileResponse;
 export type GlobalClaudeCodeConfigResponse = TextFileResponse;
 export type AgentMdResponse = TextFileResponse;
 
-type FileScope = "workspace" | "global";
-type FileKind = "agents" | "config" | "claude_json";
+export type FileWriteWarning = {
+  code: string;
+  message: string;
+  line?: number;
+};
+
+export type FileWriteResponse = {
+  warnings: FileWriteWarning[];
+};
+
+export type FileScope = "workspace" | "global";
+export type FileKind = "agents" | "config" | "claude_json";
 
 async function fileRead(
   scope: FileScope,
   kind: FileKind,
   workspaceId?: string,
+  subpath?: string,
 ): Promise<TextFileResponse> {
-  return invoke<TextFileResponse>("file_read", { scope, kind, workspaceId });
+  return invoke<TextFileResponse>("file_read", {
+    scope,
+    kind,
+    workspaceId,
+    ...(subpath ? { subpath } : {}),
+  });
+}
+
+export async function readFileChunk(
+  scope: FileScope,
+  kind: FileKind,
+  offset = 0,
+  maxBytes?: number,
+  workspaceId?: string,
+  subpath?: string,
+): Promise<TextFileChunk> {
+  return invoke<TextFileChunk>("file_read_chunk", {
+    scope,
+    kind,
+    workspaceId,
+    ...(subpath ? { subpath } : {}),
+    offset,
+    maxBytes,
+  });
 }
 
 async function fileWrite(
@@ -97,15 +179,44 @@ async function fileWrite(
   kind: FileKind,
   content: string,
   workspaceId?: string,
-): Promise<void> {
-  return invoke("file_write", { scope, kind, workspaceId, content });
+  subpath?: string,
+): Promise<FileWriteResponse> {
+  return invoke<FileWriteResponse>("file_write", {
+    scope,
+    kind,
+    workspaceId,
+    ...(subpath ? { subpath } : {}),
+    content,
+  });
+}
+
+export type FileWritePreview = {
+  exists: boolean;
+  changed: boolean;
+  diff: string;
+};
+
+export async function previewFileWrite(
+  scope: FileScope,
+  kind: FileKind,
+  content: string,
+  workspaceId?: string,
+  subpath?: string,
+): Promise<FileWritePreview> {
+  return invoke<FileWritePreview>("file_preview_write", {
+    scope,
+    kind,
+    workspaceId,
+    ...(subpath ? { subpath } : {}),
+    content,
+  });
 }
 
 export async function readGlobalAgentsMd(): Promise<GlobalAgentsResponse> {
   return fileRead("global", "agents");
 }
 
-export async function writeGlobalAgentsMd(content: string): Promise<void> {
+export async function writeGlobalAgentsMd(content: string): Promise<FileWriteResponse> {
   return fileWrite("global", "agents", content);
 }
 
@@ -114,7 +225,7 @@ export async function readGlobalClaudeCodeConfig(): Promise<GlobalClaudeCodeConf
 }
 
 export async function writeGlobalClaudeCodeConfig(content: string): Promise<void> {
-  return fileWrite("global", "config", content);
+  await fileWrite("global", "config", content);
 }
 
 export async function readClaudeJson(): Promise<GlobalClaudeCodeConfigResponse> {
@@ -122,7 +233,39 @@ export async function readClaudeJson(): Promise<GlobalClaudeCodeConfigResponse>
 }
 
 export async function writeClaudeJson(content: string): Promise<void> {
-  return fileWrite("global", "claude_json", content);
+  await fileWrite("global", "claude_json", content);
+}
+
+export type ClaudeJsonPruneResponse = {
+  removed: number;
+  backupPath: string | null;
+};
+
+export async function pruneClaudeJson(keepRecent: number): Promise<ClaudeJsonPruneResponse> {
+  return invoke<ClaudeJsonPruneResponse>("prune_claude_json", { keepRecent });
+}
+
+export type ConfigBackupResponse = {
+  path: string;
+  files: string[];
+};
+
+export async function backupConfigs(): Promise<ConfigBackupResponse> {
+  return invoke<ConfigBackupResponse>("backup_configs");
+}
+
+export async function restoreConfigs(backupPath: string): Promise<ConfigBackupResponse> {
+  return invoke<ConfigBackupResponse>("restore_configs", { backupPath });
+}
+
+export type FileMergeResult = {
+  mergedContent: string;
+  hadConflicts: boolean;
+  conflictMarkers: { startLine: number; endLine: number }[];
+};
+
+export async function mergeAgentsMd(workspaceId: string): Promise<FileMergeResult> {
+  return invoke<FileMergeResult>("file_merge", { workspaceId, kind: "agents" });
 }
 
 export async function getConfigModel(workspaceId: string): Promise<string | null> {
@@ -140,10 +283,18 @@ export async function getConfigModel(workspaceId: string): Promise<string | null
 export async function addWorkspace(
   path: string,
   claude_code_bin: string | null,
+  operationId?: string,
 ): Promise<WorkspaceInfo> {
+  if (operationId) {
+    return invoke<WorkspaceInfo>("add_workspace", { path, claude_code_bin, operationId });
+  }
   return invoke<WorkspaceInfo>("add_workspace", { path, claude_code_bin });
 }
 
+export async function getGlobalStatus(): Promise<WorkspaceActivity[]> {
+  return invoke<WorkspaceActivity[]>("get_global_status");
+}
+
 export async function isWorkspacePathDir(path: string): Promise<boolean> {
   return invoke<boolean>("is_workspace_path_dir", { path });
 }
@@ -189,6 +340,29 @@ export async function updateWorkspaceSettings(
   return invoke<WorkspaceInfo>("update_workspace_settings", { id, settings });
 }
 
+export async function renameWorkspace(id: string, name: string): Promise<WorkspaceInfo> {
+  return invoke<WorkspaceInfo>("rename_workspace", { id, name });
+}
+
+export async function setWorkspaceDescription(
+  id: string,
+  text: string,
+): Promise<WorkspaceInfo> {
+  return invoke<WorkspaceInfo>("set_workspace_description", { id, text });
+}
+
+export async function setMonitorOnly(id: string, value: boolean): Promise<WorkspaceInfo> {
+  return invoke<WorkspaceInfo>("set_monitor_only", { id, value });
+}
+
+export async function setWorkspacePinOrder(id: string, order: number): Promise<void> {
+  return invoke("set_workspace_pin_order", { id, order });
+}
+
+export async function reloadLogLevel(workspaceId: string, level: LogLevel): Promise<void> {
+  return invoke("reload_log_level", { workspaceId, level });
+}
+
 export async function updateWorkspaceClaudeCodeBin(
   id: string,
   claude_code_bin: string | null,
@@ -243,8 +417,40 @@ export async function getOpenAppIcon(appName: string): Promise<string | null> {
   return invoke<string | null>("get_open_app_icon", { appName });
 }
 
-export async function connectWorkspace(id: string): Promise<void> {
-  return invoke("connect_workspace", { id });
+export async function connectWorkspace(
+  id: string,
+  options?: { restore?: boolean; operationId?: string },
+): Promise<void> {
+  return invoke("connect_workspace", {
+    id,
+    restore: options?.restore ?? false,
+    operationId: options?.operationId ?? null,
+  });
+}
+
+export async function reconnectWorkspace(
+  id: string,
+  options?: { maxAttempts?: number; operationId?: string },
+): Promise<void> {
+  return invoke("reconnect_workspace", {
+    id,
+    maxAttempts: options?.maxAttempts ?? null,
+    operationId: options?.operationId ?? null,
+  });
+}
+
+export async function checkWorkspaceLock(workspaceId: string): Promise<WorkspaceLockStatus> {
+  return invoke<WorkspaceLockStatus>("check_workspace_lock", { workspaceId });
+}
+
+export async function clearStaleSessionState(
+  workspaceId: string,
+): Promise<StaleSessionCleanup> {
+  return invoke<StaleSessionCleanup>("clear_stale_session_state", { workspaceId });
+}
+
+export async function cancelConnect(operationId: string): Promise<void> {
+  return invoke("cancel_connect", { operationId });
 }
 
 export async function startThread(workspaceId: string) {
@@ -265,6 +471,7 @@ export async function sendUserMessage(
     accessMode?: "read-only" | "current" | "full-access";
     images?: string[];
     collaborationMode?: Record<string, unknown> | null;
+    focusPaths?: string[];
   },
 ) {
   const payload: Record<string, unknown> = {
@@ -279,6 +486,9 @@ export async function sendUserMessage(
   if (options?.collaborationMode) {
     payload.collaborationMode = options.collaborationMode;
   }
+  if (options?.focusPaths?.length) {
+    payload.focusPaths = options.focusPaths;
+  }
   return invoke("send_user_message", payload);
 }
 
@@ -290,6 +500,76 @@ export async function interruptTurn(
   return invoke("turn_interrupt", { workspaceId, threadId, turnId });
 }
 
+export async function getCurrentTurnOutput(
+  workspaceId: string,
+  threadId: string,
+): Promise<{ threadId: string; inProgress: boolean; text: string }> {
+  return invoke("get_current_turn_output", { workspaceId, threadId });
+}
+
+export type PendingRequestInfo = {
+  id: number;
+  method: string;
+  enqueuedAtMs: number;
+  ageMs: number;
+};
+
+/** Resolves to `null` when the app-server doesn't advertise its method list. */
+export async function supportsMethod(
+  workspaceId: string,
+  method: string,
+): Promise<boolean | null> {
+  return invoke<boolean | null>("supports_method", { workspaceId, method });
+}
+
+export type ApprovalPolicy = "untrusted" | "on-failure" | "on-request" | "never";
+
+export async function setRuntimeApprovalPolicy(
+  workspaceId: string,
+  policy: ApprovalPolicy,
+): Promise<void> {
+  await invoke("set_runtime_approval_policy", { workspaceId, policy });
+}
+
+export async function getSessionInfo(workspaceId: string): Promise<SessionInfo> {
+  return invoke<SessionInfo>("get_session_info", { workspaceId });
+}
+
+export async function getSessionProcessInfo(
+  workspaceId: string,
+): Promise<SessionProcessInfo> {
+  return invoke<SessionProcessInfo>("get_session_process_info", { workspaceId });
+}
+
+export async function getPathDiagnostics(workspaceId: string): Promise<PathDiagnostics> {
+  return invoke<PathDiagnostics>("get_path_diagnostics", { workspaceId });
+}
+
+export async function getUnreadThreads(
+  workspaceId: string,
+  lastViewed: Record<string, number>,
+): Promise<string[]> {
+  return invoke<string[]>("get_unread_threads", { workspaceId, lastViewed });
+}
+
+export async function getRecentThreads(
+  limit?: number | null,
+): Promise<RecentThread[]> {
+  return invoke<RecentThread[]>("get_recent_threads", { limit: limit ?? null });
+}
+
+export async function recheckCodex(workspaceId: string): Promise<string | null> {
+  return invoke<string | null>("recheck_codex", { workspaceId });
+}
+
+export async function getSessionPendingRequests(
+  workspaceId: string,
+): Promise<PendingRequestInfo[]> {
+  return invoke<PendingRequestInfo[]>("get_session_pending_requests", {
+    workspaceId,
+  });
+}
+
 export async function startReview(
   workspaceId: string,
   threadId: string,
@@ -334,15 +614,55 @@ export async function rememberApprovalRule(
   return invoke("remember_approval_rule", { workspaceId, command });
 }
 
+export type GitRepoError =
+  | { kind: "not_a_git_repo"; path: string }
+  | { kind: "failed"; message: string };
+
+// Keeps the backend's `kind` so callers can tell a folder that isn't a repository yet
+// apart from a repository that failed to load.
+export class GitRepoStatusError extends Error {
+  readonly kind: GitRepoError["kind"];
+  readonly path: string | null;
+
+  constructor(error: GitRepoError) {
+    super(
+      error.kind === "not_a_git_repo"
+        ? `Not a git repository: ${error.path}`
+        : error.message,
+    );
+    this.name = "GitRepoStatusError";
+    this.kind = error.kind;
+    this.path = error.kind === "not_a_git_repo" ? error.path : null;
+  }
+}
+
 export async function getGitStatus(workspace_id: string): Promise<{
   branchName: string;
+  ahead: number | null;
+  behind: number | null;
   files: GitFileStatus[];
   stagedFiles: GitFileStatus[];
   unstagedFiles: GitFileStatus[];
   totalAdditions: number;
   totalDeletions: number;
 }> {
-  return invoke("get_git_status", { workspaceId: workspace_id });
+  try {
+    return await invoke("get_git_status", { workspaceId: workspace_id });
+  } catch (error) {
+    if (error && typeof error === "object" && "kind" in error) {
+      throw new GitRepoStatusError(error as GitRepoError);
+    }
+    throw error;
+  }
+}
+
+export async function streamGitStatus(
+  workspace_id: string,
+  onChunk: (chunk: GitStatusChunk) => void,
+): Promise<void> {
+  const channel = new Channel<GitStatusChunk>();
+  channel.onmessage = onChunk;
+  return invoke("stream_git_status", { workspaceId: workspace_id, channel });
 }
 
 export async function listGitRoots(
@@ -354,15 +674,117 @@ export async function listGitRoots(
 
 export async function getGitDiffs(
   workspace_id: string,
+  mode?: GitDiffMode,
+  contextLines?: number,
+): Promise<GitFileDiff[]> {
+  return invoke("get_git_diffs", {
+    workspaceId: workspace_id,
+    mode: mode ?? null,
+    contextLines: contextLines ?? null,
+  });
+}
+
+export async function getGitFileDiff(
+  workspace_id: string,
+  path: string,
+): Promise<GitFileDiff> {
+  return invoke("get_git_file_diff", { workspaceId: workspace_id, path });
+}
+
+export async function getWorkingPatchId(workspace_id: string): Promise<string | null> {
+  return invoke("get_working_patch_id", { workspaceId: workspace_id });
+}
+
+export async function diffAgainstBranch(
+  workspace_id: string,
+  branch: string,
 ): Promise<GitFileDiff[]> {
-  return invoke("get_git_diffs", { workspaceId: workspace_id });
+  return invoke("diff_against_branch", { workspaceId: workspace_id, branch });
+}
+
+export async function getGitDiffForFile(
+  workspace_id: string,
+  path: string,
+  options?: { hunkCursor?: number; hunkLimit?: number },
+): Promise<GitDiffHunkPage> {
+  return invoke("get_git_diff_for_file", {
+    workspaceId: workspace_id,
+    path,
+    hunkCursor: options?.hunkCursor ?? null,
+    hunkLimit: options?.hunkLimit ?? null,
+  });
 }
 
 export async function getGitLog(
   workspace_id: string,
   limit = 40,
+  options?: {
+    cursor?: string | null;
+    countTotal?: boolean;
+    path?: string | null;
+    author?: string | null;
+  },
 ): Promise<GitLogResponse> {
-  return invoke("get_git_log", { workspaceId: workspace_id, limit });
+  return invoke("get_git_log", {
+    workspaceId: workspace_id,
+    limit,
+    cursor: options?.cursor ?? null,
+    countTotal: options?.countTotal ?? null,
+    path: options?.path ?? null,
+    author: options?.author ?? null,
+  });
+}
+
+export async function getHeadInfo(workspace_id: string): Promise<GitLogEntry | null> {
+  return invoke("get_head_info", { workspaceId: workspace_id });
+}
+
+export async function listStagedFiles(workspace_id: string): Promise<GitStagedFile[]> {
+  return invoke("list_staged_files", { workspaceId: workspace_id });
+}
+
+export async function changedFilesBetween(
+  workspace_id: string,
+  base: string,
+  head: string,
+): Promise<GitChangedFile[]> {
+  return invoke("changed_files_between", { workspaceId: workspace_id, base, head });
+}
+
+export async function createWorkspaceFile(
+  workspace_id: string,
+  path: string,
+  content: string,
+  overwrite = false,
+): Promise<GitPathStatus> {
+  return invoke("create_workspace_file", {
+    workspaceId: workspace_id,
+    path,
+    content,
+    overwrite,
+  });
+}
+
+export async function deleteWorkspaceFile(
+  workspace_id: string,
+  path: string,
+  options: { stage?: boolean; recursive?: boolean } = {},
+): Promise<GitPathStatus> {
+  return invoke("delete_workspace_file", {
+    workspaceId: workspace_id,
+    path,
+    stage: options.stage ?? false,
+    recursive: options.recursive ?? false,
+  });
+}
+
+export async function moveWorkspaceFile(
+  workspace_id: string,
+  from: string,
+  to: string,
+  force = false,
+): Promise<GitPathStatus> {
+  return invoke("move_workspace_file", { workspaceId: workspace_id, from, to, force });
 }
 
 export async function getGitCommitDiff(
@@ -372,20 +794,117 @@ export async function getGitCommitDiff(
   return invoke("get_git_commit_diff", { workspaceId: workspace_id, sha });
 }
 
+export async function getCommitDetails(
+  workspace_id: string,
+  sha: string,
+): Promise<GitCommitDetails> {
+  return invoke("get_commit_details", { workspaceId: workspace_id, sha });
+}
+
+export async function getReflog(
+  workspace_id: string,
+  refName?: string,
+  limit?: number,
+): Promise<GitReflogEntry[]> {
+  return invoke("get_reflog", {
+    workspaceId: workspace_id,
+    refName: refName ?? null,
+    limit: limit ?? null,
+  });
+}
+
+export async function getRepoOperationState(
+  workspace_id: string,
+): Promise<GitOperationState> {
+  return invoke("get_repo_operation_state", { workspaceId: workspace_id });
+}
+
+export async function exportReviewBundle(
+  workspace_id: string,
+): Promise<GitReviewBundle> {
+  return invoke("export_review_bundle", { workspaceId: workspace_id });
+}
+
+export async function resetToCommit(
+  workspace_id: string,
+  sha: string,
+  mode: GitResetMode,
+  confirm = false,
+): Promise<GitResetResult> {
+  return invoke("reset_to_commit", { workspaceId: workspace_id, sha, mode, confirm });
+}
+
+export async function getCommitFileStats(
+  workspace_id: string,
+  sha: string,
+): Promise<GitCommitFileStat[]> {
+  return invoke("get_commit_file_stats", { workspaceId: workspace_id, sha });
+}
+
+export async function getProjectSummary(workspace_id: string): Promise<ProjectSummary> {
+  return invoke("get_project_summary", { workspaceId: workspace_id });
+}
+
+export async function detectRepoConventions(
+  workspace_id: string,
+): Promise<RepoConventions> {
+  return invoke("detect_repo_conventions", { workspaceId: workspace_id });
+}
+
 export async function getGitRemote(workspace_id: string): Promise<string | null> {
   return invoke("get_git_remote", { workspaceId: workspace_id });
 }
 
-export async function stageGitFile(workspaceId: string, path: string) {
-  return invoke("stage_git_file", { workspaceId, path });
+export async function setGitRemote(
+  workspace_id: string,
+  name: string,
+  url: string,
+): Promise<GitRemoteInfo[]> {
+  return invoke("set_git_remote", { workspaceId: workspace_id, name, url });
+}
+
+export async function removeGitRemote(
+  workspace_id: string,
+  name: string,
+): Promise<GitRemoteInfo[]> {
+  return invoke("remove_git_remote", { workspaceId: workspace_id, name });
+}
+
+export async function listStashes(workspace_id: string): Promise<GitStashEntry[]> {
+  return invoke("list_stashes", { workspaceId: workspace_id });
+}
+
+export async function gitStashPush(
+  workspace_id: string,
+  message?: string,
+): Promise<string> {
+  return invoke("git_stash_push", { workspaceId: workspace_id, message: message ?? null });
+}
+
+export async function gitStashPop(workspace_id: string, index?: number): Promise<void> {
+  return invoke("git_stash_pop", { workspaceId: workspace_id, index: index ?? null });
+}
+
+export async function stashApply(workspace_id: string, index: number): Promise<void> {
+  return invoke("stash_apply", { workspaceId: workspace_id, index });
+}
+
+export async function stageGitFile(
+  workspaceId: string,
+  path: string,
+): Promise<GitPathStatus> {
+  return invoke<GitPathStatus>("stage_git_file", { workspaceId, path });
 }
 
 export async function stageGitAll(workspaceId: string): Promise<void> {
   return invoke("stage_git_all", { workspaceId });
 }
 
-export async function unstageGitFile(workspaceId: string, path: string) {
-  return invoke("unstage_git_file", { workspaceId, path });
+export async function unstageGitFile(
+  workspaceId: string,
+  path: string,
+): Promise<GitPathStatus> {
+  return invoke<GitPathStatus>("unstage_git_file", { workspaceId, path });
 }
 
 export async function revertGitFile(workspaceId: string, path: string) {
@@ -396,11 +915,48 @@ export async function revertGitAll(workspaceId: string) {
   return invoke("revert_git_all", { workspaceId });
 }
 
+export type GitCommitError =
+  | { kind: "commit_failed"; message: string }
+  | { kind: "commit_ok_push_failed"; sha: string; message: string };
+
+function commitErrorToError(error: unknown): unknown {
+  if (error && typeof error === "object" && "kind" in error) {
+    const commitError = error as GitCommitError;
+    if (commitError.kind === "commit_ok_push_failed") {
+      return new Error(
+        `Committed ${commitError.sha.slice(0, 7)} but push failed: ${commitError.message}`,
+      );
+    }
+    return new Error(commitError.message);
+  }
+  return error;
+}
+
+export async function createGitCommit(
+  workspace_id: string,
+  message: string,
+): Promise<string> {
+  try {
+    return await invoke<string>("create_git_commit", { workspaceId: workspace_id, message });
+  } catch (error) {
+    throw commitErrorToError(error);
+  }
+}
+
 export async function commitGit(
   workspaceId: string,
   message: string,
-): Promise<void> {
-  return invoke("commit_git", { workspaceId, message });
+  paths?: string[],
+): Promise<GitLogEntry> {
+  try {
+    return await invoke<GitLogEntry>("commit_git", {
+      workspaceId,
+      message,
+      paths: paths ?? null,
+    });
+  } catch (error) {
+    throw commitErrorToError(error);
+  }
 }
 
 export async function pushGit(workspaceId: string): Promise<void> {
@@ -458,6 +1014,38 @@ export async function localUsageSnapshot(
   return invoke("local_usage_snapshot", payload);
 }
 
+export async function getThreadPreferences(
+  workspaceId: string,
+  threadId: string,
+): Promise<ThreadPreferences> {
+  return invoke<ThreadPreferences>("get_thread_preferences", {
+    workspaceId,
+    threadId,
+  });
+}
+
+export async function setThreadPreferences(
+  workspaceId: string,
+  threadId: string,
+  preferences: ThreadPreferences,
+  persist = false,
+): Promise<ThreadPreferences> {
+  return invoke<ThreadPreferences>("set_thread_preferences", {
+    workspaceId,
+    threadId,
+    model: preferences.model,
+    effort: preferences.effort,
+    persist,
+  });
+}
+
+export async function listModels(
+  workspaceId: string,
+  refresh = false,
+): Promise<ModelInfo[]> {
+  return invoke<ModelInfo[]>("list_models", { workspaceId, refresh });
+}
+
 export async function getModelList(workspaceId: string) {
   return invoke<any>("model_list", { workspaceId });
 }
@@ -581,6 +1169,14 @@ export async function updateAppSettings(settings: AppSettings): Promise<AppSetti
   return invoke<AppSettings>("update_app_settings", { settings });
 }
 
+export async function getGlobalSettings(): Promise<GlobalSettings> {
+  return invoke<GlobalSettings>("get_global_settings");
+}
+
+export async function updateGlobalSettings(settings: GlobalSettings): Promise<void> {
+  return invoke("update_global_settings", { settings });
+}
+
 type MenuAcceleratorUpdate = {
   id: string;
   accelerator: string | null;
@@ -613,16 +1209,32 @@ export async function readWorkspaceFile(
   });
 }
 
-export async function readAgentMd(workspaceId: string): Promise<AgentMdResponse> {
-  return fileRead("workspace", "agents", workspaceId);
+export async function readAgentMd(
+  workspaceId: string,
+  subpath?: string,
+): Promise<AgentMdResponse> {
+  return fileRead("workspace", "agents", workspaceId, subpath);
 }
 
-export async function writeAgentMd(workspaceId: string, content: string): Promise<void> {
-  return fileWrite("workspace", "agents", content, workspaceId);
+export async function writeAgentMd(
+  workspaceId: string,
+  content: string,
+  subpath?: string,
+): Promise<FileWriteResponse> {
+  return fileWrite("workspace", "agents", content, workspaceId, subpath);
 }
 
-export async function listGitBranches(workspaceId: string) {
-  return invoke<any>("list_git_branches", { workspaceId });
+export type AgentsFileEntry = {
+  path: string;
+  size: number;
+};
+
+export async function findAgentsFiles(workspaceId: string): Promise<AgentsFileEntry[]> {
+  return invoke<AgentsFileEntry[]>("find_agents_files", { workspaceId });
+}
+
+export async function listGitBranches(workspaceId: string): Promise<GitBranchInfo[]> {
+  return invoke<GitBranchInfo[]>("list_git_branches", { workspaceId });
 }
 
 export async function checkoutGitBranch(workspaceId: string, name: string) {
@@ -732,6 +1344,19 @@ export async function listThreads(
   return invoke<any>("list_threads", { workspaceId, cursor, limit });
 }
 
+export type ThreadSearchResponse = {
+  searchMethod: "server" | "client";
+  data: Record<string, unknown>[];
+};
+
+export async function searchThreads(
+  workspaceId: string,
+  query: string,
+  limit?: number | null,
+) {
+  return invoke<ThreadSearchResponse>("search_threads", { workspaceId, query, limit });
+}
+
 export async function listMcpServerStatus(
   workspaceId: string,
   cursor?: string | null,
@@ -748,6 +1373,22 @@ export async function archiveThread(workspaceId: string, threadId: string) {
   return invoke<any>("archive_thread", { workspaceId, threadId });
 }
 
+export async function pinThread(workspaceId: string, threadId: string) {
+  return invoke<any>("pin_thread", { workspaceId, threadId });
+}
+
+export async function unpinThread(workspaceId: string, threadId: string) {
+  return invoke<any>("unpin_thread", { workspaceId, threadId });
+}
+
+export async function exportThreadMarkdown(workspaceId: string, threadId: string) {
+  return invoke<string>("export_thread_markdown", { workspaceId, threadId });
+}
+
+export async function bulkArchiveThreads(workspaceId: string, threadIds: string[]) {
+  return invoke<BulkOperationResult>("bulk_archive_threads", { workspaceId, threadIds });
+}
+
 export async function setThreadName(
   workspaceId: string,
   threadId: string,
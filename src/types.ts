@@ -1,6 +1,7 @@
 export type WorkspaceSettings = {
   sidebarCollapsed: boolean;
   sortOrder?: number | null;
+  pinOrder?: number | null;
   groupId?: string | null;
   gitRoot?: string | null;
   codexHome?: string | null;
@@ -8,7 +9,21 @@ export type WorkspaceSettings = {
   launchScript?: string | null;
   launchScripts?: LaunchScriptEntry[] | null;
   worktreeSetupScript?: string | null;
-};
+  gitPushAfterCommit?: boolean;
+  gitPushRemoteName?: string;
+  startupJitterMs?: number;
+  gitSignCommits?: boolean;
+  gitSigningKey?: string | null;
+  logLevel?: LogLevel | null;
+  autoRefreshGitStatus?: boolean;
+  model?: string | null;
+  effort?: string | null;
+  defaultAccessMode?: AccessMode | null;
+  pingIntervalSecs?: number | null;
+  stallTimeoutSecs?: number | null;
+};
+
+export type LogLevel = "error" | "warn" | "info" | "debug" | "trace";
 
 export type LaunchScriptIconId =
   | "play"
@@ -43,10 +58,62 @@ export type WorkspaceGroup = {
 
 export type WorkspaceKind = "main" | "worktree";
 
+export type WorkspaceActivity = {
+  workspaceId: string;
+  name: string;
+  connected: boolean;
+  activeTurns: number;
+  lastEventAtMs: number | null;
+};
+
+export type WorkspaceLockStatus = {
+  heldElsewhere: boolean;
+  pid: number | null;
+};
+
+export type StaleSessionCleanup = {
+  removedLockPid: number | null;
+  removedLock: boolean;
+  removedTempFiles: string[];
+};
+
+export type SessionInfo = {
+  uptimeSecs: number;
+  idleSecs: number;
+};
+
+export type RecentThread = Record<string, unknown> & {
+  id: string;
+  workspaceId: string;
+  lastActivityAt: number;
+};
+
+export type SessionProcessInfo = {
+  pid: number;
+  cpuPercent: number | null;
+  memoryBytes: number | null;
+  status: string | null;
+};
+
+export type BulkOperationResult = {
+  succeeded: string[];
+  failed: { id: string; error: string }[];
+};
+
+export type PathDiagnostics = {
+  path: string | null;
+  addedEntries: string[];
+  codexBin: string;
+  matches: string[];
+  found: boolean;
+};
+
 export type WorktreeInfo = {
   branch: string;
 };
 
+export type SessionState = "spawning" | "initializing" | "ready" | "dead";
+
 export type WorkspaceInfo = {
   id: string;
   name: string;
@@ -57,10 +124,15 @@ export type WorkspaceInfo = {
   parentId?: string | null;
   worktree?: WorktreeInfo | null;
   settings: WorkspaceSettings;
+  lastError?: string | null;
+  monitorOnly?: boolean;
+  description?: string | null;
+  sessionState?: SessionState | null;
 };
 
 export type AppServerEvent = {
   workspace_id: string;
+  threadId?: string;
   message: Record<string, unknown>;
 };
 
@@ -139,6 +211,14 @@ export type OpenAppTarget = {
   args: string[];
 };
 
+export type GlobalSettings = {
+  defaultCodexBin: string | null;
+  theme: ThemePreference;
+  locale: string | null;
+  telemetryEnabled: boolean;
+  maxWorkspaces: number | null;
+};
+
 export type AppSettings = {
   codexBin: string | null;
   codexArgs: string | null;
@@ -197,6 +277,7 @@ export type AppSettings = {
   workspaceGroups: WorkspaceGroup[];
   openAppTargets: OpenAppTarget[];
   selectedOpenAppId: string;
+  eventLogEnabled?: boolean;
 };
 
 export type CodexDoctorResult = {
@@ -257,6 +338,25 @@ export type GitFileStatus = {
   status: string;
   additions: number;
   deletions: number;
+  renameFrom?: string | null;
+};
+
+export type GitStagedFile = {
+  path: string;
+  status: string;
+  renameFrom?: string | null;
+};
+
+export type GitChangedFile = {
+  path: string;
+  status: string;
+};
+
+export type GitPathStatus = {
+  path: string;
+  indexStatus: string | null;
+  worktreeStatus: string | null;
+  renameFrom: string | null;
 };
 
 export type GitFileDiff = {
@@ -268,6 +368,16 @@ export type GitFileDiff = {
   newImageData?: string | null;
   oldImageMime?: string | null;
   newImageMime?: string | null;
+  mode?: GitDiffMode;
+};
+
+export type GitDiffMode = "staged" | "unstaged" | "combined";
+
+export type GitDiffHunkPage = {
+  path: string;
+  hunks: string[];
+  totalHunks: number;
+  nextHunkCursor: number | null;
 };
 
 export type GitCommitDiff = {
@@ -289,8 +399,116 @@ export type GitLogEntry = {
   timestamp: number;
 };
 
+export type GitStatusChunk = {
+  files: GitFileStatus[];
+  stagedFiles: GitFileStatus[];
+  unstagedFiles: GitFileStatus[];
+  totalAdditions: number;
+  totalDeletions: number;
+  done: boolean;
+  branchName?: string;
+};
+
+export type GitCommitFileStat = {
+  path: string;
+  status: string;
+  additions: number;
+  deletions: number;
+};
+
+export type ProjectLanguageStat = {
+  extension: string;
+  files: number;
+  lines: number;
+};
+
+export type ProjectSummary = {
+  trackedFiles: number;
+  totalLines: number;
+  languages: ProjectLanguageStat[];
+  latestCommitTimestamp: number | null;
+};
+
+export type EditorConfigDefaults = {
+  endOfLine: string | null;
+  indentStyle: string | null;
+  indentSize: string | null;
+};
+
+export type RepoConventions = {
+  sampledFiles: number;
+  lfFiles: number;
+  crlfFiles: number;
+  mixedFiles: number;
+  lineEnding: "lf" | "crlf" | null;
+  indentStyle: "tabs" | "spaces" | null;
+  indentWidth: number | null;
+  coreAutocrlf: string | null;
+  editorconfig: EditorConfigDefaults | null;
+};
+
+export type GitRemoteInfo = {
+  name: string;
+  url: string | null;
+};
+
+export type GitStashEntry = {
+  index: number;
+  message: string;
+  sha: string;
+};
+
+export type GitSignatureInfo = {
+  name: string;
+  email: string;
+  timestamp: number;
+};
+
+export type GitOperationState = {
+  state: string;
+  inProgress: boolean;
+  detail: string | null;
+  conflictedPaths: string[];
+};
+
+export type GitReviewBundle = {
+  branchName: string;
+  headSha: string | null;
+  files: GitFileStatus[];
+  totalAdditions: number;
+  totalDeletions: number;
+  patch: string;
+};
+
+export type GitResetMode = "soft" | "mixed" | "hard";
+
+export type GitResetResult = {
+  sha: string;
+  affectedFiles: number;
+};
+
+export type GitReflogEntry = {
+  oldSha: string;
+  newSha: string;
+  committer: GitSignatureInfo;
+  message: string;
+};
+
+export type GitCommitDetails = {
+  sha: string;
+  summary: string;
+  message: string;
+  author: GitSignatureInfo;
+  committer: GitSignatureInfo;
+  parents: string[];
+  filesChanged: number;
+  insertions: number;
+  deletions: number;
+};
+
 export type GitLogResponse = {
-  total: number;
+  total: number | null;
+  nextCursor: string | null;
   entries: GitLogEntry[];
   ahead: number;
   behind: number;
@@ -449,6 +667,23 @@ export type ModelOption = {
   isDefault: boolean;
 };
 
+export type ThreadPreferences = {
+  model: string | null;
+  effort: string | null;
+};
+
+export type ModelInfo = {
+  id: string;
+  model: string;
+  displayName: string;
+  contextWindow: number | null;
+  supportedEfforts: string[];
+  defaultEffort: string | null;
+  isDefault: boolean;
+  selectable: boolean;
+  raw: Record<string, unknown>;
+};
+
 export type CollaborationModeOption = {
   id: string;
   label: string;
@@ -488,6 +723,15 @@ export type BranchInfo = {
   lastCommit: number;
 };
 
+export type GitBranchInfo = {
+  name: string;
+  isRemote: boolean;
+  isHead: boolean;
+  upstream: string | null;
+  lastCommitSha: string | null;
+  lastCommit: number;
+};
+
 export type DebugEntry = {
   id: string;
   timestamp: number;